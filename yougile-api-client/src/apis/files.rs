@@ -13,13 +13,33 @@ pub async fn upload_file(
     configuration: &Configuration,
     file_data: Vec<u8>,
     file_name: &str,
+) -> Result<FileUpload, YougileError> {
+    upload(configuration, file_data, file_name, None).await
+}
+
+/// Загружает файл с указанным MIME-типом и возвращает его URL.
+pub async fn upload_file_with_type(
+    configuration: &Configuration,
+    file_data: Vec<u8>,
+    file_name: &str,
+    mime_type: &str,
+) -> Result<FileUpload, YougileError> {
+    upload(configuration, file_data, file_name, Some(mime_type)).await
+}
+
+async fn upload(
+    configuration: &Configuration,
+    file_data: Vec<u8>,
+    file_name: &str,
+    mime_type: Option<&str>,
 ) -> Result<FileUpload, YougileError> {
     let url = format!("{}{}", configuration.base_path, UPLOAD_FILE_PATH);
 
-    let form = reqwest::multipart::Form::new().part(
-        "file",
-        reqwest::multipart::Part::bytes(file_data).file_name(file_name.to_string()),
-    );
+    let mut part = reqwest::multipart::Part::bytes(file_data).file_name(file_name.to_string());
+    if let Some(mime_type) = mime_type {
+        part = part.mime_str(mime_type)?;
+    }
+    let form = reqwest::multipart::Form::new().part("file", part);
 
     let resp = configuration
         .client
@@ -481,6 +481,21 @@ impl YouGileClient {
         crate::apis::files::upload_file(&self.configuration, file_data, file_name).await
     }
 
+    pub async fn upload_file_with_type(
+        &self,
+        file_data: Vec<u8>,
+        file_name: &str,
+        mime_type: &str,
+    ) -> Result<FileUpload, YougileError> {
+        crate::apis::files::upload_file_with_type(
+            &self.configuration,
+            file_data,
+            file_name,
+            mime_type,
+        )
+        .await
+    }
+
     // Sticker methods (sprint stickers)
     pub async fn create_sprint_sticker(
         &self,
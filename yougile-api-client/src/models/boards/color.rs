@@ -0,0 +1,207 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Цвет колонки. В API передаётся числом от 1 до 16
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum ColumnColor {
+    /// 1 - #7B869E
+    #[default]
+    Gray,
+    /// 2 - #FF8C8C
+    Red,
+    /// 3 - #E9A24F
+    Orange,
+    /// 4 - #FCE258
+    Yellow,
+    /// 5 - #7CAE5E
+    Green,
+    /// 6 - #49C5BC
+    Turquoise,
+    /// 7 - #8CACFF
+    Blue,
+    /// 8 - #CC8CFF
+    Violet,
+    /// 9 - #667085
+    DarkGray,
+    /// 10 - #EB3737
+    BrightRed,
+    /// 11 - #F2732B
+    BrightOrange,
+    /// 12 - #F5CC00
+    BrightYellow,
+    /// 13 - #5CDC11
+    BrightGreen,
+    /// 14 - #08A7A9
+    BrightTurquoise,
+    /// 15 - #5089F2
+    BrightBlue,
+    /// 16 - #E25EF2
+    BrightViolet,
+    /// Значение, неизвестное этой версии клиента
+    Unknown(String),
+}
+
+impl ColumnColor {
+    /// Все известные цвета в порядке номеров API
+    pub const ALL: [ColumnColor; 16] = [
+        ColumnColor::Gray,
+        ColumnColor::Red,
+        ColumnColor::Orange,
+        ColumnColor::Yellow,
+        ColumnColor::Green,
+        ColumnColor::Turquoise,
+        ColumnColor::Blue,
+        ColumnColor::Violet,
+        ColumnColor::DarkGray,
+        ColumnColor::BrightRed,
+        ColumnColor::BrightOrange,
+        ColumnColor::BrightYellow,
+        ColumnColor::BrightGreen,
+        ColumnColor::BrightTurquoise,
+        ColumnColor::BrightBlue,
+        ColumnColor::BrightViolet,
+    ];
+
+    /// Цвет по номеру из API (1-16)
+    pub fn from_index(index: u8) -> ColumnColor {
+        match index {
+            1..=16 => ColumnColor::ALL[index as usize - 1].clone(),
+            other => ColumnColor::Unknown(other.to_string()),
+        }
+    }
+
+    /// Номер цвета в API (1-16)
+    pub fn index(&self) -> Option<u8> {
+        ColumnColor::ALL
+            .iter()
+            .position(|color| color == self)
+            .map(|position| position as u8 + 1)
+    }
+
+    /// RGB-компоненты цвета для отрисовки
+    pub fn rgb(&self) -> Option<(u8, u8, u8)> {
+        match self {
+            ColumnColor::Gray => Some((0x7B, 0x86, 0x9E)),
+            ColumnColor::Red => Some((0xFF, 0x8C, 0x8C)),
+            ColumnColor::Orange => Some((0xE9, 0xA2, 0x4F)),
+            ColumnColor::Yellow => Some((0xFC, 0xE2, 0x58)),
+            ColumnColor::Green => Some((0x7C, 0xAE, 0x5E)),
+            ColumnColor::Turquoise => Some((0x49, 0xC5, 0xBC)),
+            ColumnColor::Blue => Some((0x8C, 0xAC, 0xFF)),
+            ColumnColor::Violet => Some((0xCC, 0x8C, 0xFF)),
+            ColumnColor::DarkGray => Some((0x66, 0x70, 0x85)),
+            ColumnColor::BrightRed => Some((0xEB, 0x37, 0x37)),
+            ColumnColor::BrightOrange => Some((0xF2, 0x73, 0x2B)),
+            ColumnColor::BrightYellow => Some((0xF5, 0xCC, 0x00)),
+            ColumnColor::BrightGreen => Some((0x5C, 0xDC, 0x11)),
+            ColumnColor::BrightTurquoise => Some((0x08, 0xA7, 0xA9)),
+            ColumnColor::BrightBlue => Some((0x50, 0x89, 0xF2)),
+            ColumnColor::BrightViolet => Some((0xE2, 0x5E, 0xF2)),
+            ColumnColor::Unknown(_) => None,
+        }
+    }
+
+    /// Цвет в виде строки `#RRGGBB`
+    pub fn hex(&self) -> Option<String> {
+        self.rgb()
+            .map(|(r, g, b)| format!("#{:02X}{:02X}{:02X}", r, g, b))
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, ColumnColor::Unknown(_))
+    }
+}
+
+impl From<u8> for ColumnColor {
+    fn from(index: u8) -> Self {
+        ColumnColor::from_index(index)
+    }
+}
+
+impl fmt::Display for ColumnColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.index(), self) {
+            (Some(index), _) => write!(f, "{}", index),
+            (None, ColumnColor::Unknown(value)) => f.write_str(value),
+            (None, _) => Ok(()),
+        }
+    }
+}
+
+impl Serialize for ColumnColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ColumnColor::Unknown(value) => match value.parse::<f64>() {
+                Ok(number) => serializer.serialize_f64(number),
+                Err(_) => serializer.serialize_str(value),
+            },
+            known => serializer.serialize_u8(known.index().unwrap_or_default()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ColumnColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawColor {
+            Number(f64),
+            Text(String),
+        }
+
+        Ok(match RawColor::deserialize(deserializer)? {
+            RawColor::Number(number) if number.fract() == 0.0 && (1.0..=16.0).contains(&number) => {
+                ColumnColor::from_index(number as u8)
+            }
+            RawColor::Number(number) => ColumnColor::Unknown(number.to_string()),
+            RawColor::Text(text) => match text.parse::<u8>() {
+                Ok(index @ 1..=16) => ColumnColor::from_index(index),
+                _ => ColumnColor::Unknown(text),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_colors_round_trip_as_numbers() {
+        for (position, color) in ColumnColor::ALL.into_iter().enumerate() {
+            let json = serde_json::to_string(&color).unwrap();
+            assert_eq!(json, (position + 1).to_string());
+            assert_eq!(serde_json::from_str::<ColumnColor>(&json).unwrap(), color);
+        }
+        assert_eq!(
+            serde_json::from_str::<ColumnColor>("7.0").unwrap(),
+            ColumnColor::Blue
+        );
+        assert_eq!(
+            serde_json::from_str::<ColumnColor>("\"16\"").unwrap(),
+            ColumnColor::BrightViolet
+        );
+    }
+
+    #[test]
+    fn unknown_values_are_kept() {
+        let number: ColumnColor = serde_json::from_str("17").unwrap();
+        assert_eq!(number, ColumnColor::Unknown("17".to_string()));
+        assert_eq!(serde_json::to_string(&number).unwrap(), "17.0");
+
+        let fraction: ColumnColor = serde_json::from_str("2.5").unwrap();
+        assert_eq!(fraction, ColumnColor::Unknown("2.5".to_string()));
+        assert_eq!(fraction.rgb(), None);
+
+        let text: ColumnColor = serde_json::from_str("\"teal\"").unwrap();
+        assert_eq!(text, ColumnColor::Unknown("teal".to_string()));
+        assert_eq!(serde_json::to_string(&text).unwrap(), "\"teal\"");
+    }
+
+    #[test]
+    fn hex_matches_the_api_palette() {
+        assert_eq!(ColumnColor::Gray.hex().as_deref(), Some("#7B869E"));
+        assert_eq!(ColumnColor::BrightYellow.hex().as_deref(), Some("#F5CC00"));
+        assert_eq!(ColumnColor::Unknown("0".to_string()).hex(), None);
+    }
+}
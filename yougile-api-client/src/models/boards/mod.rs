@@ -1,8 +1,10 @@
 mod board;
+mod color;
 mod column;
 mod permissions;
 
 pub use board::*;
+pub use color::*;
 pub use column::*;
 pub use permissions::*;
 
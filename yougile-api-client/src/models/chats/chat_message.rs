@@ -1,5 +1,5 @@
+use crate::models::chats::{React, Reactions};
 use crate::models::{self, PagingMetadata, common::Page};
-use crate::models::chats::Reactions;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
@@ -100,34 +100,6 @@ impl UpdateChatMessage {
     }
 }
 
-/// Список реакций админа
-#[derive(
-    Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Default,
-)]
-pub enum React {
-    #[serde(rename = "👍")]
-    #[default]
-    ThumbsUp,
-    #[serde(rename = "👎")]
-    ThumbsDown,
-    #[serde(rename = "👏")]
-    Clap,
-    #[serde(rename = "🙂")]
-    SlightlySmiling,
-    #[serde(rename = "😀")]
-    Grinning,
-    #[serde(rename = "😕")]
-    Confused,
-    #[serde(rename = "🎉")]
-    Tada,
-    #[serde(rename = "❤")]
-    Heart,
-    #[serde(rename = "🚀")]
-    Rocket,
-    #[serde(rename = "✔")]
-    CheckMark,
-}
-
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChatId {
     /// ID сообщения, также является временем создания
@@ -140,3 +112,28 @@ impl ChatId {
         ChatId { id }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_sends_a_single_react() {
+        let update = UpdateChatMessage {
+            react: Some(React::Other("🦀".to_string())),
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_string(&update).unwrap(), r#"{"react":"🦀"}"#);
+        assert_eq!(
+            serde_json::from_str::<UpdateChatMessage>(r#"{"react":"🚀"}"#).unwrap(),
+            UpdateChatMessage {
+                react: Some(React::Rocket),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&UpdateChatMessage::new()).unwrap(),
+            "{}"
+        );
+    }
+}
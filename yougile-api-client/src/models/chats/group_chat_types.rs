@@ -1,23 +1,25 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-// Type for the users field in GroupChat - likely a list of user IDs
+/// Сотрудники в чате
 pub type UsersList = Vec<String>;
 
-// Type for user_role_map - mapping user IDs to role IDs
+/// Роли сотрудников в чате: ID сотрудника — ID роли
 pub type UserRoleMap = HashMap<String, String>;
 
-// Type for role_config_map - mapping role IDs to role configurations
+/// Настройки ролей: ID роли — настройки
+pub type RoleConfigMap = HashMap<String, RoleConfig>;
+
+/// Настройки роли в групповом чате
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct RoleConfig {
     #[serde(rename = "title")]
     pub title: String,
+    /// Права роли; их набор не описан в спецификации, поэтому они хранятся как есть
     #[serde(rename = "permissions", skip_serializing_if = "Option::is_none")]
-    pub permissions: Option<HashMap<String, serde_json::Value>>, // Keep as Value for now to avoid nested complexity
+    pub permissions: Option<HashMap<String, serde_json::Value>>,
 }
 
-pub type RoleConfigMap = HashMap<String, RoleConfig>;
-
 impl RoleConfig {
     pub fn new(title: String) -> Self {
         Self {
@@ -25,5 +27,35 @@ impl RoleConfig {
             permissions: None,
         }
     }
+
+    pub fn with_permissions(
+        title: String,
+        permissions: HashMap<String, serde_json::Value>,
+    ) -> Self {
+        Self {
+            title,
+            permissions: Some(permissions),
+        }
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permissions_are_kept_as_is() {
+        let json = r#"{"title":"Admin","permissions":{"canEdit":true,"limits":{"max":3}}}"#;
+        let config: RoleConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.title, "Admin");
+        assert_eq!(
+            config.permissions.as_ref().unwrap()["limits"],
+            serde_json::json!({"max": 3})
+        );
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::from_str::<serde_json::Value>(json).unwrap()
+        );
+    }
+}
@@ -1,10 +1,110 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
+use std::fmt;
 
+/// Реакция на сообщение
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum React {
+    #[default]
+    ThumbsUp,
+    ThumbsDown,
+    Clap,
+    SlightlySmiling,
+    Grinning,
+    Confused,
+    Tada,
+    Heart,
+    Rocket,
+    CheckMark,
+    /// Эмодзи, неизвестный этой версии клиента
+    Other(String),
+}
+
+impl React {
+    /// Все реакции, доступные в YouGile
+    pub const ALL: [React; 10] = [
+        React::ThumbsUp,
+        React::ThumbsDown,
+        React::Clap,
+        React::SlightlySmiling,
+        React::Grinning,
+        React::Confused,
+        React::Tada,
+        React::Heart,
+        React::Rocket,
+        React::CheckMark,
+    ];
+
+    /// Эмодзи реакции
+    pub fn as_str(&self) -> &str {
+        match self {
+            React::ThumbsUp => "👍",
+            React::ThumbsDown => "👎",
+            React::Clap => "👏",
+            React::SlightlySmiling => "🙂",
+            React::Grinning => "😀",
+            React::Confused => "😕",
+            React::Tada => "🎉",
+            React::Heart => "❤",
+            React::Rocket => "🚀",
+            React::CheckMark => "✔",
+            React::Other(value) => value,
+        }
+    }
+
+    pub fn is_other(&self) -> bool {
+        matches!(self, React::Other(_))
+    }
+}
+
+impl From<&str> for React {
+    fn from(value: &str) -> Self {
+        // Эмодзи может приходить с селектором варианта U+FE0F
+        match value.trim_end_matches('\u{FE0F}') {
+            "👍" => React::ThumbsUp,
+            "👎" => React::ThumbsDown,
+            "👏" => React::Clap,
+            "🙂" => React::SlightlySmiling,
+            "😀" => React::Grinning,
+            "😕" => React::Confused,
+            "🎉" => React::Tada,
+            "❤" => React::Heart,
+            "🚀" => React::Rocket,
+            "✔" => React::CheckMark,
+            _ => React::Other(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for React {
+    fn from(value: String) -> Self {
+        React::from(value.as_str())
+    }
+}
+
+impl fmt::Display for React {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for React {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for React {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(React::from)
+    }
+}
+
+/// Реакции на сообщение: пользователи, поставившие каждую реакцию
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct Reactions {
     #[serde(flatten)]
-    pub reactions: HashMap<String, Vec<String>>,
+    pub reactions: HashMap<React, Vec<String>>,
 }
 
 impl Reactions {
@@ -14,8 +114,93 @@ impl Reactions {
         }
     }
 
-    pub fn add_reaction(&mut self, emoji: String, user_ids: Vec<String>) {
-        self.reactions.insert(emoji, user_ids);
+    pub fn add_reaction(&mut self, react: React, user_ids: Vec<String>) {
+        self.reactions.insert(react, user_ids);
+    }
+
+    /// ID пользователей, поставивших реакцию
+    pub fn users(&self, react: &React) -> &[String] {
+        self.reactions.get(react).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn count(&self, react: &React) -> usize {
+        self.users(react).len()
+    }
+
+    /// Общее число реакций на сообщение
+    pub fn total(&self) -> usize {
+        self.reactions.values().map(Vec::len).sum()
+    }
+
+    pub fn has_reacted(&self, react: &React, user_id: &str) -> bool {
+        self.users(react).iter().any(|id| id == user_id)
+    }
+
+    /// Реакции, поставленные пользователем
+    pub fn of_user(&self, user_id: &str) -> Vec<React> {
+        let mut reacts: Vec<React> = self
+            .reactions
+            .iter()
+            .filter(|(_, users)| users.iter().any(|id| id == user_id))
+            .map(|(react, _)| react.clone())
+            .collect();
+        reacts.sort();
+        reacts
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&React, &[String])> {
+        self.reactions
+            .iter()
+            .map(|(react, users)| (react, users.as_slice()))
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_reacts_round_trip() {
+        for react in React::ALL {
+            let json = serde_json::to_string(&react).unwrap();
+            assert_eq!(serde_json::from_str::<React>(&json).unwrap(), react);
+        }
+        assert_eq!(React::from("❤\u{FE0F}"), React::Heart);
+    }
+
+    #[test]
+    fn other_reacts_round_trip() {
+        for emoji in ["🦀", "👍🏽", "👩\u{200D}💻", "🏳\u{FE0F}\u{200D}🌈"] {
+            let react = React::from(emoji);
+            assert_eq!(react, React::Other(emoji.to_string()));
+            assert_eq!(
+                serde_json::to_string(&react).unwrap(),
+                format!("\"{}\"", emoji)
+            );
+            assert_eq!(
+                serde_json::from_str::<React>(&format!("\"{}\"", emoji)).unwrap(),
+                react
+            );
+        }
+    }
+
+    #[test]
+    fn reactions_are_keyed_by_react() {
+        let reactions: Reactions =
+            serde_json::from_str(r#"{"👍": ["u1", "u2"], "🦀": ["u1"], "🚀": []}"#).unwrap();
+
+        assert_eq!(reactions.count(&React::ThumbsUp), 2);
+        assert_eq!(reactions.users(&React::Other("🦀".to_string())), ["u1"]);
+        assert_eq!(reactions.total(), 3);
+        assert!(reactions.has_reacted(&React::ThumbsUp, "u2"));
+        assert_eq!(
+            reactions.of_user("u1"),
+            [React::ThumbsUp, React::Other("🦀".to_string())]
+        );
+        assert!(Reactions::new().is_empty());
+    }
+}
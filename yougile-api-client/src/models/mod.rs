@@ -2,13 +2,14 @@ pub mod auth;
 pub use self::auth::{AuthCredentials, AuthKey, AuthKeyWithDetails};
 pub mod boards;
 pub use self::boards::{
-    Board, BoardList, BoardPermissions, Column, ColumnList, CreateBoard, CreateColumn, UpdateBoard,
-    UpdateColumn,
+    Board, BoardList, BoardPermissions, Column, ColumnColor, ColumnList, ColumnPermissions,
+    CreateBoard, CreateColumn, UpdateBoard, UpdateColumn,
 };
 pub mod chats;
 pub use self::chats::{
     ChatId, ChatMessage, ChatMessageList, CreateChatMessage, CreateGroupChat, GroupChat,
-    GroupChatList, React, UpdateChatMessage, UpdateGroupChat,
+    GroupChatList, React, Reactions, RoleConfig, RoleConfigMap,
+    UpdateChatMessage, UpdateGroupChat, UserRoleMap,
 };
 pub mod common;
 pub use self::common::{Company, CompanyList, Id, PagingMetadata, UpdateCompany};
@@ -26,8 +27,9 @@ pub use self::stickers::*;
 
 pub mod tasks;
 pub use self::tasks::{
-    AssignUsers, CheckList, CreateTask, Deadline, EditSubtasks, EditWhoToNotify, Move, Stopwatch,
-    Task, TaskChatSubscribers, TaskList, TaskPermissions, TimeTracking, Timer, UpdateTask,
+    AssignUsers, CheckList, CheckListItem, CreateTask, Deadline, EditSubtasks, EditWhoToNotify,
+    Move, Stopwatch, Task, TaskChatSubscribers, TaskColor, TaskList, TaskPermissions, TimeTracking,
+    Timer, UpdateDeadline, UpdateStopwatch, UpdateTask, UpdateTimer,
 };
 pub mod users;
 pub use self::users::{CreateUser, UpdateUser, User, UserList};
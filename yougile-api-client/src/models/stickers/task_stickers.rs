@@ -13,6 +13,10 @@ pub enum StickerValue {
     Unassigned,       // For unassigned stickers (represented by "-" in API)
 }
 
+/// Значение "-", которым API сбрасывает поле: открепляет стикер от задачи,
+/// убирает пользователя из проекта, отдела или чата, делает отдел корневым
+pub const UNSET: &str = "-";
+
 pub type TaskStickers = HashMap<String, StickerValue>;
 
 impl Default for StickerValue {
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Цвет карточки задачи на доске
+///
+/// В отличие от цветов колонок, API не публикует RGB-значения этих цветов.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum TaskColor {
+    #[default]
+    Primary,
+    Gray,
+    Red,
+    Pink,
+    Yellow,
+    Green,
+    Turquoise,
+    Blue,
+    Violet,
+    /// Цвет, неизвестный этой версии клиента
+    Unknown(String),
+}
+
+impl TaskColor {
+    /// Все известные цвета в порядке палитры YouGile
+    pub const ALL: [TaskColor; 9] = [
+        TaskColor::Primary,
+        TaskColor::Gray,
+        TaskColor::Red,
+        TaskColor::Pink,
+        TaskColor::Yellow,
+        TaskColor::Green,
+        TaskColor::Turquoise,
+        TaskColor::Blue,
+        TaskColor::Violet,
+    ];
+
+    /// Значение цвета в API, например `task-red`
+    pub fn as_str(&self) -> &str {
+        match self {
+            TaskColor::Primary => "task-primary",
+            TaskColor::Gray => "task-gray",
+            TaskColor::Red => "task-red",
+            TaskColor::Pink => "task-pink",
+            TaskColor::Yellow => "task-yellow",
+            TaskColor::Green => "task-green",
+            TaskColor::Turquoise => "task-turquoise",
+            TaskColor::Blue => "task-blue",
+            TaskColor::Violet => "task-violet",
+            TaskColor::Unknown(value) => value,
+        }
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, TaskColor::Unknown(_))
+    }
+}
+
+impl From<&str> for TaskColor {
+    fn from(value: &str) -> Self {
+        match value {
+            "task-primary" => TaskColor::Primary,
+            "task-gray" => TaskColor::Gray,
+            "task-red" => TaskColor::Red,
+            "task-pink" => TaskColor::Pink,
+            "task-yellow" => TaskColor::Yellow,
+            "task-green" => TaskColor::Green,
+            "task-turquoise" => TaskColor::Turquoise,
+            "task-blue" => TaskColor::Blue,
+            "task-violet" => TaskColor::Violet,
+            other => TaskColor::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for TaskColor {
+    fn from(value: String) -> Self {
+        TaskColor::from(value.as_str())
+    }
+}
+
+impl fmt::Display for TaskColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for TaskColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TaskColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(TaskColor::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_colors_round_trip() {
+        for color in TaskColor::ALL {
+            let json = serde_json::to_string(&color).unwrap();
+            assert_eq!(json, format!("\"{}\"", color.as_str()));
+            assert_eq!(serde_json::from_str::<TaskColor>(&json).unwrap(), color);
+        }
+    }
+
+    #[test]
+    fn unknown_color_is_kept() {
+        let color: TaskColor = serde_json::from_str("\"task-orange\"").unwrap();
+        assert_eq!(color, TaskColor::Unknown("task-orange".to_string()));
+        assert!(color.is_unknown());
+        assert_eq!(serde_json::to_string(&color).unwrap(), "\"task-orange\"");
+    }
+}
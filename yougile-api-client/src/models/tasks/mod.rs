@@ -1,4 +1,5 @@
 mod check_list;
+mod color;
 mod deadline;
 mod permissions;
 mod stopwatch;
@@ -7,6 +8,7 @@ mod time_tracking;
 mod timer;
 
 pub use check_list::*;
+pub use color::*;
 pub use deadline::*;
 pub use permissions::*;
 pub use stopwatch::*;
@@ -3,8 +3,8 @@ use crate::models::{
     common::Page,
     stickers::{StickerValue, TaskStickers},
     tasks::{
-        CreateStopwatch, CreateTimer, UpdateDeadline, UpdateStopwatch, UpdateTimeTracking,
-        UpdateTimer,
+        CreateStopwatch, CreateTimer, TaskColor, UpdateDeadline, UpdateStopwatch,
+        UpdateTimeTracking, UpdateTimer,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -64,7 +64,7 @@ pub struct Task {
     pub stickers: Option<TaskStickers>,
     /// Цвет карточки задач на доске, доступны цвета: task-primary, task-gray, task-red, task-pink, task-yellow, task-green, task-turquoise, task-blue, task-violet
     #[serde(rename = "color", skip_serializing_if = "Option::is_none")]
-    pub color: Option<String>,
+    pub color: Option<TaskColor>,
     /// ID задачи, сквозной через всю компанию
     #[serde(rename = "idTaskCommon", skip_serializing_if = "Option::is_none")]
     pub id_task_common: Option<String>,
@@ -143,7 +143,7 @@ pub struct CreateTask {
     pub stickers: Option<TaskStickers>,
     /// Цвет карточки задач на доске, доступны цвета: task-primary, task-gray, task-red, task-pink, task-yellow, task-green, task-turquoise, task-blue, task-violet
     #[serde(rename = "color", skip_serializing_if = "Option::is_none")]
-    pub color: Option<String>,
+    pub color: Option<TaskColor>,
     /// ID задачи, сквозной через всю компанию
     #[serde(rename = "idTaskCommon", skip_serializing_if = "Option::is_none")]
     pub id_task_common: Option<String>,
@@ -221,7 +221,7 @@ pub struct UpdateTask {
     pub stickers: Option<TaskStickers>,
     /// Цвет карточки задач на доске, доступны цвета: task-primary, task-gray, task-red, task-pink, task-yellow, task-green, task-turquoise, task-blue, task-violet
     #[serde(rename = "color", skip_serializing_if = "Option::is_none")]
-    pub color: Option<String>,
+    pub color: Option<TaskColor>,
     /// ID задачи, сквозной через всю компанию
     #[serde(rename = "idTaskCommon", skip_serializing_if = "Option::is_none")]
     pub id_task_common: Option<String>,
@@ -1,17 +1,25 @@
 use crate::{
     SDKError,
+    directory::Directory,
+    references::ReferenceResolver,
+    reminders::DeadlineReminders,
     resources::{
         AuthAPI, BoardsAPI, ChatsAPI, ColumnsAPI, DepartmentsAPI, FilesAPI, GroupChatsAPI,
-        ProjectsAPI, StickersAPI, TasksAPI, UsersAPI, WebhooksAPI,
+        ProjectsAPI, SprintsAPI, StickersAPI, TasksAPI, UsersAPI, WebhooksAPI,
     },
+    scheduler::Scheduler,
 };
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use yougile_api_client::{YouGileClient, apis::configuration::Configuration, models::Company};
 
 /// Builder for creating a YouGileSDK instance
 pub struct YouGileSDKBuilder {
     token: Option<String>,
     base_url: Option<String>,
+    directory_ttl: Option<Duration>,
+    user_id: Option<String>,
 }
 
 impl YouGileSDKBuilder {
@@ -19,6 +27,8 @@ impl YouGileSDKBuilder {
         Self {
             token: None,
             base_url: None,
+            directory_ttl: None,
+            user_id: None,
         }
     }
 
@@ -34,6 +44,18 @@ impl YouGileSDKBuilder {
         self
     }
 
+    /// Set how long the directory keeps loaded objects before reloading them
+    pub fn directory_ttl(mut self, ttl: Duration) -> Self {
+        self.directory_ttl = Some(ttl);
+        self
+    }
+
+    /// Set the ID of the user the token belongs to, used for `assignee:me` in task queries
+    pub fn user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
     /// Build the YouGileSDK client
     pub fn build(self) -> Result<YouGileSDK, SDKError> {
         let token = self
@@ -48,7 +70,16 @@ impl YouGileSDKBuilder {
 
         let client = Arc::new(YouGileClient::new(config));
 
-        Ok(YouGileSDK { client })
+        let mut directory = Directory::new(client.clone());
+        if let Some(ttl) = self.directory_ttl {
+            directory = directory.with_ttl(ttl);
+        }
+
+        Ok(YouGileSDK {
+            client,
+            directory: Arc::new(directory),
+            user_id: self.user_id,
+        })
     }
 }
 
@@ -61,6 +92,8 @@ impl Default for YouGileSDKBuilder {
 /// The main YouGile SDK client with a high-level API
 pub struct YouGileSDK {
     client: Arc<YouGileClient>,
+    directory: Arc<Directory>,
+    user_id: Option<String>,
 }
 
 impl YouGileSDK {
@@ -76,7 +109,11 @@ impl YouGileSDK {
 
     /// Get access to the tasks API
     pub fn tasks(&self) -> TasksAPI {
-        TasksAPI::new(self.client.clone())
+        let tasks = TasksAPI::new(self.client.clone()).with_directory(self.directory.clone());
+        match &self.user_id {
+            Some(user_id) => tasks.with_me(user_id),
+            None => tasks,
+        }
     }
 
     /// Get access to the projects API
@@ -119,6 +156,11 @@ impl YouGileSDK {
         StickersAPI::new(self.client.clone())
     }
 
+    /// Get access to the sprints API
+    pub fn sprints(&self) -> SprintsAPI {
+        SprintsAPI::new(self.client.clone())
+    }
+
     /// Get access to the files API
     pub fn files(&self) -> FilesAPI {
         FilesAPI::new(self.client.clone())
@@ -129,6 +171,31 @@ impl YouGileSDK {
         WebhooksAPI::new(self.client.clone())
     }
 
+    /// Get the shared directory used for name and ID lookups
+    pub fn directory(&self) -> Arc<Directory> {
+        self.directory.clone()
+    }
+
+    /// Create a recurring task scheduler that keeps its state in `state_path`
+    pub async fn scheduler(&self, state_path: impl AsRef<Path>) -> Result<Scheduler, SDKError> {
+        Scheduler::load(self.client.clone(), state_path).await
+    }
+
+    /// Get a resolver for mentions and task references, backed by the shared directory
+    pub fn references(&self) -> ReferenceResolver {
+        ReferenceResolver::new(self.client.clone(), self.directory.clone())
+    }
+
+    /// Create a deadline reminder job that keeps its state in `state_path`
+    pub async fn reminders(
+        &self,
+        state_path: impl AsRef<Path>,
+    ) -> Result<DeadlineReminders, SDKError> {
+        Ok(DeadlineReminders::load(self.client.clone(), state_path)
+            .await?
+            .directory(self.directory.clone()))
+    }
+
     /// Get the company information
     pub async fn get_company(&self) -> Result<Company, SDKError> {
         self.client.get_company().await.map_err(SDKError::from)
@@ -0,0 +1,551 @@
+//! Task dependency graph built from deadline links
+//!
+//! A task's `deadline.links` lists the tasks that follow it on the Gantt chart:
+//! if task A links to task B, B should not start before A's deadline. Tasks
+//! without a start date are treated as milestones at their deadline.
+//!
+//! `deadline.blockedPoints` is not taken into account. The API only describes
+//! it as the points (start or end) that block the deadline date, without
+//! saying how they interact with links, so dates are used as they are.
+
+use crate::SDKError;
+use std::collections::{HashMap, HashSet};
+
+use yougile_api_client::models::*;
+
+/// Dependency graph over a set of tasks
+#[derive(Clone, Debug, Default)]
+pub struct DependencyGraph {
+    tasks: HashMap<String, Task>,
+    /// Task IDs in the order they were added
+    order: Vec<String>,
+    successors: HashMap<String, Vec<String>>,
+    predecessors: HashMap<String, Vec<String>>,
+    /// Links to tasks outside of the graph, as (from, to)
+    external: Vec<(String, String)>,
+}
+
+/// A successor whose start is earlier than the deadline of its predecessor
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    pub predecessor_id: String,
+    pub successor_id: String,
+    pub predecessor_deadline: f64,
+    pub successor_start: f64,
+}
+
+impl Violation {
+    /// How much the successor starts too early, in milliseconds
+    pub fn overlap(&self) -> f64 {
+        self.predecessor_deadline - self.successor_start
+    }
+}
+
+/// Earliest and latest dates of a task given its dependencies, in milliseconds
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduledTask {
+    pub task_id: String,
+    pub earliest_start: f64,
+    pub earliest_finish: f64,
+    pub latest_start: f64,
+    pub latest_finish: f64,
+}
+
+impl ScheduledTask {
+    /// How far the task can slip without delaying the end of the schedule
+    pub fn slack(&self) -> f64 {
+        self.latest_start - self.earliest_start
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.slack() <= 0.0
+    }
+}
+
+/// Result of the critical path computation
+#[derive(Clone, Debug, Default)]
+pub struct Schedule {
+    pub tasks: HashMap<String, ScheduledTask>,
+    /// Longest chain of dependent tasks, first to last
+    pub critical_path: Vec<String>,
+    pub start: f64,
+    pub end: f64,
+}
+
+impl DependencyGraph {
+    pub fn new(tasks: impl IntoIterator<Item = Task>) -> Self {
+        let mut graph = Self::default();
+        for task in tasks {
+            if graph.tasks.contains_key(&task.id) {
+                continue;
+            }
+            graph.order.push(task.id.clone());
+            graph.tasks.insert(task.id.clone(), task);
+        }
+
+        for from in &graph.order {
+            let links = graph.tasks[from]
+                .deadline
+                .as_ref()
+                .and_then(|deadline| deadline.links.clone())
+                .unwrap_or_default();
+            for to in links {
+                if !graph.tasks.contains_key(&to) {
+                    graph.external.push((from.clone(), to));
+                    continue;
+                }
+                let successors = graph.successors.entry(from.clone()).or_default();
+                if successors.contains(&to) {
+                    continue;
+                }
+                successors.push(to.clone());
+                graph.predecessors.entry(to).or_default().push(from.clone());
+            }
+        }
+
+        graph
+    }
+
+    pub fn task(&self, task_id: &str) -> Option<&Task> {
+        self.tasks.get(task_id)
+    }
+
+    pub fn tasks(&self) -> impl Iterator<Item = &Task> {
+        self.order.iter().map(|id| &self.tasks[id])
+    }
+
+    /// Tasks that must finish before this one starts
+    pub fn predecessors(&self, task_id: &str) -> &[String] {
+        self.predecessors
+            .get(task_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Tasks that start after this one finishes
+    pub fn successors(&self, task_id: &str) -> &[String] {
+        self.successors
+            .get(task_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Links pointing at tasks that were not loaded into the graph, as (from, to)
+    pub fn external_links(&self) -> &[(String, String)] {
+        &self.external
+    }
+
+    /// True if `to` already depends on `from`, directly or transitively,
+    /// so that linking `to` → `from` would create a cycle
+    pub fn depends_on(&self, to: &str, from: &str) -> bool {
+        let mut stack = vec![from];
+        let mut visited = HashSet::new();
+        while let Some(id) = stack.pop() {
+            if id == to {
+                return true;
+            }
+            if visited.insert(id) {
+                stack.extend(self.successors(id).iter().map(String::as_str));
+            }
+        }
+        false
+    }
+
+    /// Groups of tasks that depend on each other in a loop
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        Tarjan::new(self).run()
+    }
+
+    /// Tasks ordered so that every task comes after its predecessors,
+    /// or an error listing a cycle
+    pub fn topological_order(&self) -> Result<Vec<String>, SDKError> {
+        let mut remaining: HashMap<&str, usize> = self
+            .order
+            .iter()
+            .map(|id| (id.as_str(), self.predecessors(id).len()))
+            .collect();
+        let mut ready: Vec<&str> = self
+            .order
+            .iter()
+            .rev()
+            .map(String::as_str)
+            .filter(|id| remaining[id] == 0)
+            .collect();
+        let mut sorted = Vec::with_capacity(self.order.len());
+
+        while let Some(id) = ready.pop() {
+            sorted.push(id.to_string());
+            for successor in self.successors(id).iter().rev() {
+                let count = remaining
+                    .get_mut(successor.as_str())
+                    .expect("successors are part of the graph");
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(successor);
+                }
+            }
+        }
+
+        if sorted.len() < self.order.len() {
+            let cycle = self.cycles().into_iter().next().unwrap_or_default();
+            return Err(SDKError::validation(format!(
+                "Task dependencies contain a cycle: {}",
+                cycle.join(" -> ")
+            )));
+        }
+        Ok(sorted)
+    }
+
+    /// Successors whose start lies before the deadline of a predecessor
+    pub fn violations(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for from in &self.order {
+            let Some(predecessor_deadline) = self.tasks[from]
+                .deadline
+                .as_ref()
+                .map(|deadline| deadline.deadline)
+            else {
+                continue;
+            };
+
+            for to in self.successors(from) {
+                let Some(successor_start) = self.tasks[to].deadline.as_ref().map(start_of) else {
+                    continue;
+                };
+                if successor_start < predecessor_deadline {
+                    violations.push(Violation {
+                        predecessor_id: from.clone(),
+                        successor_id: to.clone(),
+                        predecessor_deadline,
+                        successor_start,
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    /// Earliest and latest start of every task and the critical path
+    ///
+    /// Tasks keep their planned duration (`deadline - start_date`) and never
+    /// start before their planned start. Fails if the dependencies contain a cycle.
+    pub fn schedule(&self) -> Result<Schedule, SDKError> {
+        let order = self.topological_order()?;
+
+        let planned_start = |id: &str| self.tasks[id].deadline.as_ref().map(start_of);
+        let start = self
+            .order
+            .iter()
+            .filter_map(|id| planned_start(id))
+            .fold(f64::INFINITY, f64::min);
+        let start = if start.is_finite() { start } else { 0.0 };
+
+        let mut earliest: HashMap<&str, (f64, f64)> = HashMap::new();
+        for id in &order {
+            let duration = duration_of(&self.tasks[id]);
+            let after_predecessors = self
+                .predecessors(id)
+                .iter()
+                .map(|predecessor| earliest[predecessor.as_str()].1)
+                .fold(start, f64::max);
+            let begin = planned_start(id).map_or(after_predecessors, |planned| {
+                planned.max(after_predecessors)
+            });
+            earliest.insert(id, (begin, begin + duration));
+        }
+
+        let end = earliest
+            .values()
+            .map(|(_, finish)| *finish)
+            .fold(start, f64::max);
+
+        let mut latest: HashMap<&str, (f64, f64)> = HashMap::new();
+        for id in order.iter().rev() {
+            let duration = duration_of(&self.tasks[id]);
+            let finish = self
+                .successors(id)
+                .iter()
+                .map(|successor| latest[successor.as_str()].0)
+                .fold(end, f64::min);
+            latest.insert(id, (finish - duration, finish));
+        }
+
+        let tasks: HashMap<String, ScheduledTask> = order
+            .iter()
+            .map(|id| {
+                let (earliest_start, earliest_finish) = earliest[id.as_str()];
+                let (latest_start, latest_finish) = latest[id.as_str()];
+                let scheduled = ScheduledTask {
+                    task_id: id.clone(),
+                    earliest_start,
+                    earliest_finish,
+                    latest_start,
+                    latest_finish,
+                };
+                (id.clone(), scheduled)
+            })
+            .collect();
+
+        let critical_path = self.critical_path(&order, &tasks, end);
+
+        Ok(Schedule {
+            tasks,
+            critical_path,
+            start,
+            end,
+        })
+    }
+
+    /// Walk back from the task finishing last through predecessors that leave no gap
+    fn critical_path(
+        &self,
+        order: &[String],
+        tasks: &HashMap<String, ScheduledTask>,
+        end: f64,
+    ) -> Vec<String> {
+        let Some(mut current) = order
+            .iter()
+            .rev()
+            .find(|id| tasks[*id].earliest_finish >= end)
+        else {
+            return Vec::new();
+        };
+
+        let mut path = vec![current.clone()];
+        while let Some(predecessor) = self
+            .predecessors(current)
+            .iter()
+            .filter(|predecessor| tasks[*predecessor].is_critical())
+            .find(|predecessor| {
+                tasks[*predecessor].earliest_finish >= tasks[current].earliest_start
+            })
+        {
+            path.push(predecessor.clone());
+            current = predecessor;
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// Planned start of a task: its start date, or the deadline for milestones
+fn start_of(deadline: &Deadline) -> f64 {
+    deadline.start_date.unwrap_or(deadline.deadline)
+}
+
+fn duration_of(task: &Task) -> f64 {
+    task.deadline.as_ref().map_or(0.0, |deadline| {
+        (deadline.deadline - start_of(deadline)).max(0.0)
+    })
+}
+
+/// Tarjan's strongly connected components, keeping only the looping ones
+///
+/// Runs with an explicit stack so long dependency chains cannot overflow the
+/// call stack.
+struct Tarjan<'a> {
+    graph: &'a DependencyGraph,
+    index: usize,
+    indices: HashMap<&'a str, usize>,
+    lowlinks: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: HashSet<&'a str>,
+    cycles: Vec<Vec<String>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn new(graph: &'a DependencyGraph) -> Self {
+        Self {
+            graph,
+            index: 0,
+            indices: HashMap::new(),
+            lowlinks: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: Default::default(),
+            cycles: Vec::new(),
+        }
+    }
+
+    fn run(mut self) -> Vec<Vec<String>> {
+        for id in &self.graph.order {
+            if !self.indices.contains_key(id.as_str()) {
+                self.connect(id);
+            }
+        }
+        self.cycles
+    }
+
+    fn connect(&mut self, root: &'a str) {
+        // Tasks being visited, each with the position of its next successor
+        let mut visiting: Vec<(&'a str, usize)> = Vec::new();
+        self.enter(root);
+        visiting.push((root, 0));
+
+        while let Some((id, next)) = visiting.last_mut() {
+            let id = *id;
+            if let Some(successor) = self.graph.successors(id).get(*next) {
+                *next += 1;
+                let successor = successor.as_str();
+                if !self.indices.contains_key(successor) {
+                    self.enter(successor);
+                    visiting.push((successor, 0));
+                } else if self.on_stack.contains(successor) {
+                    let low = self.lowlinks[id].min(self.indices[successor]);
+                    self.lowlinks.insert(id, low);
+                }
+                continue;
+            }
+
+            visiting.pop();
+            if let Some((parent, _)) = visiting.last() {
+                let low = self.lowlinks[parent].min(self.lowlinks[id]);
+                self.lowlinks.insert(parent, low);
+            }
+            self.finish(id);
+        }
+    }
+
+    fn enter(&mut self, id: &'a str) {
+        self.indices.insert(id, self.index);
+        self.lowlinks.insert(id, self.index);
+        self.index += 1;
+        self.stack.push(id);
+        self.on_stack.insert(id);
+    }
+
+    /// Pop the component rooted at `id` once all of its successors are visited
+    fn finish(&mut self, id: &'a str) {
+        if self.lowlinks[id] != self.indices[id] {
+            return;
+        }
+
+        let mut component = Vec::new();
+        while let Some(member) = self.stack.pop() {
+            self.on_stack.remove(member);
+            component.push(member.to_string());
+            if member == id {
+                break;
+            }
+        }
+        component.reverse();
+
+        let self_loop = self.graph.successors(id).iter().any(|to| to == id);
+        if component.len() > 1 || self_loop {
+            self.cycles.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::DAY_MS;
+
+    /// A task planned from `start` to `end` days, linking to `links`
+    fn task(id: &str, start: f64, end: f64, links: &[&str]) -> Task {
+        Task {
+            id: id.to_string(),
+            deadline: Some(Deadline {
+                deadline: end * DAY_MS,
+                start_date: Some(start * DAY_MS),
+                links: Some(links.iter().map(|link| link.to_string()).collect()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn diamond() -> DependencyGraph {
+        // a -> b -> d and a -> c -> d, with b the longer branch
+        DependencyGraph::new([
+            task("a", 0.0, 2.0, &["b", "c"]),
+            task("b", 2.0, 7.0, &["d"]),
+            task("c", 2.0, 4.0, &["d"]),
+            task("d", 7.0, 8.0, &[]),
+        ])
+    }
+
+    #[test]
+    fn cycles_are_found() {
+        let graph = DependencyGraph::new([
+            task("a", 0.0, 1.0, &["b"]),
+            task("b", 1.0, 2.0, &["c"]),
+            task("c", 2.0, 3.0, &["a", "d"]),
+            task("d", 3.0, 4.0, &["d"]),
+            task("e", 4.0, 5.0, &["missing"]),
+        ]);
+
+        let mut cycles = graph.cycles();
+        cycles.sort();
+        assert_eq!(cycles, [vec!["a", "b", "c"], vec!["d"]]);
+        assert!(graph.topological_order().is_err());
+        assert!(graph.schedule().is_err());
+        assert_eq!(
+            graph.external_links(),
+            [("e".to_string(), "missing".to_string())]
+        );
+    }
+
+    #[test]
+    fn long_chains_do_not_overflow() {
+        let count = 100_000;
+        let ids: Vec<String> = (0..count).map(|index| format!("t{}", index)).collect();
+        let mut tasks: Vec<Task> = ids
+            .iter()
+            .zip(ids.iter().skip(1))
+            .map(|(id, next)| task(id, 0.0, 0.0, &[next]))
+            .collect();
+        tasks.push(task(&ids[count - 1], 0.0, 0.0, &[&ids[0]]));
+
+        let cycles = DependencyGraph::new(tasks).cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), count);
+    }
+
+    #[test]
+    fn diamond_orders_and_depends() {
+        let graph = diamond();
+
+        assert!(graph.cycles().is_empty());
+        let order = graph.topological_order().unwrap();
+        let position = |id: &str| order.iter().position(|other| other == id).unwrap();
+        assert!(position("a") < position("b") && position("a") < position("c"));
+        assert!(position("b") < position("d") && position("c") < position("d"));
+        assert_eq!(graph.predecessors("d"), ["b", "c"]);
+        assert!(graph.depends_on("d", "a"));
+        assert!(!graph.depends_on("b", "c"));
+        assert!(graph.violations().is_empty());
+    }
+
+    #[test]
+    fn critical_path_follows_the_longest_branch() {
+        let schedule = diamond().schedule().unwrap();
+
+        assert_eq!(schedule.critical_path, ["a", "b", "d"]);
+        assert_eq!(schedule.start, 0.0);
+        assert_eq!(schedule.end, 8.0 * DAY_MS);
+        assert!(!schedule.tasks["c"].is_critical());
+        assert_eq!(schedule.tasks["c"].slack(), 3.0 * DAY_MS);
+        assert_eq!(schedule.tasks["d"].earliest_start, 7.0 * DAY_MS);
+    }
+
+    #[test]
+    fn early_starts_are_violations() {
+        let graph = DependencyGraph::new([
+            task("a", 0.0, 3.0, &["b", "c"]),
+            task("b", 2.0, 4.0, &[]),
+            task("c", 3.0, 5.0, &[]),
+        ]);
+
+        let violations = graph.violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].successor_id, "b");
+        assert_eq!(violations[0].overlap(), DAY_MS);
+
+        // The schedule pushes the successor back instead
+        let schedule = graph.schedule().unwrap();
+        assert_eq!(schedule.tasks["b"].earliest_start, 3.0 * DAY_MS);
+        assert_eq!(schedule.end, 5.0 * DAY_MS);
+    }
+}
@@ -0,0 +1,711 @@
+//! Cached directory of workspace objects for name ↔ ID resolution
+//!
+//! Every tool built on the SDK needs to turn "Ivan Petrov", "Backlog" or
+//! "Priority: High" into IDs and back. [`Directory`] loads users, projects, boards,
+//! columns, departments and stickers lazily on first use, keeps them in memory and
+//! reloads them once the configured TTL has passed or after explicit invalidation.
+//!
+//! Name lookups accept an ID, the exact name, a prefix or part of the name, and
+//! tolerate small typos such as "Ivna" for "Ivan". Deleted projects, boards,
+//! columns and departments are left out of lookups but still have their names
+//! resolved by ID.
+
+use crate::SDKError;
+use crate::resources::{BoardsAPI, ColumnsAPI, DepartmentsAPI, ProjectsAPI, StickersAPI, UsersAPI};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// Kind of objects cached by the [`Directory`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DirectoryKind {
+    Users,
+    Projects,
+    Boards,
+    Columns,
+    Departments,
+    Stickers,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StickerKind {
+    String,
+    Sprint,
+}
+
+/// A string or sprint sticker with its states
+#[derive(Clone, Debug, PartialEq)]
+pub struct StickerEntry {
+    pub id: String,
+    pub name: String,
+    pub kind: StickerKind,
+    pub states: Vec<StickerStateEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StickerStateEntry {
+    pub id: String,
+    pub name: String,
+}
+
+impl StickerEntry {
+    pub fn state(&self, state_id: &str) -> Option<&StickerStateEntry> {
+        self.states.iter().find(|state| state.id == state_id)
+    }
+}
+
+/// Lazily loaded, shared cache of workspace objects
+pub struct Directory {
+    client: Arc<YouGileClient>,
+    ttl: Option<Duration>,
+    users: Slot<User>,
+    projects: Slot<Project>,
+    boards: Slot<Board>,
+    columns: Slot<Column>,
+    departments: Slot<Department>,
+    stickers: Slot<StickerEntry>,
+}
+
+impl Directory {
+    /// Create a directory that keeps loaded objects until invalidated
+    pub fn new(client: Arc<YouGileClient>) -> Self {
+        Self {
+            client,
+            ttl: None,
+            users: Slot::default(),
+            projects: Slot::default(),
+            boards: Slot::default(),
+            columns: Slot::default(),
+            departments: Slot::default(),
+            stickers: Slot::default(),
+        }
+    }
+
+    /// Reload objects on next access once they are older than `ttl`
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Drop the cached objects of one kind
+    pub async fn invalidate(&self, kind: DirectoryKind) {
+        match kind {
+            DirectoryKind::Users => self.users.clear().await,
+            DirectoryKind::Projects => self.projects.clear().await,
+            DirectoryKind::Boards => self.boards.clear().await,
+            DirectoryKind::Columns => self.columns.clear().await,
+            DirectoryKind::Departments => self.departments.clear().await,
+            DirectoryKind::Stickers => self.stickers.clear().await,
+        }
+    }
+
+    /// Drop every cached object
+    pub async fn invalidate_all(&self) {
+        for kind in [
+            DirectoryKind::Users,
+            DirectoryKind::Projects,
+            DirectoryKind::Boards,
+            DirectoryKind::Columns,
+            DirectoryKind::Departments,
+            DirectoryKind::Stickers,
+        ] {
+            self.invalidate(kind).await;
+        }
+    }
+
+    // Cached collections
+
+    pub async fn users(&self) -> Result<Arc<Vec<User>>, SDKError> {
+        let client = self.client.clone();
+        self.users
+            .get_or_load(self.ttl, || async move {
+                UsersAPI::new(client).search().all().await
+            })
+            .await
+    }
+
+    pub async fn projects(&self) -> Result<Arc<Vec<Project>>, SDKError> {
+        let client = self.client.clone();
+        self.projects
+            .get_or_load(self.ttl, || async move {
+                ProjectsAPI::new(client).list_all().await
+            })
+            .await
+    }
+
+    pub async fn boards(&self) -> Result<Arc<Vec<Board>>, SDKError> {
+        let client = self.client.clone();
+        self.boards
+            .get_or_load(self.ttl, || async move {
+                BoardsAPI::new(client).search().all().await
+            })
+            .await
+    }
+
+    pub async fn columns(&self) -> Result<Arc<Vec<Column>>, SDKError> {
+        let client = self.client.clone();
+        self.columns
+            .get_or_load(self.ttl, || async move {
+                ColumnsAPI::new(client).search().all().await
+            })
+            .await
+    }
+
+    pub async fn departments(&self) -> Result<Arc<Vec<Department>>, SDKError> {
+        let client = self.client.clone();
+        self.departments
+            .get_or_load(self.ttl, || async move {
+                DepartmentsAPI::new(client).list_all().await
+            })
+            .await
+    }
+
+    /// String and sprint stickers with their states
+    pub async fn stickers(&self) -> Result<Arc<Vec<StickerEntry>>, SDKError> {
+        let client = self.client.clone();
+        self.stickers
+            .get_or_load(self.ttl, || async move {
+                let api = StickersAPI::new(client);
+                let strings = api.search_string().all().await?;
+                let sprints = api.search_sprint().all().await?;
+
+                let strings = strings.into_iter().map(|sticker| StickerEntry {
+                    id: sticker.id,
+                    name: sticker.data.name,
+                    kind: StickerKind::String,
+                    states: sticker
+                        .states
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|state| state.data.deleted != Some(true))
+                        .map(|state| StickerStateEntry {
+                            id: state.id,
+                            name: state.data.name,
+                        })
+                        .collect(),
+                });
+                let sprints = sprints.into_iter().map(|sticker| StickerEntry {
+                    id: sticker.id,
+                    name: sticker.data.name,
+                    kind: StickerKind::Sprint,
+                    states: sticker
+                        .states
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|state| state.data.deleted != Some(true))
+                        .map(|state| StickerStateEntry {
+                            id: state.id,
+                            name: state.data.name,
+                        })
+                        .collect(),
+                });
+
+                Ok(strings.chain(sprints).collect())
+            })
+            .await
+    }
+
+    // Name → object lookups, see `score`. A single lookup fails if several objects
+    // match equally well; the `find_*` variants return all of them instead.
+
+    /// Find a user by ID, real name or email
+    pub async fn user(&self, query: &str) -> Result<User, SDKError> {
+        pick(&self.users().await?, query, "user")
+    }
+
+    pub async fn find_users(&self, query: &str) -> Result<Vec<User>, SDKError> {
+        Ok(best_matches(&self.users().await?, query))
+    }
+
+    pub async fn project(&self, query: &str) -> Result<Project, SDKError> {
+        pick(&self.projects().await?, query, "project")
+    }
+
+    pub async fn find_projects(&self, query: &str) -> Result<Vec<Project>, SDKError> {
+        Ok(best_matches(&self.projects().await?, query))
+    }
+
+    /// Find a board, optionally only within one project
+    pub async fn board(&self, project_id: Option<&str>, query: &str) -> Result<Board, SDKError> {
+        let boards = self.boards_in(project_id).await?;
+        pick(&boards, query, "board")
+    }
+
+    pub async fn find_boards(
+        &self,
+        project_id: Option<&str>,
+        query: &str,
+    ) -> Result<Vec<Board>, SDKError> {
+        Ok(best_matches(&self.boards_in(project_id).await?, query))
+    }
+
+    /// Find a column, optionally only within one board
+    pub async fn column(&self, board_id: Option<&str>, query: &str) -> Result<Column, SDKError> {
+        let columns = self.columns_in(board_id).await?;
+        pick(&columns, query, "column")
+    }
+
+    pub async fn find_columns(
+        &self,
+        board_id: Option<&str>,
+        query: &str,
+    ) -> Result<Vec<Column>, SDKError> {
+        Ok(best_matches(&self.columns_in(board_id).await?, query))
+    }
+
+    /// Columns of one board, or of the whole company for `None`, without deleted ones
+    pub async fn columns_in(&self, board_id: Option<&str>) -> Result<Vec<Column>, SDKError> {
+        Ok(self
+            .columns()
+            .await?
+            .iter()
+            .filter(|column| column.deleted != Some(true))
+            .filter(|column| board_id.is_none_or(|board_id| column.board_id == board_id))
+            .cloned()
+            .collect())
+    }
+
+    /// Boards of one project, or of the whole company for `None`, without deleted ones
+    pub async fn boards_in(&self, project_id: Option<&str>) -> Result<Vec<Board>, SDKError> {
+        Ok(self
+            .boards()
+            .await?
+            .iter()
+            .filter(|board| board.deleted != Some(true))
+            .filter(|board| project_id.is_none_or(|project_id| board.project_id == project_id))
+            .cloned()
+            .collect())
+    }
+
+    pub async fn department(&self, query: &str) -> Result<Department, SDKError> {
+        pick(&self.departments().await?, query, "department")
+    }
+
+    pub async fn find_departments(&self, query: &str) -> Result<Vec<Department>, SDKError> {
+        Ok(best_matches(&self.departments().await?, query))
+    }
+
+    pub async fn sticker(&self, query: &str) -> Result<StickerEntry, SDKError> {
+        pick(&self.stickers().await?, query, "sticker")
+    }
+
+    pub async fn find_stickers(&self, query: &str) -> Result<Vec<StickerEntry>, SDKError> {
+        Ok(best_matches(&self.stickers().await?, query))
+    }
+
+    /// Find a sticker and one of its states, e.g. `("Priority", "High")`
+    pub async fn sticker_state(
+        &self,
+        sticker_query: &str,
+        state_query: &str,
+    ) -> Result<(StickerEntry, StickerStateEntry), SDKError> {
+        let sticker = self.sticker(sticker_query).await?;
+        let state = pick(&sticker.states, state_query, "sticker state")?;
+        Ok((sticker, state))
+    }
+
+    // ID → name lookups
+
+    pub async fn user_name(&self, user_id: &str) -> Result<Option<String>, SDKError> {
+        Ok(self
+            .users()
+            .await?
+            .iter()
+            .find(|user| user.id == user_id)
+            .map(|user| user.real_name.clone()))
+    }
+
+    pub async fn project_title(&self, project_id: &str) -> Result<Option<String>, SDKError> {
+        Ok(self
+            .projects()
+            .await?
+            .iter()
+            .find(|project| project.id == project_id)
+            .map(|project| project.title.clone()))
+    }
+
+    pub async fn board_title(&self, board_id: &str) -> Result<Option<String>, SDKError> {
+        Ok(self
+            .boards()
+            .await?
+            .iter()
+            .find(|board| board.id == board_id)
+            .map(|board| board.title.clone()))
+    }
+
+    pub async fn column_title(&self, column_id: &str) -> Result<Option<String>, SDKError> {
+        Ok(self
+            .columns()
+            .await?
+            .iter()
+            .find(|column| column.id == column_id)
+            .map(|column| column.title.clone()))
+    }
+
+    pub async fn department_title(&self, department_id: &str) -> Result<Option<String>, SDKError> {
+        Ok(self
+            .departments()
+            .await?
+            .iter()
+            .find(|department| department.id == department_id)
+            .map(|department| department.title.clone()))
+    }
+
+    pub async fn sticker_name(&self, sticker_id: &str) -> Result<Option<String>, SDKError> {
+        Ok(self
+            .stickers()
+            .await?
+            .iter()
+            .find(|sticker| sticker.id == sticker_id)
+            .map(|sticker| sticker.name.clone()))
+    }
+
+    pub async fn sticker_state_name(
+        &self,
+        sticker_id: &str,
+        state_id: &str,
+    ) -> Result<Option<String>, SDKError> {
+        Ok(self
+            .stickers()
+            .await?
+            .iter()
+            .find(|sticker| sticker.id == sticker_id)
+            .and_then(|sticker| sticker.state(state_id))
+            .map(|state| state.name.clone()))
+    }
+}
+
+/// One cached collection with its load time
+struct Slot<T> {
+    entry: Mutex<Option<(Instant, Arc<Vec<T>>)>>,
+}
+
+impl<T> Default for Slot<T> {
+    fn default() -> Self {
+        Self {
+            entry: Mutex::new(None),
+        }
+    }
+}
+
+impl<T> Slot<T> {
+    /// Return the cached collection, loading it if missing or expired.
+    /// The lock is held while loading so concurrent callers share one request.
+    async fn get_or_load<F, Fut>(
+        &self,
+        ttl: Option<Duration>,
+        load: F,
+    ) -> Result<Arc<Vec<T>>, SDKError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<T>, SDKError>>,
+    {
+        let mut entry = self.entry.lock().await;
+
+        if let Some((loaded_at, items)) = entry.as_ref()
+            && ttl.is_none_or(|ttl| loaded_at.elapsed() < ttl)
+        {
+            return Ok(items.clone());
+        }
+
+        let items = Arc::new(load().await?);
+        *entry = Some((Instant::now(), items.clone()));
+        Ok(items)
+    }
+
+    async fn clear(&self) {
+        *self.entry.lock().await = None;
+    }
+}
+
+/// Objects that can be looked up by ID or by name
+trait Named {
+    fn id(&self) -> &str;
+    fn names(&self) -> Vec<&str>;
+    /// Deleted objects are never returned by lookups
+    fn is_deleted(&self) -> bool {
+        false
+    }
+}
+
+impl Named for User {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn names(&self) -> Vec<&str> {
+        vec![&self.real_name, &self.email]
+    }
+}
+
+impl Named for Project {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn names(&self) -> Vec<&str> {
+        vec![&self.title]
+    }
+    fn is_deleted(&self) -> bool {
+        self.deleted == Some(true)
+    }
+}
+
+impl Named for Board {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn names(&self) -> Vec<&str> {
+        vec![&self.title]
+    }
+    fn is_deleted(&self) -> bool {
+        self.deleted == Some(true)
+    }
+}
+
+impl Named for Column {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn names(&self) -> Vec<&str> {
+        vec![&self.title]
+    }
+    fn is_deleted(&self) -> bool {
+        self.deleted == Some(true)
+    }
+}
+
+impl Named for Department {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn names(&self) -> Vec<&str> {
+        vec![&self.title]
+    }
+    fn is_deleted(&self) -> bool {
+        self.deleted == Some(true)
+    }
+}
+
+impl Named for StickerEntry {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn names(&self) -> Vec<&str> {
+        vec![&self.name]
+    }
+}
+
+impl Named for StickerStateEntry {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn names(&self) -> Vec<&str> {
+        vec![&self.name]
+    }
+}
+
+/// How well an object matches a query: ID, exact name, name prefix, substring,
+/// then a name or one of its words within a few typos of the query
+fn score<T: Named>(item: &T, query: &str) -> u8 {
+    if item.is_deleted() {
+        return 0;
+    }
+    if item.id() == query {
+        return 5;
+    }
+
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return 0;
+    }
+
+    item.names()
+        .into_iter()
+        .map(|name| {
+            let name = name.trim().to_lowercase();
+            if name == query {
+                4
+            } else if name.starts_with(&query) {
+                3
+            } else if name.contains(&query) {
+                2
+            } else if is_typo_of(&name, &query) {
+                1
+            } else {
+                0
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Whether `query` is the name, or one of its words, with a few typos: one per
+/// four characters, at least one. Queries shorter than three characters must
+/// match without typos.
+fn is_typo_of(name: &str, query: &str) -> bool {
+    let query: Vec<char> = query.chars().collect();
+    if query.len() < 3 {
+        return false;
+    }
+    let allowed = (query.len() / 4).max(1);
+
+    std::iter::once(name)
+        .chain(name.split_whitespace())
+        .any(|candidate| {
+            let candidate: Vec<char> = candidate.chars().collect();
+            candidate.len().abs_diff(query.len()) <= allowed
+                && edit_distance(&candidate, &query) <= allowed
+        })
+}
+
+/// Levenshtein distance, counting a swap of neighbouring characters as one edit
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut before_previous: Vec<usize> = Vec::new();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut current = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before_previous[j - 2] + 1);
+            }
+        }
+        before_previous = std::mem::replace(&mut previous, current);
+    }
+    previous[b.len()]
+}
+
+/// All objects sharing the best non-zero score
+fn best_matches<T: Named + Clone>(items: &[T], query: &str) -> Vec<T> {
+    let scored: Vec<_> = items
+        .iter()
+        .map(|item| (score(item, query), item))
+        .collect();
+    let best = scored.iter().map(|(score, _)| *score).max().unwrap_or(0);
+
+    if best == 0 {
+        return Vec::new();
+    }
+
+    scored
+        .into_iter()
+        .filter(|(score, _)| *score == best)
+        .map(|(_, item)| item.clone())
+        .collect()
+}
+
+fn pick<T: Named + Clone>(items: &[T], query: &str, kind: &str) -> Result<T, SDKError> {
+    let mut matches = best_matches(items, query);
+    match matches.len() {
+        0 => Err(SDKError::not_found(format!("{} '{}'", kind, query))),
+        1 => Ok(matches.remove(0)),
+        count => Err(SDKError::validation(format!(
+            "{} '{}' is ambiguous: {} matches",
+            kind, query, count
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: &str, real_name: &str, email: &str) -> User {
+        User {
+            id: id.to_string(),
+            real_name: real_name.to_string(),
+            email: email.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn board(id: &str, title: &str, deleted: bool) -> Board {
+        Board {
+            id: id.to_string(),
+            title: title.to_string(),
+            deleted: deleted.then_some(true),
+            ..Default::default()
+        }
+    }
+
+    fn ids<T: Named>(items: &[T]) -> Vec<&str> {
+        items.iter().map(Named::id).collect()
+    }
+
+    #[test]
+    fn score_ranks_id_exact_prefix_substring_and_typos() {
+        let ivan = user("u1", "Ivan Petrov", "ivan@example.com");
+
+        assert_eq!(score(&ivan, "u1"), 5);
+        assert_eq!(score(&ivan, " ivan petrov "), 4);
+        assert_eq!(score(&ivan, "IVAN@"), 3);
+        assert_eq!(score(&ivan, "petrov"), 2);
+        assert_eq!(score(&ivan, "Ivna"), 1);
+        assert_eq!(score(&ivan, "Petorv"), 1);
+        assert_eq!(score(&ivan, "Ivan Petrvo"), 1);
+        assert_eq!(score(&ivan, "Oleg"), 0);
+        assert_eq!(score(&ivan, ""), 0);
+    }
+
+    #[test]
+    fn short_queries_need_more_than_a_typo() {
+        let board = board("b1", "QA", false);
+
+        assert_eq!(score(&board, "Q"), 3);
+        assert_eq!(score(&board, "X"), 0);
+        assert_eq!(score(&board, "QB"), 0);
+    }
+
+    #[test]
+    fn typos_work_for_cyrillic_names() {
+        let board = board("b1", "Бэклог", false);
+
+        assert_eq!(score(&board, "Беклог"), 1);
+    }
+
+    #[test]
+    fn best_matches_keep_only_the_top_score() {
+        let users = [
+            user("u1", "Ivan Petrov", "ivan@example.com"),
+            user("u2", "Ivan Sidorov", "sidorov@example.com"),
+            user("u3", "Ivanna Li", "li@example.com"),
+        ];
+
+        assert_eq!(ids(&best_matches(&users, "ivan")), ["u1", "u2", "u3"]);
+        assert_eq!(ids(&best_matches(&users, "Ivan Petrov")), ["u1"]);
+        assert_eq!(ids(&best_matches(&users, "sidrov")), ["u2"]);
+        assert!(best_matches(&users, "Oleg").is_empty());
+    }
+
+    #[test]
+    fn deleted_objects_are_never_picked() {
+        let boards = [board("b1", "Backlog", true), board("b2", "Backlog", false)];
+
+        assert_eq!(pick(&boards, "Backlog", "board").unwrap().id, "b2");
+        assert!(pick(&boards, "b1", "board").is_err());
+    }
+
+    #[test]
+    fn pick_fails_on_missing_and_ambiguous_names() {
+        let users = [
+            user("u1", "Ivan Petrov", "ivan@example.com"),
+            user("u2", "Ivan Sidorov", "sidorov@example.com"),
+        ];
+
+        assert_eq!(pick(&users, "sidorov", "user").unwrap().id, "u2");
+        assert!(matches!(
+            pick(&users, "Oleg", "user"),
+            Err(SDKError::NotFound(_))
+        ));
+        assert!(matches!(
+            pick(&users, "Ivan", "user"),
+            Err(SDKError::ValidationError(_))
+        ));
+    }
+}
@@ -46,13 +46,121 @@
 //! - `client.chats()` - Access chat-related operations
 //! - `client.group_chats()` - Access group chat-related operations
 //! - `client.stickers()` - Access sticker-related operations
+//! - `client.sprints()` - Access sprint operations built on sprint stickers
 //! - `client.webhooks()` - Access webhook-related operations
 //! - `client.files()` - Access file-related operations
+//!
+//! ## Task Queries
+//!
+//! `client.tasks().query("assignee:me sticker:Priority=High due<7d -completed")` runs a
+//! query written in a small search language, see [`query`] for the supported terms.
+//!
+//! ## Task Dependencies
+//!
+//! `client.tasks().dependencies_for_board(board_id)` builds a [`dependencies::DependencyGraph`]
+//! from deadline links for cycle checks, scheduling and the critical path.
+//!
+//! ## Reports
+//!
+//! `client.tasks().time_report(tasks)` sums planned and worked hours per assignee, column,
+//! board or sticker state, see [`reports`] for CSV and JSON export. Snapshots taken with
+//! `client.tasks().snapshot(tasks)` feed burndown, cumulative flow, cycle time and
+//! throughput analytics rendered as CSV, JSON or ASCII charts.
+//! `client.chats().tally_reactions(chat_ids, from, to)` counts reactions given and received
+//! per user, e.g. the 🚀 each user collected over a sprint.
+//! `client.projects().permission_audit(system_roles)` lists who can do what in every project
+//! and on every board with overridden rights, flagging company admins, observers with write
+//! rights and members of deleted roles. System roles are approximated by the presets chosen in
+//! `system_roles` and marked as such.
+//!
+//! ## Recurring Tasks
+//!
+//! `client.scheduler(state_path)` creates tasks from templates on cron-like rules,
+//! see [`scheduler`].
+//!
+//! ## Deadline Reminders
+//!
+//! `client.reminders(state_path)` posts digests of tasks that are due soon or overdue,
+//! grouped by assignee, into task chats or a group chat, see [`reminders`].
+//!
+//! ## Markdown Messages
+//!
+//! `client.chats().send_markdown(chat_id, "**Done**, see ABC-12")` posts Markdown with
+//! its HTML rendering; [`markup`] also converts YouGile HTML back to Markdown or plain text.
+//!
+//! ## Mentions and References
+//!
+//! `client.references()` extracts and resolves mentions, task codes and URLs in messages
+//! and descriptions, and finds messages that mention a user since a given time, see
+//! [`references`].
+//!
+//! ## Permissions
+//!
+//! [`permissions::PermissionsBuilder`] builds project role permissions from read-only,
+//! contributor, manager and full presets, and [`permissions::EffectivePermissions`] checks
+//! whether a role may move, complete or edit a task assigned to, followed by or created by
+//! the user, see [`permissions`].
+//!
+//! ## Departments
+//!
+//! `client.departments().tree()` builds a [`DepartmentTree`] with parent and child
+//! navigation, members including sub-departments, cycle checks for moves and text or
+//! Graphviz DOT rendering of the org chart.
+//!
+//! ## Onboarding and Offboarding
+//!
+//! `client.users().offboard(user_id, successor, options)` plans reassigning a leaving
+//! user's tasks and removing them from group chats, departments and projects before
+//! deleting the account; `client.users().onboard(email, &template)` plans the invite and
+//! memberships of a new user. The returned [`UserWorkflow`] shows the plan, runs it step by
+//! step and can save its log to resume after a failure.
+//!
+//! ## File Attachments
+//!
+//! `client.files().upload().max_size(bytes).on_read_progress(f).path(path)` reads a file or
+//! reader into memory and uploads it with its detected MIME type; `client.files().attach_to_task(task_id, &file, how)`
+//! links it from the task description or chat and `attach_to_chat(chat_id, &file, caption)`
+//! posts it into a chat.
+//!
+//! ## Name Lookups
+//!
+//! `client.directory()` returns a shared [`Directory`] that caches users, projects, boards,
+//! columns, departments and stickers, and resolves names to IDs and back.
 
 mod builder;
+pub mod dependencies;
+mod directory;
 mod error;
+pub mod markup;
+pub mod permissions;
+pub mod query;
+pub mod references;
+pub mod reminders;
+pub mod reports;
 mod resources;
+pub mod scheduler;
+mod state;
+mod throttle;
+mod time;
 
 pub use builder::YouGileSDK;
+pub use directory::{Directory, DirectoryKind, StickerEntry, StickerKind, StickerStateEntry};
 pub use error::SDKError;
+pub use resources::departments::DepartmentTree;
+pub use resources::files::{FileUploadBuilder, TaskAttachment, UploadProgress, UploadedFile};
+pub use resources::projects::{
+    CopyMembers, MemberCopyReport, MemberRole, ProjectAssignment, ProjectMembers,
+};
+pub use resources::sprints::{Sprint, SprintRollover, SprintSummary};
+pub use resources::tasks::{
+    BulkEntry, BulkOptions, BulkOutcome, BulkReport, ChatFollower, ChecklistProgress, Checklists,
+    ColumnMapping, ColumnMoveReport, DroppedSticker, MoveFailure, MoveReport, SkipReason,
+    SkippedLink, StickerRemap, StopwatchSwitch, TaskChat, TaskNode, TaskSet, TaskStopwatch,
+    TaskTimer, TaskTree, TreeRollup,
+};
+pub use resources::users::{
+    LoggedStep, OffboardOptions, OnboardTemplate, StepStatus, UserStep, UserWorkflow, WorkflowKind,
+    WorkflowLog,
+};
+pub use resources::{GroupChatMembers, ReactionCount};
 pub use yougile_api_client::{YouGileClient, models::*};
@@ -0,0 +1,710 @@
+//! Conversion between Markdown, plain text and the HTML used by YouGile
+//!
+//! Chat messages carry both `text` and `text_html`, and task descriptions are
+//! stored as HTML. [`markdown_to_html`] renders a small Markdown dialect:
+//!
+//! - `**bold**`, `*italic*` or `_italic_`, `~~strikethrough~~` and `` `code` ``
+//! - fenced code blocks between lines of ```` ``` ````
+//! - lists starting with `-`, `*`, `+` or `1.`
+//! - links written as `[text](https://example.com)`; only `http`, `https` and
+//!   `mailto` targets become links, others are left as their text
+//!
+//! The HTML YouGile uses for mentions and task links is not documented, so
+//! mentions such as `@Name` and task codes such as `ABC-12` stay plain text.
+//!
+//! A backslash escapes the next punctuation character. [`html_to_markdown`] and
+//! [`html_to_text`] go the other way, e.g. to show descriptions in a terminal.
+
+use std::fmt::Write;
+
+/// Render Markdown as HTML
+pub fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut list: Option<(bool, Vec<String>)> = None;
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            flush_paragraph(&mut html, &mut paragraph);
+            flush_list(&mut html, &mut list);
+            let code: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| !line.trim().starts_with("```"))
+                .collect();
+            let _ = write!(html, "<pre><code>{}</code></pre>", escape(&code.join("\n")));
+            continue;
+        }
+
+        if let Some((ordered, item)) = list_item(trimmed) {
+            flush_paragraph(&mut html, &mut paragraph);
+            match &mut list {
+                Some((current, items)) if *current == ordered => items.push(inline(item)),
+                _ => {
+                    flush_list(&mut html, &mut list);
+                    list = Some((ordered, vec![inline(item)]));
+                }
+            }
+            continue;
+        }
+
+        flush_list(&mut html, &mut list);
+        if trimmed.is_empty() {
+            flush_paragraph(&mut html, &mut paragraph);
+        } else {
+            paragraph.push(inline(trimmed));
+        }
+    }
+
+    flush_paragraph(&mut html, &mut paragraph);
+    flush_list(&mut html, &mut list);
+    html
+}
+
+/// HTML for plain text: the text escaped, with line breaks kept
+pub fn text_to_html(text: &str) -> String {
+    escape(text).replace('\n', "<br>")
+}
+
+/// Convert HTML to Markdown in the dialect read by [`markdown_to_html`]
+pub fn html_to_markdown(html: &str) -> String {
+    Converter::new(false).convert(html)
+}
+
+/// Convert HTML to plain text for terminals
+///
+/// Formatting is dropped, links keep their target in parentheses and list
+/// items start with `-` or their number.
+pub fn html_to_text(html: &str) -> String {
+    Converter::new(true).convert(html)
+}
+
+fn flush_paragraph(html: &mut String, paragraph: &mut Vec<String>) {
+    if !paragraph.is_empty() {
+        let _ = write!(html, "<p>{}</p>", paragraph.join("<br>"));
+        paragraph.clear();
+    }
+}
+
+fn flush_list(html: &mut String, list: &mut Option<(bool, Vec<String>)>) {
+    if let Some((ordered, items)) = list.take() {
+        let tag = if ordered { "ol" } else { "ul" };
+        let _ = write!(html, "<{}>", tag);
+        for item in items {
+            let _ = write!(html, "<li>{}</li>", item);
+        }
+        let _ = write!(html, "</{}>", tag);
+    }
+}
+
+/// Whether a line is a list item, and its text
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(marker) {
+            return Some((false, item.trim_start()));
+        }
+    }
+
+    let digits = line.len()
+        - line
+            .trim_start_matches(|ch: char| ch.is_ascii_digit())
+            .len();
+    if digits > 0 {
+        return line[digits..]
+            .strip_prefix(". ")
+            .map(|item| (true, item.trim_start()));
+    }
+    None
+}
+
+/// Render the inline Markdown of a single line
+fn inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut html = String::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let ch = chars[index];
+        let rest = &chars[index..];
+
+        if ch == '\\' && rest.get(1).is_some_and(|next| next.is_ascii_punctuation()) {
+            html.push_str(&escape(&rest[1].to_string()));
+            index += 2;
+            continue;
+        }
+
+        if ch == '`'
+            && let Some(end) = find(&chars, index + 1, &['`'])
+        {
+            let code: String = chars[index + 1..end].iter().collect();
+            let _ = write!(html, "<code>{}</code>", escape(&code));
+            index = end + 1;
+            continue;
+        }
+
+        if let Some((marker, tag)) = [(&['*', '*'][..], "b"), (&['~', '~'][..], "s")]
+            .into_iter()
+            .find(|(marker, _)| rest.starts_with(marker))
+            && let Some(end) = find(&chars, index + 2, marker)
+            && end > index + 2
+        {
+            let inner: String = chars[index + 2..end].iter().collect();
+            let _ = write!(html, "<{}>{}</{}>", tag, inline(&inner), tag);
+            index = end + 2;
+            continue;
+        }
+
+        let word_start = index == 0 || !chars[index - 1].is_alphanumeric();
+        if (ch == '*' || (ch == '_' && word_start))
+            && rest.get(1).is_some_and(|next| !next.is_whitespace())
+            && let Some(end) = find(&chars, index + 1, &[ch])
+            && end > index + 1
+        {
+            let inner: String = chars[index + 1..end].iter().collect();
+            let _ = write!(html, "<i>{}</i>", inline(&inner));
+            index = end + 1;
+            continue;
+        }
+
+        if ch == '['
+            && let Some(close) = find(&chars, index + 1, &[']', '('])
+            && let Some(end) = find(&chars, close + 2, &[')'])
+        {
+            let label: String = chars[index + 1..close].iter().collect();
+            let url: String = chars[close + 2..end].iter().collect();
+            let url = url.trim();
+            if safe_url(url) {
+                let _ = write!(html, "<a href=\"{}\">{}</a>", escape(url), inline(&label));
+            } else {
+                html.push_str(&inline(&label));
+            }
+            index = end + 1;
+            continue;
+        }
+
+        html.push_str(&escape(&ch.to_string()));
+        index += 1;
+    }
+
+    html
+}
+
+/// Whether a link target has no scheme or one of `http`, `https` and `mailto`
+fn safe_url(url: &str) -> bool {
+    let scheme_end = url.find(|ch: char| !(ch.is_ascii_alphanumeric() || "+-.".contains(ch)));
+    match scheme_end {
+        Some(end) if url[end..].starts_with(':') => {
+            let scheme = url[..end].to_ascii_lowercase();
+            matches!(scheme.as_str(), "http" | "https" | "mailto")
+        }
+        // Characters like control codes could hide a scheme from browsers
+        _ => !url.contains(':') || url.starts_with(['/', '?', '#']),
+    }
+}
+
+/// Position of the first occurrence of `pattern` at or after `from`
+fn find(chars: &[char], from: usize, pattern: &[char]) -> Option<usize> {
+    (from..chars.len()).find(|&index| chars[index..].starts_with(pattern))
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| Some((decode_entity(&rest[1..end])?, end)));
+        match entity {
+            Some((ch, end)) => {
+                decoded.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = name.strip_prefix('#')?;
+            let code = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Token {
+    Open(String, Vec<(String, String)>),
+    Close(String),
+    Text(String),
+}
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(decode_entities(rest)));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(decode_entities(&rest[..start])));
+        }
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            tokens.push(Token::Text(decode_entities(rest)));
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_lowercase()));
+        } else if !tag.starts_with('!') && !tag.starts_with('?') {
+            let tag = tag.trim_end_matches('/');
+            let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            tokens.push(Token::Open(
+                tag[..name_end].to_lowercase(),
+                attributes(&tag[name_end..]),
+            ));
+        }
+    }
+
+    tokens
+}
+
+fn attributes(mut text: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+
+    loop {
+        text = text.trim_start();
+        if text.is_empty() {
+            break;
+        }
+
+        let name_end = text
+            .find(|ch: char| ch == '=' || ch.is_whitespace())
+            .unwrap_or(text.len());
+        let name = text[..name_end].to_lowercase();
+        text = text[name_end..].trim_start();
+
+        let value = match text.strip_prefix('=') {
+            Some(value) => {
+                let value = value.trim_start();
+                let (quoted, rest) = match value.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let end = value[1..].find(quote).map_or(value.len(), |end| end + 1);
+                        (&value[1..end], value.get(end + 1..).unwrap_or(""))
+                    }
+                    _ => {
+                        let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                        (&value[..end], &value[end..])
+                    }
+                };
+                text = rest;
+                decode_entities(quoted)
+            }
+            None => String::new(),
+        };
+        attributes.push((name, value));
+    }
+
+    attributes
+}
+
+fn attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// How an element is completed when its closing tag is reached
+enum Closing {
+    Suffix(String),
+    Code(&'static str),
+    Block,
+    Link { start: usize, href: String },
+    Preformatted,
+    List,
+}
+
+struct Converter {
+    plain: bool,
+    out: String,
+    open: Vec<(String, Closing)>,
+    /// Open lists, with the next number for ordered ones
+    lists: Vec<Option<usize>>,
+    preformatted: usize,
+    code: usize,
+}
+
+impl Converter {
+    fn new(plain: bool) -> Self {
+        Self {
+            plain,
+            out: String::new(),
+            open: Vec::new(),
+            lists: Vec::new(),
+            preformatted: 0,
+            code: 0,
+        }
+    }
+
+    fn convert(mut self, html: &str) -> String {
+        for token in tokenize(html) {
+            match token {
+                Token::Text(text) => self.text(&text),
+                Token::Open(name, attributes) => self.open(name, &attributes),
+                Token::Close(name) => {
+                    if let Some(position) = self.open.iter().rposition(|(open, _)| *open == name) {
+                        while self.open.len() > position {
+                            if let Some((_, closing)) = self.open.pop() {
+                                self.close(closing);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        while let Some((_, closing)) = self.open.pop() {
+            self.close(closing);
+        }
+
+        let lines: Vec<&str> = self.out.lines().map(str::trim_end).collect();
+        let mut result = String::new();
+        let mut blank = 0;
+        for line in lines {
+            if line.is_empty() {
+                blank += 1;
+                continue;
+            }
+            if !result.is_empty() {
+                result.push_str(if blank > 0 { "\n\n" } else { "\n" });
+            }
+            blank = 0;
+            result.push_str(line);
+        }
+        result
+    }
+
+    fn open(&mut self, name: String, attributes: &[(String, String)]) {
+        let markdown = !self.plain;
+        let closing = match name.as_str() {
+            "br" => {
+                self.out.push('\n');
+                return;
+            }
+            "img" => {
+                let alt = attribute(attributes, "alt").unwrap_or("image");
+                let src = attribute(attributes, "src").unwrap_or("");
+                if markdown {
+                    let _ = write!(self.out, "![{}]({})", alt, src);
+                } else {
+                    let _ = write!(self.out, "[{}]", alt);
+                }
+                return;
+            }
+            "hr" => {
+                self.block();
+                self.out.push_str("---");
+                self.block();
+                return;
+            }
+            "b" | "strong" if markdown => self.wrap("**"),
+            "i" | "em" if markdown => self.wrap("*"),
+            "s" | "strike" | "del" if markdown => self.wrap("~~"),
+            "code" if self.preformatted == 0 => {
+                self.code += 1;
+                let marker = if markdown { "`" } else { "" };
+                self.out.push_str(marker);
+                Closing::Code(marker)
+            }
+            "pre" => {
+                self.block();
+                if markdown {
+                    self.out.push_str("```\n");
+                }
+                self.preformatted += 1;
+                Closing::Preformatted
+            }
+            "a" => Closing::Link {
+                start: self.out.len(),
+                href: attribute(attributes, "href").unwrap_or("").to_string(),
+            },
+            "ul" | "ol" => {
+                if self.lists.is_empty() {
+                    self.block();
+                } else {
+                    self.line();
+                }
+                self.lists
+                    .push((name == "ol").then(|| attribute_number(attributes, "start")));
+                Closing::List
+            }
+            "li" => {
+                self.line();
+                let depth = self.lists.len().saturating_sub(1);
+                self.out.push_str(&"  ".repeat(depth));
+                match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        let _ = write!(self.out, "{}. ", number);
+                        *number += 1;
+                    }
+                    _ => self.out.push_str("- "),
+                }
+                Closing::Suffix(String::new())
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.block();
+                if markdown {
+                    let level = name[1..].parse().unwrap_or(1);
+                    let _ = write!(self.out, "{} ", "#".repeat(level));
+                }
+                Closing::Block
+            }
+            "p" | "div" | "blockquote" | "table" | "tr" => {
+                self.block();
+                Closing::Block
+            }
+            _ => Closing::Suffix(String::new()),
+        };
+        self.open.push((name, closing));
+    }
+
+    fn close(&mut self, closing: Closing) {
+        match closing {
+            Closing::Suffix(suffix) => self.out.push_str(&suffix),
+            Closing::Code(marker) => {
+                self.code = self.code.saturating_sub(1);
+                self.out.push_str(marker);
+            }
+            Closing::Block => self.block(),
+            Closing::Preformatted => {
+                self.preformatted = self.preformatted.saturating_sub(1);
+                if !self.plain {
+                    if !self.out.ends_with('\n') {
+                        self.out.push('\n');
+                    }
+                    self.out.push_str("```");
+                }
+                self.block();
+            }
+            Closing::List => {
+                self.lists.pop();
+                // Nested lists continue the item they belong to
+                if self.lists.is_empty() {
+                    self.block();
+                } else {
+                    self.line();
+                }
+            }
+            Closing::Link { start, href } => {
+                let label = self.out[start..].trim().to_string();
+                if href.is_empty() || label == href {
+                    return;
+                }
+                if label.is_empty() {
+                    self.out.push_str(&href);
+                } else if self.plain {
+                    let _ = write!(self.out, " ({})", href);
+                } else {
+                    self.out.truncate(start);
+                    let _ = write!(self.out, "[{}]({})", label, href);
+                }
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.preformatted > 0 {
+            self.out.push_str(text);
+            return;
+        }
+
+        let mut collapsed = String::with_capacity(text.len());
+        let mut previous = self.out.chars().last();
+        for ch in text.chars() {
+            if ch.is_whitespace() {
+                if previous.is_some_and(|previous| !previous.is_whitespace()) {
+                    collapsed.push(' ');
+                    previous = Some(' ');
+                }
+                continue;
+            }
+            if !self.plain && self.code == 0 && needs_escape(ch, previous) {
+                collapsed.push('\\');
+            }
+            collapsed.push(ch);
+            previous = Some(ch);
+        }
+        self.out.push_str(&collapsed);
+    }
+
+    fn wrap(&mut self, marker: &str) -> Closing {
+        self.out.push_str(marker);
+        Closing::Suffix(marker.to_string())
+    }
+
+    /// Start a new line unless already at the start of one
+    fn line(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    /// Start a new paragraph
+    fn block(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.line();
+            self.out.push('\n');
+        }
+    }
+}
+
+fn attribute_number(attributes: &[(String, String)], name: &str) -> usize {
+    attribute(attributes, name)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(1)
+}
+
+/// Characters that would otherwise be read as Markdown by [`markdown_to_html`]
+fn needs_escape(ch: char, previous: Option<char>) -> bool {
+    match ch {
+        '\\' | '*' | '`' | '[' | ']' => true,
+        '~' => previous == Some('~'),
+        '_' => previous.is_none_or(|previous| !previous.is_alphanumeric()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_inline_markdown() {
+        assert_eq!(
+            markdown_to_html("**bold** *it* _it_ ~~gone~~ `a<b`"),
+            "<p><b>bold</b> <i>it</i> <i>it</i> <s>gone</s> <code>a&lt;b</code></p>"
+        );
+        assert_eq!(
+            markdown_to_html("see [docs](https://example.com) or snake_case_name"),
+            "<p>see <a href=\"https://example.com\">docs</a> or snake_case_name</p>"
+        );
+        assert_eq!(markdown_to_html(r"\*not italic\*"), "<p>*not italic*</p>");
+    }
+
+    #[test]
+    fn renders_blocks() {
+        assert_eq!(
+            markdown_to_html("first\nsecond\n\n- a\n- b\n1. one\n```\nlet x = 1 < 2;\n```"),
+            "<p>first<br>second</p><ul><li>a</li><li>b</li></ul><ol><li>one</li></ol>\
+             <pre><code>let x = 1 &lt; 2;</code></pre>"
+        );
+    }
+
+    #[test]
+    fn keeps_mentions_and_task_codes_as_text() {
+        assert_eq!(
+            markdown_to_html("@Ivan see ABC-12 and ЗАД-3"),
+            "<p>@Ivan see ABC-12 and ЗАД-3</p>"
+        );
+    }
+
+    #[test]
+    fn links_only_safe_schemes() {
+        assert_eq!(
+            markdown_to_html("[mail](mailto:a@example.com) [page](/tasks?id=1) [top](#top)"),
+            "<p><a href=\"mailto:a@example.com\">mail</a> <a href=\"/tasks?id=1\">page</a> \
+             <a href=\"#top\">top</a></p>"
+        );
+        for url in [
+            "javascript:alert%281%29",
+            "JavaScript:void",
+            "data:text/html,x",
+            "vbscript:x",
+            "java\tscript:void",
+        ] {
+            assert_eq!(
+                markdown_to_html(&format!("[click]({})", url)),
+                "<p>click</p>",
+                "link to {:?}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn markdown_round_trips_through_html() {
+        for markdown in [
+            "**bold** and *italic* with `code`",
+            "@Ivan see ABC-12",
+            "[docs](https://example.com)",
+            "- one\n- two",
+            "1. one\n2. two",
+            r"a \* star",
+        ] {
+            assert_eq!(
+                html_to_markdown(&markdown_to_html(markdown)),
+                markdown,
+                "round trip of {:?}",
+                markdown
+            );
+        }
+    }
+
+    #[test]
+    fn converts_html_to_text() {
+        assert_eq!(
+            html_to_text(
+                "<p>Hello <b>world</b> &amp; <a href=\"https://example.com\">docs</a></p>\
+                 <ol start=\"3\"><li>three</li><li>four</li></ol><p>x<br>y</p>"
+            ),
+            "Hello world & docs (https://example.com)\n\n3. three\n4. four\n\nx\ny"
+        );
+    }
+
+    #[test]
+    fn text_to_html_escapes_and_keeps_lines() {
+        assert_eq!(text_to_html("a < b\n\"c\""), "a &lt; b<br>&quot;c&quot;");
+    }
+}
@@ -0,0 +1,872 @@
+//! Permission presets and effective-permission checks
+//!
+//! Project roles carry a tree of permissions: the project itself, its boards,
+//! their columns and the tasks in them, with separate task rights for tasks
+//! assigned to the user, tasks with the user, tasks created by the user and all
+//! other tasks. [`PermissionsBuilder`] builds that tree from a [`Preset`] and
+//! [`EffectivePermissions`] answers what a role may do with a given task:
+//!
+//! ```rust,no_run
+//! use yougile_sdk::permissions::{
+//!     EffectivePermissions, PermissionsBuilder, Preset, TaskAction, TaskRelation,
+//! };
+//!
+//! let permissions = PermissionsBuilder::preset(Preset::Contributor)
+//!     .tasks(TaskRelation::Other, |tasks| tasks.complete = true)
+//!     .build();
+//!
+//! let effective = EffectivePermissions::new(permissions);
+//! assert!(effective.can(&[TaskRelation::Other], TaskAction::Complete));
+//! assert!(!effective.can(&[TaskRelation::Other], TaskAction::Delete));
+//! ```
+
+use crate::{MemberRole, SDKError};
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+use yougile_api_client::models::boards::Move as ColumnMove;
+use yougile_api_client::models::projects::ProjectPermissionChildValue;
+use yougile_api_client::models::*;
+
+/// Named starting points for a permission tree
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// See every task, change nothing
+    ReadOnly,
+    /// Create tasks, work on own tasks and discuss all of them
+    Contributor,
+    /// Manage every task, column and board, but not the project itself
+    Manager,
+    /// Every permission
+    Full,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 4] = [
+        Preset::ReadOnly,
+        Preset::Contributor,
+        Preset::Manager,
+        Preset::Full,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Preset::ReadOnly => "read-only",
+            Preset::Contributor => "contributor",
+            Preset::Manager => "manager",
+            Preset::Full => "full",
+        }
+    }
+
+    /// The complete permission tree of the preset
+    pub fn permissions(&self) -> ProjectPermissions {
+        match self {
+            Preset::ReadOnly => ProjectPermissions {
+                boards: BoardPermissions {
+                    show_stickers: true,
+                    columns: ColumnPermissions {
+                        all_tasks: view_task(),
+                        with_me_tasks: view_task(),
+                        my_tasks: view_task(),
+                        created_by_me_tasks: view_task(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Preset::Contributor => {
+                let discuss = TaskPermissions {
+                    send_messages: true,
+                    send_files: true,
+                    ..view_task()
+                };
+                let work = TaskPermissions {
+                    edit_description: true,
+                    complete: true,
+                    edit_subtasks: EditSubtasks::Yes,
+                    edit_stickers: true,
+                    edit_pins: true,
+                    r#move: Move::Board,
+                    edit_who_to_notify: EditWhoToNotify::VariantSelf,
+                    ..discuss.clone()
+                };
+                ProjectPermissions {
+                    boards: BoardPermissions {
+                        show_stickers: true,
+                        columns: ColumnPermissions {
+                            add_task: true,
+                            all_tasks: TaskPermissions {
+                                assign_users: AssignUsers::AddSelf,
+                                ..discuss
+                            },
+                            with_me_tasks: work.clone(),
+                            my_tasks: TaskPermissions {
+                                edit_title: true,
+                                ..work
+                            },
+                            created_by_me_tasks: full_task(Move::Board),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+            }
+            Preset::Manager => ProjectPermissions {
+                add_board: true,
+                boards: BoardPermissions {
+                    edit_title: true,
+                    show_stickers: true,
+                    edit_stickers: true,
+                    add_column: true,
+                    settings: true,
+                    columns: ColumnPermissions {
+                        edit_title: true,
+                        delete: true,
+                        r#move: ColumnMove::Project,
+                        add_task: true,
+                        all_tasks: full_task(Move::Project),
+                        with_me_tasks: full_task(Move::Project),
+                        my_tasks: full_task(Move::Project),
+                        created_by_me_tasks: full_task(Move::Project),
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Preset::Full => ProjectPermissions {
+                edit_title: true,
+                delete: true,
+                add_board: true,
+                boards: BoardPermissions {
+                    edit_title: true,
+                    delete: true,
+                    r#move: true,
+                    show_stickers: true,
+                    edit_stickers: true,
+                    add_column: true,
+                    settings: true,
+                    columns: ColumnPermissions {
+                        edit_title: true,
+                        delete: true,
+                        r#move: ColumnMove::Yes,
+                        add_task: true,
+                        all_tasks: full_task(Move::Yes),
+                        with_me_tasks: full_task(Move::Yes),
+                        my_tasks: full_task(Move::Yes),
+                        created_by_me_tasks: full_task(Move::Yes),
+                    },
+                },
+                children: Default::default(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Presets standing in for the system roles of a project
+///
+/// The API does not expose what the admin, worker and observer roles may do,
+/// so their rights can only be approximated by presets chosen by the caller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SystemRolePresets {
+    pub admin: Preset,
+    pub worker: Preset,
+    pub observer: Preset,
+}
+
+impl SystemRolePresets {
+    pub fn new(admin: Preset, worker: Preset, observer: Preset) -> Self {
+        Self {
+            admin,
+            worker,
+            observer,
+        }
+    }
+
+    /// Preset for a system role, `None` for custom roles
+    pub fn preset(&self, role: &MemberRole) -> Option<Preset> {
+        match role {
+            MemberRole::Admin => Some(self.admin),
+            MemberRole::Worker => Some(self.worker),
+            MemberRole::Observer => Some(self.observer),
+            MemberRole::Custom(_) => None,
+        }
+    }
+}
+
+/// How a task relates to the user whose permissions are checked
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TaskRelation {
+    /// The user is assigned to the task
+    Mine,
+    /// The user follows the task chat
+    WithMe,
+    CreatedByMe,
+    /// None of the above, only the rights for all tasks apply
+    Other,
+}
+
+impl TaskRelation {
+    /// Every relation of `task` to the user, or `[Other]` if there is none
+    ///
+    /// Tasks do not list their chat subscribers, so pass them in (see
+    /// `TaskChat::subscribers`) to detect [`TaskRelation::WithMe`].
+    pub fn of(task: &Task, user_id: &str, subscribers: &[String]) -> Vec<TaskRelation> {
+        let mut relations = Vec::new();
+        if task
+            .assigned
+            .as_ref()
+            .is_some_and(|assigned| assigned.iter().any(|id| id == user_id))
+        {
+            relations.push(TaskRelation::Mine);
+        }
+        if subscribers.iter().any(|id| id == user_id) {
+            relations.push(TaskRelation::WithMe);
+        }
+        if task.created_by.as_deref() == Some(user_id) {
+            relations.push(TaskRelation::CreatedByMe);
+        }
+        if relations.is_empty() {
+            relations.push(TaskRelation::Other);
+        }
+        relations
+    }
+}
+
+/// Builds a [`ProjectPermissions`] tree, usually starting from a [`Preset`]
+///
+/// Board and column settings apply to every board and column of the project.
+#[derive(Clone, Debug, Default)]
+pub struct PermissionsBuilder {
+    permissions: ProjectPermissions,
+}
+
+impl PermissionsBuilder {
+    /// Start with no permissions at all
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn preset(preset: Preset) -> Self {
+        Self {
+            permissions: preset.permissions(),
+        }
+    }
+
+    pub fn edit_title(mut self, allowed: bool) -> Self {
+        self.permissions.edit_title = allowed;
+        self
+    }
+
+    pub fn delete(mut self, allowed: bool) -> Self {
+        self.permissions.delete = allowed;
+        self
+    }
+
+    pub fn add_board(mut self, allowed: bool) -> Self {
+        self.permissions.add_board = allowed;
+        self
+    }
+
+    /// Change the board permissions
+    pub fn board(mut self, update: impl FnOnce(&mut BoardPermissions)) -> Self {
+        update(&mut self.permissions.boards);
+        self
+    }
+
+    /// Change the column permissions
+    pub fn column(mut self, update: impl FnOnce(&mut ColumnPermissions)) -> Self {
+        update(&mut self.permissions.boards.columns);
+        self
+    }
+
+    /// Change the task permissions for one relation; [`TaskRelation::Other`]
+    /// changes the rights for all tasks
+    pub fn tasks(
+        mut self,
+        relation: TaskRelation,
+        update: impl FnOnce(&mut TaskPermissions),
+    ) -> Self {
+        update(task_permissions_mut(
+            &mut self.permissions.boards.columns,
+            relation,
+        ));
+        self
+    }
+
+    /// Change the task permissions of every relation alike
+    pub fn all_tasks(mut self, update: impl Fn(&mut TaskPermissions)) -> Self {
+        let columns = &mut self.permissions.boards.columns;
+        for tasks in [
+            &mut columns.all_tasks,
+            &mut columns.with_me_tasks,
+            &mut columns.my_tasks,
+            &mut columns.created_by_me_tasks,
+        ] {
+            update(tasks);
+        }
+        self
+    }
+
+    pub fn build(self) -> ProjectPermissions {
+        self.permissions
+    }
+
+    /// A new project role with the built permissions
+    pub fn role(self, name: &str) -> CreateProjectRole {
+        CreateProjectRole::new(name.to_string(), self.permissions)
+    }
+}
+
+impl From<ProjectPermissions> for PermissionsBuilder {
+    fn from(permissions: ProjectPermissions) -> Self {
+        Self { permissions }
+    }
+}
+
+/// A right in a project, board or column, or over all tasks, as listed by audits
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Capability {
+    EditProject,
+    DeleteProject,
+    AddBoard,
+    EditBoard,
+    DeleteBoard,
+    MoveBoard,
+    BoardSettings,
+    EditStickers,
+    AddColumn,
+    EditColumn,
+    DeleteColumn,
+    MoveColumn,
+    AddTask,
+    ShowTasks,
+    EditTasks,
+    CompleteTasks,
+    MoveTasks,
+    DeleteTasks,
+    CommentTasks,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 19] = [
+        Capability::EditProject,
+        Capability::DeleteProject,
+        Capability::AddBoard,
+        Capability::EditBoard,
+        Capability::DeleteBoard,
+        Capability::MoveBoard,
+        Capability::BoardSettings,
+        Capability::EditStickers,
+        Capability::AddColumn,
+        Capability::EditColumn,
+        Capability::DeleteColumn,
+        Capability::MoveColumn,
+        Capability::AddTask,
+        Capability::ShowTasks,
+        Capability::EditTasks,
+        Capability::CompleteTasks,
+        Capability::MoveTasks,
+        Capability::DeleteTasks,
+        Capability::CommentTasks,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Capability::EditProject => "edit-project",
+            Capability::DeleteProject => "delete-project",
+            Capability::AddBoard => "add-board",
+            Capability::EditBoard => "edit-board",
+            Capability::DeleteBoard => "delete-board",
+            Capability::MoveBoard => "move-board",
+            Capability::BoardSettings => "board-settings",
+            Capability::EditStickers => "edit-stickers",
+            Capability::AddColumn => "add-column",
+            Capability::EditColumn => "edit-column",
+            Capability::DeleteColumn => "delete-column",
+            Capability::MoveColumn => "move-column",
+            Capability::AddTask => "add-task",
+            Capability::ShowTasks => "show-tasks",
+            Capability::EditTasks => "edit-tasks",
+            Capability::CompleteTasks => "complete-tasks",
+            Capability::MoveTasks => "move-tasks",
+            Capability::DeleteTasks => "delete-tasks",
+            Capability::CommentTasks => "comment-tasks",
+        }
+    }
+
+    /// Whether the capability changes anything; only showing tasks does not
+    pub fn is_write(&self) -> bool {
+        *self != Capability::ShowTasks
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Capability {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// A single thing that can be done with a task
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TaskAction {
+    Show,
+    Delete,
+    EditTitle,
+    EditDescription,
+    Complete,
+    Close,
+    AssignUsers,
+    Connect,
+    EditSubtasks,
+    EditStickers,
+    EditPins,
+    /// Move within the board at least, see [`EffectivePermissions::can_move`]
+    Move,
+    SendMessages,
+    SendFiles,
+    EditWhoToNotify,
+}
+
+impl TaskAction {
+    pub const ALL: [TaskAction; 15] = [
+        TaskAction::Show,
+        TaskAction::Delete,
+        TaskAction::EditTitle,
+        TaskAction::EditDescription,
+        TaskAction::Complete,
+        TaskAction::Close,
+        TaskAction::AssignUsers,
+        TaskAction::Connect,
+        TaskAction::EditSubtasks,
+        TaskAction::EditStickers,
+        TaskAction::EditPins,
+        TaskAction::Move,
+        TaskAction::SendMessages,
+        TaskAction::SendFiles,
+        TaskAction::EditWhoToNotify,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskAction::Show => "show",
+            TaskAction::Delete => "delete",
+            TaskAction::EditTitle => "edit-title",
+            TaskAction::EditDescription => "edit-description",
+            TaskAction::Complete => "complete",
+            TaskAction::Close => "close",
+            TaskAction::AssignUsers => "assign-users",
+            TaskAction::Connect => "connect",
+            TaskAction::EditSubtasks => "edit-subtasks",
+            TaskAction::EditStickers => "edit-stickers",
+            TaskAction::EditPins => "edit-pins",
+            TaskAction::Move => "move",
+            TaskAction::SendMessages => "send-messages",
+            TaskAction::SendFiles => "send-files",
+            TaskAction::EditWhoToNotify => "edit-who-to-notify",
+        }
+    }
+
+    fn allowed(&self, tasks: &TaskPermissions) -> bool {
+        match self {
+            TaskAction::Show => tasks.show,
+            TaskAction::Delete => tasks.delete,
+            TaskAction::EditTitle => tasks.edit_title,
+            TaskAction::EditDescription => tasks.edit_description,
+            TaskAction::Complete => tasks.complete,
+            TaskAction::Close => tasks.close,
+            TaskAction::AssignUsers => tasks.assign_users != AssignUsers::No,
+            TaskAction::Connect => tasks.connect,
+            TaskAction::EditSubtasks => tasks.edit_subtasks != EditSubtasks::No,
+            TaskAction::EditStickers => tasks.edit_stickers,
+            TaskAction::EditPins => tasks.edit_pins,
+            TaskAction::Move => tasks.r#move != Move::No,
+            TaskAction::SendMessages => tasks.send_messages,
+            TaskAction::SendFiles => tasks.send_files,
+            TaskAction::EditWhoToNotify => tasks.edit_who_to_notify != EditWhoToNotify::No,
+        }
+    }
+}
+
+impl fmt::Display for TaskAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Answers what a role may do, for pre-checks before calling the API
+///
+/// A task gets the rights for all tasks combined with the rights of every
+/// relation it has to the user, taking the broadest value of each field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EffectivePermissions {
+    permissions: ProjectPermissions,
+}
+
+impl EffectivePermissions {
+    pub fn new(permissions: ProjectPermissions) -> Self {
+        Self { permissions }
+    }
+
+    pub fn of_role(role: &ProjectRole) -> Self {
+        Self::new(role.permissions.clone())
+    }
+
+    /// Permissions of a project member
+    ///
+    /// Custom roles are looked up in `roles`. The rights of system roles are not
+    /// known, so they are only approximated when `system_roles` is given.
+    /// Returns `None` for a custom role missing from `roles` and for a system
+    /// role without `system_roles`.
+    pub fn of_member(
+        role: &MemberRole,
+        roles: &[ProjectRole],
+        system_roles: Option<&SystemRolePresets>,
+    ) -> Option<Self> {
+        let permissions = match role {
+            MemberRole::Custom(id) => roles
+                .iter()
+                .find(|role| role.id == *id)?
+                .permissions
+                .clone(),
+            system => system_roles?.preset(system)?.permissions(),
+        };
+        Some(Self::new(permissions))
+    }
+
+    /// Permissions within one board, with its override from `children` applied
+    ///
+    /// Overrides that do not hold a complete board permission set are ignored.
+    pub fn for_board(&self, board_id: &str) -> Self {
+        let mut permissions = self.permissions.clone();
+        let board = match permissions.children.get(board_id) {
+            Some(ProjectPermissionChildValue::PermissionMap(map)) => serde_json::to_value(map).ok(),
+            Some(ProjectPermissionChildValue::RawValue(value)) => Some(value.clone()),
+            _ => None,
+        }
+        .and_then(|value| serde_json::from_value::<BoardPermissions>(value).ok());
+        if let Some(board) = board {
+            permissions.boards = board;
+        }
+        Self::new(permissions)
+    }
+
+    pub fn project(&self) -> &ProjectPermissions {
+        &self.permissions
+    }
+
+    pub fn board(&self) -> &BoardPermissions {
+        &self.permissions.boards
+    }
+
+    pub fn column(&self) -> &ColumnPermissions {
+        &self.permissions.boards.columns
+    }
+
+    /// Whether the role has `capability`; task capabilities refer to all tasks,
+    /// regardless of their relation to the user
+    pub fn has(&self, capability: Capability) -> bool {
+        let project = &self.permissions;
+        let board = &project.boards;
+        let column = &board.columns;
+        let tasks = &column.all_tasks;
+        match capability {
+            Capability::EditProject => project.edit_title,
+            Capability::DeleteProject => project.delete,
+            Capability::AddBoard => project.add_board,
+            Capability::EditBoard => board.edit_title,
+            Capability::DeleteBoard => board.delete,
+            Capability::MoveBoard => board.r#move,
+            Capability::BoardSettings => board.settings,
+            Capability::EditStickers => board.edit_stickers,
+            Capability::AddColumn => board.add_column,
+            Capability::EditColumn => column.edit_title,
+            Capability::DeleteColumn => column.delete,
+            Capability::MoveColumn => column.r#move != ColumnMove::No,
+            Capability::AddTask => column.add_task,
+            Capability::ShowTasks => tasks.show,
+            Capability::EditTasks => tasks.edit_title || tasks.edit_description,
+            Capability::CompleteTasks => tasks.complete,
+            Capability::MoveTasks => tasks.r#move != Move::No,
+            Capability::DeleteTasks => tasks.delete,
+            Capability::CommentTasks => tasks.send_messages,
+        }
+    }
+
+    /// Every capability the role has, in [`Capability::ALL`] order
+    pub fn capabilities(&self) -> Vec<Capability> {
+        Capability::ALL
+            .into_iter()
+            .filter(|capability| self.has(*capability))
+            .collect()
+    }
+
+    /// Combined rights for a task with the given relations to the user
+    pub fn task(&self, relations: &[TaskRelation]) -> TaskPermissions {
+        let columns = &self.permissions.boards.columns;
+        let mut tasks = columns.all_tasks.clone();
+        for relation in relations {
+            let extra = match relation {
+                TaskRelation::Mine => &columns.my_tasks,
+                TaskRelation::WithMe => &columns.with_me_tasks,
+                TaskRelation::CreatedByMe => &columns.created_by_me_tasks,
+                TaskRelation::Other => continue,
+            };
+            tasks = merge_tasks(&tasks, extra);
+        }
+        tasks
+    }
+
+    pub fn can(&self, relations: &[TaskRelation], action: TaskAction) -> bool {
+        action.allowed(&self.task(relations))
+    }
+
+    /// Whether the task may be moved as far as `scope`
+    ///
+    /// `Move::Board` asks for moves within the board, `Move::Project` within
+    /// the project and `Move::Yes` into other projects.
+    pub fn can_move(&self, relations: &[TaskRelation], scope: Move) -> bool {
+        move_rank(self.task(relations).r#move) >= move_rank(scope)
+    }
+
+    pub fn can_complete(&self, relations: &[TaskRelation]) -> bool {
+        self.can(relations, TaskAction::Complete)
+    }
+
+    /// Whether the title or the description may be edited
+    pub fn can_edit(&self, relations: &[TaskRelation]) -> bool {
+        self.can(relations, TaskAction::EditTitle)
+            || self.can(relations, TaskAction::EditDescription)
+    }
+
+    /// Fail with [`SDKError::PermissionDenied`] unless `action` is allowed
+    pub fn check(&self, relations: &[TaskRelation], action: TaskAction) -> Result<(), SDKError> {
+        if self.can(relations, action) {
+            Ok(())
+        } else {
+            Err(SDKError::permission_denied(format!(
+                "Action '{}' is not allowed for this task",
+                action
+            )))
+        }
+    }
+}
+
+impl From<&ProjectRole> for EffectivePermissions {
+    fn from(role: &ProjectRole) -> Self {
+        Self::of_role(role)
+    }
+}
+
+fn view_task() -> TaskPermissions {
+    TaskPermissions {
+        show: true,
+        ..Default::default()
+    }
+}
+
+fn full_task(r#move: Move) -> TaskPermissions {
+    TaskPermissions {
+        show: true,
+        delete: true,
+        edit_title: true,
+        edit_description: true,
+        complete: true,
+        close: true,
+        assign_users: AssignUsers::Yes,
+        connect: true,
+        edit_subtasks: EditSubtasks::Yes,
+        edit_stickers: true,
+        edit_pins: true,
+        r#move,
+        send_messages: true,
+        send_files: true,
+        edit_who_to_notify: EditWhoToNotify::Yes,
+    }
+}
+
+fn task_permissions_mut(
+    columns: &mut ColumnPermissions,
+    relation: TaskRelation,
+) -> &mut TaskPermissions {
+    match relation {
+        TaskRelation::Mine => &mut columns.my_tasks,
+        TaskRelation::WithMe => &mut columns.with_me_tasks,
+        TaskRelation::CreatedByMe => &mut columns.created_by_me_tasks,
+        TaskRelation::Other => &mut columns.all_tasks,
+    }
+}
+
+/// The broader value of every field
+fn merge_tasks(a: &TaskPermissions, b: &TaskPermissions) -> TaskPermissions {
+    TaskPermissions {
+        show: a.show || b.show,
+        delete: a.delete || b.delete,
+        edit_title: a.edit_title || b.edit_title,
+        edit_description: a.edit_description || b.edit_description,
+        complete: a.complete || b.complete,
+        close: a.close || b.close,
+        assign_users: std::cmp::max_by_key(a.assign_users, b.assign_users, |value| {
+            assign_rank(*value)
+        }),
+        connect: a.connect || b.connect,
+        edit_subtasks: std::cmp::max_by_key(
+            a.edit_subtasks,
+            b.edit_subtasks,
+            |value| match value {
+                EditSubtasks::No => 0,
+                EditSubtasks::Complete => 1,
+                EditSubtasks::Yes => 2,
+            },
+        ),
+        edit_stickers: a.edit_stickers || b.edit_stickers,
+        edit_pins: a.edit_pins || b.edit_pins,
+        r#move: std::cmp::max_by_key(a.r#move, b.r#move, |value| move_rank(*value)),
+        send_messages: a.send_messages || b.send_messages,
+        send_files: a.send_files || b.send_files,
+        edit_who_to_notify: std::cmp::max_by_key(
+            a.edit_who_to_notify,
+            b.edit_who_to_notify,
+            |value| match value {
+                EditWhoToNotify::No => 0,
+                EditWhoToNotify::VariantSelf => 1,
+                EditWhoToNotify::Yes => 2,
+            },
+        ),
+    }
+}
+
+fn assign_rank(value: AssignUsers) -> u8 {
+    match value {
+        AssignUsers::No => 0,
+        AssignUsers::AddSelf => 1,
+        AssignUsers::SetSelf => 2,
+        AssignUsers::ChangeFromSelf => 3,
+        AssignUsers::Yes => 4,
+    }
+}
+
+fn move_rank(value: Move) -> u8 {
+    match value {
+        Move::No => 0,
+        Move::Board => 1,
+        Move::Project => 2,
+        Move::Yes => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(id: &str, preset: Preset) -> ProjectRole {
+        ProjectRole {
+            id: id.to_string(),
+            name: preset.to_string(),
+            description: None,
+            permissions: preset.permissions(),
+        }
+    }
+
+    #[test]
+    fn task_merges_rights_of_every_relation() {
+        let effective = EffectivePermissions::new(Preset::Contributor.permissions());
+
+        assert!(!effective.can(&[TaskRelation::Other], TaskAction::EditTitle));
+        assert!(effective.can(&[TaskRelation::Mine], TaskAction::EditTitle));
+        assert!(effective.can(&[TaskRelation::Other], TaskAction::SendMessages));
+        assert!(!effective.can(&[TaskRelation::Mine], TaskAction::Delete));
+        assert!(effective.can(
+            &[TaskRelation::Mine, TaskRelation::CreatedByMe],
+            TaskAction::Delete
+        ));
+    }
+
+    #[test]
+    fn merge_takes_the_broader_value() {
+        let narrow = TaskPermissions {
+            show: true,
+            assign_users: AssignUsers::Yes,
+            r#move: Move::Board,
+            ..Default::default()
+        };
+        let broad = TaskPermissions {
+            complete: true,
+            assign_users: AssignUsers::AddSelf,
+            r#move: Move::Project,
+            ..Default::default()
+        };
+
+        let merged = merge_tasks(&narrow, &broad);
+        assert!(merged.show && merged.complete);
+        assert_eq!(merged.assign_users, AssignUsers::Yes);
+        assert_eq!(merged.r#move, Move::Project);
+        assert_eq!(merged, merge_tasks(&broad, &narrow));
+    }
+
+    #[test]
+    fn can_move_compares_scopes() {
+        let permissions = PermissionsBuilder::new()
+            .tasks(TaskRelation::Other, |tasks| tasks.r#move = Move::Project)
+            .build();
+        let effective = EffectivePermissions::new(permissions);
+
+        assert!(effective.can_move(&[TaskRelation::Other], Move::Board));
+        assert!(effective.can_move(&[TaskRelation::Other], Move::Project));
+        assert!(!effective.can_move(&[TaskRelation::Other], Move::Yes));
+    }
+
+    #[test]
+    fn of_member_needs_presets_for_system_roles() {
+        let roles = [role("role-1", Preset::Manager)];
+        let presets = SystemRolePresets::new(Preset::Full, Preset::Contributor, Preset::ReadOnly);
+
+        assert!(EffectivePermissions::of_member(&MemberRole::Admin, &roles, None).is_none());
+        assert!(
+            EffectivePermissions::of_member(&MemberRole::Custom("missing".into()), &roles, None)
+                .is_none()
+        );
+
+        let custom =
+            EffectivePermissions::of_member(&MemberRole::Custom("role-1".into()), &roles, None)
+                .unwrap();
+        assert_eq!(custom.project(), &Preset::Manager.permissions());
+
+        let observer =
+            EffectivePermissions::of_member(&MemberRole::Observer, &roles, Some(&presets)).unwrap();
+        assert_eq!(observer.project(), &Preset::ReadOnly.permissions());
+    }
+
+    #[test]
+    fn for_board_applies_the_board_override() {
+        let mut permissions = Preset::ReadOnly.permissions();
+        let board = Preset::Full.permissions().boards;
+        permissions.children.insert(
+            "board-1".to_string(),
+            ProjectPermissionChildValue::RawValue(serde_json::to_value(&board).unwrap()),
+        );
+        permissions.children.insert(
+            "board-2".to_string(),
+            ProjectPermissionChildValue::RawValue(serde_json::json!({ "editTitle": true })),
+        );
+        let effective = EffectivePermissions::new(permissions);
+
+        assert_ne!(effective.board(), &board);
+        assert_eq!(effective.for_board("board-1").board(), &board);
+        assert_eq!(effective.for_board("board-2").board(), effective.board());
+        assert_eq!(effective.for_board("board-3").board(), effective.board());
+    }
+}
@@ -0,0 +1,572 @@
+//! Task query language
+//!
+//! Queries are whitespace-separated terms that must all match, e.g.
+//! `assignee:me sticker:Priority=High due<7d -completed board:"Sprint 12" text:login`.
+//! Names are resolved to IDs, filters supported by the task search endpoint are
+//! pushed down to the server and the rest are applied client-side. A term that
+//! starts with a quote, such as `"assignee:me"`, is searched as literal text.
+
+mod parser;
+
+pub use parser::{Comparison, Filter, FilterKind, TaskQuery, TimeSpec, UserRef};
+
+use crate::SDKError;
+use crate::directory::Directory;
+use crate::resources::tasks::{ChecklistProgress, TaskSearchBuilder};
+use crate::time::{DAY_MS, now_ms};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// Builder that resolves and runs a [`TaskQuery`]
+pub struct TaskQueryBuilder {
+    client: Arc<YouGileClient>,
+    query: Result<TaskQuery, SDKError>,
+    directory: Option<Arc<Directory>>,
+    me: Option<String>,
+    now: Option<f64>,
+}
+
+impl TaskQueryBuilder {
+    pub(crate) fn new(client: Arc<YouGileClient>, query: Result<TaskQuery, SDKError>) -> Self {
+        Self {
+            client,
+            query,
+            directory: None,
+            me: None,
+            now: None,
+        }
+    }
+
+    /// Directory used to resolve names; a fresh one is loaded if not set
+    pub fn directory(mut self, directory: Arc<Directory>) -> Self {
+        self.directory = Some(directory);
+        self
+    }
+
+    /// User ID that `assignee:me` refers to; without it such a query fails
+    /// before any request is sent
+    pub fn me(mut self, user_id: impl Into<String>) -> Self {
+        self.me = Some(user_id.into());
+        self
+    }
+
+    /// Reference time for relative deadlines, defaults to the current time
+    pub fn now(mut self, timestamp: f64) -> Self {
+        self.now = Some(timestamp);
+        self
+    }
+
+    /// Resolve names, run the search and return every matching task
+    pub async fn execute(self) -> Result<Vec<Task>, SDKError> {
+        let query = self.query?;
+        if query.uses_me() && self.me.is_none() {
+            return Err(SDKError::validation(
+                "assignee:me requires a user ID, set it with TaskQueryBuilder::me",
+            ));
+        }
+        let now = self.now.unwrap_or_else(now_ms);
+
+        // Boards are resolved first so that `column:` terms are scoped to them
+        let mut filters: Vec<_> = query.filters.iter().collect();
+        filters.sort_by_key(|filter| !matches!(filter.kind, FilterKind::Board(_)));
+
+        let directory = self
+            .directory
+            .unwrap_or_else(|| Arc::new(Directory::new(self.client.clone())));
+        let mut resolver = Resolver::new(directory, self.me);
+        let mut checks = Vec::with_capacity(filters.len());
+        for filter in filters {
+            let check = resolver.resolve(&filter.kind, filter.negated, now).await?;
+            checks.push((filter.negated, check));
+        }
+
+        let pushdown = Pushdown::from_checks(&checks);
+        let tasks = pushdown.fetch(&self.client).await?;
+
+        Ok(tasks
+            .into_iter()
+            .filter(|task| {
+                checks
+                    .iter()
+                    .all(|(negated, check)| check.matches(task, now) != *negated)
+            })
+            .collect())
+    }
+}
+
+/// A filter with every name resolved to IDs
+enum Check {
+    /// `None` matches unassigned tasks
+    Assignee(Option<String>),
+    /// Sticker IDs, each with the accepted state IDs (`None` accepts any value)
+    Sticker(Vec<(String, Option<HashSet<String>>)>),
+    Columns(HashSet<String>),
+    /// Title as written in the query, matched case-insensitively
+    Title(String),
+    /// Lowercased text
+    Text(String),
+    Color(TaskColor),
+    /// Deadline compared against a timestamp; `Eq` matches the whole day
+    Due(Comparison, f64),
+    HasDeadline(bool),
+    ChecklistProgress(Comparison, f64),
+    HasChecklist(bool),
+    Completed,
+    Archived,
+    Deleted,
+    Overdue,
+}
+
+impl Check {
+    fn matches(&self, task: &Task, now: f64) -> bool {
+        match self {
+            Check::Assignee(None) => task.assigned.as_ref().is_none_or(Vec::is_empty),
+            Check::Assignee(Some(user_id)) => task
+                .assigned
+                .as_ref()
+                .is_some_and(|assigned| assigned.contains(user_id)),
+            Check::Sticker(candidates) => {
+                let Some(stickers) = &task.stickers else {
+                    return false;
+                };
+                candidates.iter().any(|(sticker_id, states)| {
+                    match (stickers.get(sticker_id), states) {
+                        (None, _) => false,
+                        (Some(StickerValue::StateId(value)), _) if value == UNSET => false,
+                        (Some(_), None) => true,
+                        (Some(StickerValue::StateId(state)), Some(states)) => {
+                            states.contains(state)
+                        }
+                        (Some(_), Some(_)) => false,
+                    }
+                })
+            }
+            Check::Columns(columns) => task
+                .column_id
+                .as_ref()
+                .is_some_and(|column_id| columns.contains(column_id)),
+            Check::Title(text) => task.title.to_lowercase().contains(&text.to_lowercase()),
+            Check::Text(text) => {
+                task.title.to_lowercase().contains(text)
+                    || task
+                        .description
+                        .as_ref()
+                        .is_some_and(|description| description.to_lowercase().contains(text))
+            }
+            Check::Color(color) => task.color.as_ref() == Some(color),
+            Check::Due(comparison, timestamp) => {
+                task.deadline
+                    .as_ref()
+                    .is_some_and(|deadline| match comparison {
+                        Comparison::Eq => {
+                            deadline.deadline >= *timestamp
+                                && deadline.deadline < timestamp + DAY_MS
+                        }
+                        comparison => comparison.matches(deadline.deadline, *timestamp),
+                    })
+            }
+            Check::HasDeadline(expected) => task.deadline.is_some() == *expected,
+            Check::ChecklistProgress(comparison, percent) => checklist_progress(task)
+                .is_some_and(|progress| comparison.matches(progress, *percent)),
+            Check::HasChecklist(expected) => checklist_progress(task).is_some() == *expected,
+            Check::Completed => task.completed == Some(true),
+            Check::Archived => task.archived == Some(true),
+            Check::Deleted => task.deleted == Some(true),
+            Check::Overdue => {
+                task.completed != Some(true)
+                    && task
+                        .deadline
+                        .as_ref()
+                        .is_some_and(|deadline| deadline.deadline < now)
+            }
+        }
+    }
+}
+
+/// Percentage of completed checklist items, `None` if the task has no items
+fn checklist_progress(task: &Task) -> Option<f64> {
+    ChecklistProgress::of_task(task).percent()
+}
+
+/// Filters sent to the task search endpoint
+#[derive(Default)]
+struct Pushdown {
+    columns: Option<Vec<String>>,
+    assigned_to: Option<String>,
+    sticker_id: Option<String>,
+    sticker_state_id: Option<String>,
+    title: Option<String>,
+    include_deleted: bool,
+}
+
+impl Pushdown {
+    fn from_checks(checks: &[(bool, Check)]) -> Self {
+        let mut pushdown = Pushdown::default();
+
+        for (negated, check) in checks {
+            if *negated {
+                continue;
+            }
+            match check {
+                Check::Columns(columns) => {
+                    pushdown.columns = Some(match pushdown.columns.take() {
+                        Some(current) => current
+                            .into_iter()
+                            .filter(|column| columns.contains(column))
+                            .collect(),
+                        None => columns.iter().cloned().collect(),
+                    });
+                }
+                Check::Assignee(Some(user_id)) if pushdown.assigned_to.is_none() => {
+                    pushdown.assigned_to = Some(user_id.clone());
+                }
+                Check::Sticker(candidates)
+                    if candidates.len() == 1 && pushdown.sticker_id.is_none() =>
+                {
+                    let (sticker_id, states) = &candidates[0];
+                    pushdown.sticker_id = Some(sticker_id.clone());
+                    pushdown.sticker_state_id = states
+                        .as_ref()
+                        .filter(|states| states.len() == 1)
+                        .and_then(|states| states.iter().next().cloned());
+                }
+                Check::Title(title) if pushdown.title.is_none() => {
+                    pushdown.title = Some(title.clone());
+                }
+                Check::Deleted => pushdown.include_deleted = true,
+                _ => {}
+            }
+        }
+
+        pushdown
+    }
+
+    fn search(&self, client: &Arc<YouGileClient>) -> TaskSearchBuilder {
+        let mut search = TaskSearchBuilder::new(client.clone());
+        if self.include_deleted {
+            search = search.include_deleted(true);
+        }
+        if let Some(user_id) = &self.assigned_to {
+            search = search.assigned_to(user_id);
+        }
+        if let Some(sticker_id) = &self.sticker_id {
+            search = search.sticker_id(sticker_id);
+        }
+        if let Some(state_id) = &self.sticker_state_id {
+            search = search.sticker_state_id(state_id);
+        }
+        if let Some(title) = &self.title {
+            search = search.title(title);
+        }
+        search
+    }
+
+    async fn fetch(&self, client: &Arc<YouGileClient>) -> Result<Vec<Task>, SDKError> {
+        let Some(columns) = &self.columns else {
+            return self.search(client).all().await;
+        };
+
+        let mut seen = HashSet::new();
+        let mut tasks = Vec::new();
+        for column_id in columns {
+            for task in self.search(client).column_id(column_id).all().await? {
+                if seen.insert(task.id.clone()) {
+                    tasks.push(task);
+                }
+            }
+        }
+
+        Ok(tasks)
+    }
+}
+
+/// Resolves names in a query through the [`Directory`]
+struct Resolver {
+    directory: Arc<Directory>,
+    me: Option<String>,
+    /// Boards selected by non-negated `board:` terms, used to scope `column:`
+    board_scope: Option<Vec<String>>,
+}
+
+impl Resolver {
+    fn new(directory: Arc<Directory>, me: Option<String>) -> Self {
+        Self {
+            directory,
+            me,
+            board_scope: None,
+        }
+    }
+
+    async fn resolve(
+        &mut self,
+        kind: &FilterKind,
+        negated: bool,
+        now: f64,
+    ) -> Result<Check, SDKError> {
+        let check = match kind {
+            FilterKind::Assignee(UserRef::Me) => {
+                Check::Assignee(Some(self.me.clone().ok_or_else(|| {
+                    SDKError::validation("assignee:me requires a user ID")
+                })?))
+            }
+            FilterKind::Assignee(UserRef::Nobody) => Check::Assignee(None),
+            FilterKind::Assignee(UserRef::Name(name)) => {
+                Check::Assignee(Some(self.directory.user(name).await?.id))
+            }
+            FilterKind::Sticker { name, state } => {
+                Check::Sticker(self.resolve_sticker(name, state.as_deref()).await?)
+            }
+            FilterKind::Board(name) => {
+                let boards: Vec<String> = self
+                    .directory
+                    .find_boards(None, name)
+                    .await?
+                    .into_iter()
+                    .map(|board| board.id)
+                    .collect();
+                if boards.is_empty() {
+                    return Err(SDKError::not_found(format!("board '{}'", name)));
+                }
+
+                let mut columns = HashSet::new();
+                for board_id in &boards {
+                    columns.extend(
+                        self.directory
+                            .columns_in(Some(board_id))
+                            .await?
+                            .into_iter()
+                            .map(|column| column.id),
+                    );
+                }
+                if !negated {
+                    self.board_scope = Some(boards);
+                }
+                Check::Columns(columns)
+            }
+            FilterKind::Column(name) => Check::Columns(self.resolve_columns(name).await?),
+            FilterKind::Title(text) => Check::Title(text.clone()),
+            FilterKind::Text(text) => Check::Text(text.to_lowercase()),
+            FilterKind::Color(color) => Check::Color(if color.starts_with("task-") {
+                TaskColor::from(color.as_str())
+            } else {
+                TaskColor::from(format!("task-{}", color.to_lowercase()))
+            }),
+            FilterKind::Due(comparison, time) => Check::Due(*comparison, time.resolve(now)),
+            FilterKind::HasDeadline(expected) => Check::HasDeadline(*expected),
+            FilterKind::ChecklistProgress(comparison, percent) => {
+                Check::ChecklistProgress(*comparison, *percent)
+            }
+            FilterKind::HasChecklist(expected) => Check::HasChecklist(*expected),
+            FilterKind::Completed => Check::Completed,
+            FilterKind::Archived => Check::Archived,
+            FilterKind::Deleted => Check::Deleted,
+            FilterKind::Overdue => Check::Overdue,
+        };
+
+        Ok(check)
+    }
+
+    async fn resolve_columns(&self, name: &str) -> Result<HashSet<String>, SDKError> {
+        let mut columns = HashSet::new();
+        match &self.board_scope {
+            Some(boards) => {
+                for board_id in boards {
+                    columns.extend(
+                        self.directory
+                            .find_columns(Some(board_id), name)
+                            .await?
+                            .into_iter()
+                            .map(|column| column.id),
+                    );
+                }
+            }
+            None => columns.extend(
+                self.directory
+                    .find_columns(None, name)
+                    .await?
+                    .into_iter()
+                    .map(|column| column.id),
+            ),
+        }
+
+        if columns.is_empty() {
+            return Err(SDKError::not_found(format!("column '{}'", name)));
+        }
+        Ok(columns)
+    }
+
+    async fn resolve_sticker(
+        &self,
+        name: &str,
+        state: Option<&str>,
+    ) -> Result<Vec<(String, Option<HashSet<String>>)>, SDKError> {
+        let stickers = self.directory.find_stickers(name).await?;
+        if stickers.is_empty() {
+            return Err(SDKError::not_found(format!("sticker '{}'", name)));
+        }
+
+        let Some(state) = state else {
+            return Ok(stickers
+                .into_iter()
+                .map(|sticker| (sticker.id, None))
+                .collect());
+        };
+
+        let state = state.trim().to_lowercase();
+        let resolved: Vec<_> = stickers
+            .into_iter()
+            .filter_map(|sticker| {
+                let states: HashSet<String> = sticker
+                    .states
+                    .into_iter()
+                    .filter(|candidate| candidate.name.trim().to_lowercase() == state)
+                    .map(|candidate| candidate.id)
+                    .collect();
+                (!states.is_empty()).then_some((sticker.id, Some(states)))
+            })
+            .collect();
+
+        if resolved.is_empty() {
+            return Err(SDKError::not_found(format!(
+                "state '{}' of sticker '{}'",
+                state, name
+            )));
+        }
+        Ok(resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task() -> Task {
+        Task {
+            id: "task-1".to_string(),
+            title: "Fix Login page".to_string(),
+            column_id: Some("column-1".to_string()),
+            assigned: Some(vec!["user-1".to_string()]),
+            stickers: Some(TaskStickers::from([
+                (
+                    "priority".to_string(),
+                    StickerValue::StateId("high".to_string()),
+                ),
+                (
+                    "owner".to_string(),
+                    StickerValue::StateId(UNSET.to_string()),
+                ),
+            ])),
+            ..Default::default()
+        }
+    }
+
+    fn states(ids: &[&str]) -> Option<HashSet<String>> {
+        Some(ids.iter().map(|id| id.to_string()).collect())
+    }
+
+    #[test]
+    fn checks_match_tasks() {
+        let task = task();
+
+        assert!(Check::Title("LOGIN".to_string()).matches(&task, 0.0));
+        assert!(Check::Assignee(Some("user-1".to_string())).matches(&task, 0.0));
+        assert!(!Check::Assignee(None).matches(&task, 0.0));
+        assert!(
+            Check::Sticker(vec![("priority".to_string(), states(&["low", "high"]))])
+                .matches(&task, 0.0)
+        );
+        assert!(
+            !Check::Sticker(vec![("priority".to_string(), states(&["low"]))]).matches(&task, 0.0)
+        );
+        // A detached sticker does not count as set
+        assert!(!Check::Sticker(vec![("owner".to_string(), None)]).matches(&task, 0.0));
+        assert!(!Check::Overdue.matches(&task, 0.0));
+    }
+
+    #[test]
+    fn due_today_matches_the_whole_day() {
+        let mut task = task();
+        task.deadline = Some(Deadline {
+            deadline: 10.0 * DAY_MS + 1_000.0,
+            ..Default::default()
+        });
+
+        assert!(Check::Due(Comparison::Eq, 10.0 * DAY_MS).matches(&task, 0.0));
+        assert!(!Check::Due(Comparison::Eq, 11.0 * DAY_MS).matches(&task, 0.0));
+        assert!(Check::Due(Comparison::Lt, 11.0 * DAY_MS).matches(&task, 0.0));
+        assert!(Check::Overdue.matches(&task, 11.0 * DAY_MS));
+    }
+
+    #[test]
+    fn pushdown_keeps_the_first_filter_of_each_kind() {
+        let checks = [
+            (false, Check::Title("Login Page".to_string())),
+            (false, Check::Assignee(Some("user-1".to_string()))),
+            (false, Check::Assignee(Some("user-2".to_string()))),
+            (
+                false,
+                Check::Sticker(vec![("priority".to_string(), states(&["high"]))]),
+            ),
+            (
+                false,
+                Check::Columns(["a", "b"].map(String::from).into_iter().collect()),
+            ),
+            (
+                false,
+                Check::Columns(["b", "c"].map(String::from).into_iter().collect()),
+            ),
+        ];
+        let pushdown = Pushdown::from_checks(&checks);
+
+        assert_eq!(pushdown.title.as_deref(), Some("Login Page"));
+        assert_eq!(pushdown.assigned_to.as_deref(), Some("user-1"));
+        assert_eq!(pushdown.sticker_id.as_deref(), Some("priority"));
+        assert_eq!(pushdown.sticker_state_id.as_deref(), Some("high"));
+        assert_eq!(pushdown.columns, Some(vec!["b".to_string()]));
+        assert!(!pushdown.include_deleted);
+    }
+
+    #[test]
+    fn negated_and_ambiguous_checks_stay_client_side() {
+        let checks = [
+            (true, Check::Assignee(Some("user-1".to_string()))),
+            (true, Check::Title("draft".to_string())),
+            (
+                false,
+                Check::Sticker(vec![
+                    ("priority".to_string(), None),
+                    ("severity".to_string(), None),
+                ]),
+            ),
+            (
+                false,
+                Check::Sticker(vec![("size".to_string(), states(&["s", "m"]))]),
+            ),
+            (false, Check::Deleted),
+        ];
+        let pushdown = Pushdown::from_checks(&checks);
+
+        assert!(pushdown.assigned_to.is_none());
+        assert!(pushdown.title.is_none());
+        assert_eq!(pushdown.sticker_id.as_deref(), Some("size"));
+        assert!(pushdown.sticker_state_id.is_none());
+        assert!(pushdown.include_deleted);
+    }
+
+    #[tokio::test]
+    async fn assignee_me_fails_before_any_request() {
+        let client = Arc::new(YouGileClient::new(
+            yougile_api_client::apis::configuration::Configuration::new("token".to_string())
+                .with_base_path("http://127.0.0.1:9".to_string()),
+        ));
+        let error = TaskQueryBuilder::new(client, TaskQuery::parse("assignee:me"))
+            .execute()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, SDKError::ValidationError(_)));
+    }
+}
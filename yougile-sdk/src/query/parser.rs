@@ -0,0 +1,450 @@
+use crate::SDKError;
+use crate::time::{parse_date_ms, parse_duration_ms};
+
+/// A parsed task query: every filter must match
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TaskQuery {
+    pub filters: Vec<Filter>,
+}
+
+/// A single query term, optionally negated with a leading `-`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    pub negated: bool,
+    pub kind: FilterKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterKind {
+    /// `assignee:me`, `assignee:none`, `assignee:"Ivan Petrov"`
+    Assignee(UserRef),
+    /// `sticker:Priority` or `sticker:Priority=High`
+    Sticker {
+        name: String,
+        state: Option<String>,
+    },
+    /// `board:"Sprint 12"`
+    Board(String),
+    /// `column:Backlog`
+    Column(String),
+    /// `title:login`, matched against the title only
+    Title(String),
+    /// `text:login` or a bare word, matched against title and description
+    Text(String),
+    /// `color:red` or `color:task-red`
+    Color(String),
+    /// `due<7d`, `due>=2026-01-01`
+    Due(Comparison, TimeSpec),
+    /// `due:any` (true) or `due:none` (false)
+    HasDeadline(bool),
+    /// `checklist>=50` compares the percentage of completed items
+    ChecklistProgress(Comparison, f64),
+    /// `checklist:any` (true) or `checklist:none` (false)
+    HasChecklist(bool),
+    Completed,
+    Archived,
+    Deleted,
+    /// Deadline in the past and the task is not completed
+    Overdue,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UserRef {
+    Me,
+    Nobody,
+    Name(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl Comparison {
+    pub fn matches(self, left: f64, right: f64) -> bool {
+        match self {
+            Comparison::Lt => left < right,
+            Comparison::Le => left <= right,
+            Comparison::Gt => left > right,
+            Comparison::Ge => left >= right,
+            Comparison::Eq => left == right,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimeSpec {
+    /// Offset from the moment the query runs, in milliseconds
+    Relative(f64),
+    /// Absolute timestamp in milliseconds
+    Absolute(f64),
+}
+
+impl TimeSpec {
+    pub fn resolve(self, now: f64) -> f64 {
+        match self {
+            TimeSpec::Relative(offset) => now + offset,
+            TimeSpec::Absolute(timestamp) => timestamp,
+        }
+    }
+}
+
+impl TaskQuery {
+    /// Parse a query such as `assignee:me sticker:Priority=High due<7d -completed`
+    pub fn parse(input: &str) -> Result<TaskQuery, SDKError> {
+        let filters = tokenize(input)?
+            .into_iter()
+            .map(|token| match token {
+                Token::Term(term) => parse_term(&term),
+                Token::Literal { negated, text } => Ok(Filter {
+                    negated,
+                    kind: FilterKind::Text(text),
+                }),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(TaskQuery { filters })
+    }
+
+    /// Whether the query contains `assignee:me`
+    pub fn uses_me(&self) -> bool {
+        self.filters
+            .iter()
+            .any(|filter| filter.kind == FilterKind::Assignee(UserRef::Me))
+    }
+}
+
+impl std::str::FromStr for TaskQuery {
+    type Err = SDKError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        TaskQuery::parse(input)
+    }
+}
+
+enum Token {
+    Term(String),
+    /// A term that starts with a quote, searched as text whatever it contains
+    Literal {
+        negated: bool,
+        text: String,
+    },
+}
+
+/// Split on whitespace outside of double quotes; quotes are removed
+fn tokenize(input: &str) -> Result<Vec<Token>, SDKError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut quoted = false;
+    // Set when the term opens with a quote, optionally after a `-`
+    let mut literal: Option<bool> = None;
+
+    let mut finish = |current: &mut String, literal: &mut Option<bool>| {
+        let text = std::mem::take(current);
+        tokens.push(match literal.take() {
+            Some(negated) => Token::Literal { negated, text },
+            None => Token::Term(text),
+        });
+    };
+
+    for ch in input.chars() {
+        match ch {
+            '"' => {
+                if !quoted && (current.is_empty() || current == "-") {
+                    literal = Some(current == "-");
+                    current.clear();
+                }
+                in_quotes = !in_quotes;
+                quoted = true;
+            }
+            ch if ch.is_whitespace() && !in_quotes => {
+                if !current.is_empty() || quoted {
+                    finish(&mut current, &mut literal);
+                }
+                quoted = false;
+            }
+            ch => current.push(ch),
+        }
+    }
+
+    if in_quotes {
+        return Err(SDKError::validation("Unterminated quote in query"));
+    }
+    if !current.is_empty() || quoted {
+        finish(&mut current, &mut literal);
+    }
+
+    Ok(tokens)
+}
+
+fn parse_term(token: &str) -> Result<Filter, SDKError> {
+    let (negated, term) = match token.strip_prefix('-') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, token),
+    };
+
+    let key_len = term
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+        .unwrap_or(term.len());
+    let (key, rest) = term.split_at(key_len);
+
+    let operator = ["<=", ">=", ":", "<", ">", "="]
+        .into_iter()
+        .find(|operator| rest.starts_with(operator));
+
+    let kind = match operator {
+        Some(operator) if !key.is_empty() => {
+            parse_keyed(&key.to_lowercase(), operator, &rest[operator.len()..])?
+        }
+        _ => parse_bare(term),
+    };
+
+    Ok(Filter { negated, kind })
+}
+
+fn parse_bare(word: &str) -> FilterKind {
+    match word.to_lowercase().as_str() {
+        "completed" | "done" => FilterKind::Completed,
+        "archived" => FilterKind::Archived,
+        "deleted" => FilterKind::Deleted,
+        "overdue" => FilterKind::Overdue,
+        _ => FilterKind::Text(word.to_string()),
+    }
+}
+
+fn parse_keyed(key: &str, operator: &str, value: &str) -> Result<FilterKind, SDKError> {
+    let is_match = operator == ":" || operator == "=";
+
+    let kind = match key {
+        "assignee" | "assigned" if is_match => FilterKind::Assignee(match value {
+            "me" => UserRef::Me,
+            "none" | "nobody" => UserRef::Nobody,
+            name => UserRef::Name(name.to_string()),
+        }),
+        "sticker" if is_match => match value.split_once('=') {
+            Some((name, state)) => FilterKind::Sticker {
+                name: name.to_string(),
+                state: Some(state.to_string()),
+            },
+            None => FilterKind::Sticker {
+                name: value.to_string(),
+                state: None,
+            },
+        },
+        "board" if is_match => FilterKind::Board(value.to_string()),
+        "column" if is_match => FilterKind::Column(value.to_string()),
+        "title" if is_match => FilterKind::Title(value.to_string()),
+        "text" if is_match => FilterKind::Text(value.to_string()),
+        "color" if is_match => FilterKind::Color(value.to_string()),
+        "due" if is_match => match value {
+            "any" => FilterKind::HasDeadline(true),
+            "none" => FilterKind::HasDeadline(false),
+            "overdue" => FilterKind::Overdue,
+            other => FilterKind::Due(Comparison::Eq, parse_time(other)?),
+        },
+        "due" => FilterKind::Due(parse_comparison(operator), parse_time(value)?),
+        "checklist" if is_match => match value {
+            "any" => FilterKind::HasChecklist(true),
+            "none" => FilterKind::HasChecklist(false),
+            "done" => FilterKind::ChecklistProgress(Comparison::Ge, 100.0),
+            "open" => FilterKind::ChecklistProgress(Comparison::Lt, 100.0),
+            other => FilterKind::ChecklistProgress(Comparison::Eq, parse_percent(other)?),
+        },
+        "checklist" => {
+            FilterKind::ChecklistProgress(parse_comparison(operator), parse_percent(value)?)
+        }
+        "is" if is_match => match parse_bare(value) {
+            FilterKind::Text(_) => {
+                return Err(SDKError::validation(format!("Unknown flag: is:{}", value)));
+            }
+            flag => flag,
+        },
+        _ => {
+            return Err(SDKError::validation(format!(
+                "Unsupported query term: {}{}{}",
+                key, operator, value
+            )));
+        }
+    };
+
+    Ok(kind)
+}
+
+fn parse_comparison(operator: &str) -> Comparison {
+    match operator {
+        "<" => Comparison::Lt,
+        "<=" => Comparison::Le,
+        ">" => Comparison::Gt,
+        ">=" => Comparison::Ge,
+        _ => Comparison::Eq,
+    }
+}
+
+fn parse_time(value: &str) -> Result<TimeSpec, SDKError> {
+    match value {
+        "now" => Ok(TimeSpec::Relative(0.0)),
+        _ => parse_duration_ms(value)
+            .map(TimeSpec::Relative)
+            .or_else(|| parse_date_ms(value).map(TimeSpec::Absolute))
+            .ok_or_else(|| {
+                SDKError::validation(format!(
+                    "Invalid time '{}', expected e.g. 7d, -2h or 2026-01-31",
+                    value
+                ))
+            }),
+    }
+}
+
+fn parse_percent(value: &str) -> Result<f64, SDKError> {
+    value
+        .trim_end_matches('%')
+        .parse()
+        .map_err(|_| SDKError::validation(format!("Invalid percentage '{}'", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::DAY_MS;
+
+    fn kinds(input: &str) -> Vec<FilterKind> {
+        TaskQuery::parse(input)
+            .unwrap()
+            .filters
+            .into_iter()
+            .map(|filter| filter.kind)
+            .collect()
+    }
+
+    #[test]
+    fn parses_keyed_terms() {
+        assert_eq!(
+            kinds("assignee:me sticker:Priority=High column:Backlog color:red"),
+            vec![
+                FilterKind::Assignee(UserRef::Me),
+                FilterKind::Sticker {
+                    name: "Priority".to_string(),
+                    state: Some("High".to_string()),
+                },
+                FilterKind::Column("Backlog".to_string()),
+                FilterKind::Color("red".to_string()),
+            ]
+        );
+        assert_eq!(
+            kinds("ASSIGNEE:none sticker:Estimate"),
+            vec![
+                FilterKind::Assignee(UserRef::Nobody),
+                FilterKind::Sticker {
+                    name: "Estimate".to_string(),
+                    state: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn quoted_values_keep_spaces() {
+        assert_eq!(
+            kinds(r#"assignee:"Ivan Petrov" board:"Sprint 12" """#),
+            vec![
+                FilterKind::Assignee(UserRef::Name("Ivan Petrov".to_string())),
+                FilterKind::Board("Sprint 12".to_string()),
+                FilterKind::Text(String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn quoted_terms_are_literal_text() {
+        let query = TaskQuery::parse(r#""assignee:me" -"due<7d" "-completed""#).unwrap();
+        let filters: Vec<(bool, FilterKind)> = query
+            .filters
+            .into_iter()
+            .map(|filter| (filter.negated, filter.kind))
+            .collect();
+        assert_eq!(
+            filters,
+            vec![
+                (false, FilterKind::Text("assignee:me".to_string())),
+                (true, FilterKind::Text("due<7d".to_string())),
+                (false, FilterKind::Text("-completed".to_string())),
+            ]
+        );
+        assert!(!TaskQuery::parse(r#""assignee:me""#).unwrap().uses_me());
+        assert!(TaskQuery::parse("assignee:me").unwrap().uses_me());
+    }
+
+    #[test]
+    fn negation_and_flags() {
+        let query = TaskQuery::parse("-completed is:archived done -").unwrap();
+        let filters: Vec<(bool, FilterKind)> = query
+            .filters
+            .into_iter()
+            .map(|filter| (filter.negated, filter.kind))
+            .collect();
+        assert_eq!(
+            filters,
+            vec![
+                (true, FilterKind::Completed),
+                (false, FilterKind::Archived),
+                (false, FilterKind::Completed),
+                (false, FilterKind::Text("-".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_deadlines() {
+        assert_eq!(
+            kinds("due<7d due>=2026-01-01 due:none due:overdue due>-2h"),
+            vec![
+                FilterKind::Due(Comparison::Lt, TimeSpec::Relative(7.0 * DAY_MS)),
+                FilterKind::Due(Comparison::Ge, TimeSpec::Absolute(20_454.0 * DAY_MS)),
+                FilterKind::HasDeadline(false),
+                FilterKind::Overdue,
+                FilterKind::Due(Comparison::Gt, TimeSpec::Relative(-2.0 * DAY_MS / 24.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_checklists() {
+        assert_eq!(
+            kinds("checklist>=50% checklist:done checklist:any checklist:30"),
+            vec![
+                FilterKind::ChecklistProgress(Comparison::Ge, 50.0),
+                FilterKind::ChecklistProgress(Comparison::Ge, 100.0),
+                FilterKind::HasChecklist(true),
+                FilterKind::ChecklistProgress(Comparison::Eq, 30.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_queries() {
+        for input in [
+            r#"title:"unterminated"#,
+            "due<soon",
+            "checklist>half",
+            "is:pending",
+            "board<3",
+            "owner:me",
+        ] {
+            assert!(TaskQuery::parse(input).is_err(), "{} should fail", input);
+        }
+    }
+
+    #[test]
+    fn time_spec_resolves_against_now() {
+        assert_eq!(
+            TimeSpec::Relative(DAY_MS).resolve(1_000.0),
+            1_000.0 + DAY_MS
+        );
+        assert_eq!(TimeSpec::Absolute(5.0).resolve(1_000.0), 5.0);
+    }
+}
@@ -0,0 +1,528 @@
+//! Mentions, task references and URLs in messages and descriptions
+//!
+//! [`References::extract`] finds:
+//!
+//! - mentions written as `@word`, where the word is matched against user
+//!   emails and names
+//! - task codes such as `ABC-12` or `ЗАД-3`, the `id_task_project` or
+//!   `id_task_common` of a task; a code is uppercase letters of any script, a
+//!   dash and digits, so words like `utf-8` are not taken for one
+//! - `http://` and `https://` URLs
+//!
+//! Extraction works on plain text only. The HTML YouGile uses for mentions and
+//! task links is not documented, so HTML is converted with
+//! [`crate::markup::html_to_text`] first and only the visible `@name` and task
+//! codes are found; IDs that the markup may carry are not read. A
+//! [`ReferenceResolver`] fills in the IDs of the referenced users and tasks.
+
+use crate::markup::html_to_text;
+use crate::resources::{ChatsAPI, GroupChatsAPI};
+use crate::{Directory, SDKError};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// A mention of a user
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mention {
+    /// The mention as written, without the `@`
+    pub text: String,
+    pub user_id: Option<String>,
+}
+
+/// A reference to a task by ID or code
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskReference {
+    /// The code or ID as written
+    pub text: String,
+    pub task_id: Option<String>,
+}
+
+/// Everything referenced by a piece of text, in order of appearance
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct References {
+    pub mentions: Vec<Mention>,
+    pub tasks: Vec<TaskReference>,
+    pub urls: Vec<String>,
+}
+
+impl References {
+    /// References in plain text or Markdown
+    pub fn extract(text: &str) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let mut references = Self::default();
+        let mut index = 0;
+
+        while index < chars.len() {
+            let ch = chars[index];
+            let word_start = index == 0 || !is_word(chars[index - 1]);
+
+            if word_start
+                && (starts_with(&chars[index..], "http://")
+                    || starts_with(&chars[index..], "https://"))
+            {
+                let end = (index..chars.len())
+                    .find(|&end| {
+                        chars[end].is_whitespace() || matches!(chars[end], '<' | '>' | '"')
+                    })
+                    .unwrap_or(chars.len());
+                let url: String = chars[index..end].iter().collect();
+                let url = trim_url(&url);
+                if !references.urls.iter().any(|known| known == url) {
+                    references.urls.push(url.to_string());
+                }
+                index = end;
+                continue;
+            }
+
+            if ch == '@' && word_start {
+                let end = (index + 1..chars.len())
+                    .find(|&end| !(is_word(chars[end]) || matches!(chars[end], '.' | '-')))
+                    .unwrap_or(chars.len());
+                let name: String = chars[index + 1..end].iter().collect();
+                let name = name.trim_end_matches(['.', '-']);
+                if !name.is_empty() {
+                    references.add_mention(name, None);
+                }
+                index = end.max(index + 1);
+                continue;
+            }
+
+            if ch.is_alphabetic() && word_start {
+                let end = (index..chars.len())
+                    .find(|&end| !is_word(chars[end]))
+                    .unwrap_or(chars.len());
+                if let Some(code_end) = task_code_end(&chars, index, end) {
+                    let code: String = chars[index..code_end].iter().collect();
+                    references.add_task(&code, None);
+                    index = code_end;
+                } else {
+                    index = end;
+                }
+                continue;
+            }
+
+            index += 1;
+        }
+
+        references
+    }
+
+    /// References in the visible text of YouGile HTML
+    pub fn extract_html(html: &str) -> Self {
+        Self::extract(&html_to_text(html))
+    }
+
+    /// References in the text and HTML of a chat message
+    pub fn from_message(message: &ChatMessage) -> Self {
+        let mut references = Self::extract_html(&message.text_html);
+        references.merge(Self::extract(&message.text));
+        references
+    }
+
+    /// References in the title and description of a task
+    pub fn from_task(task: &Task) -> Self {
+        let mut references = Self::extract(&task.title);
+        if let Some(description) = &task.description {
+            references.merge(Self::extract_html(description));
+        }
+        references
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mentions.is_empty() && self.tasks.is_empty() && self.urls.is_empty()
+    }
+
+    /// Add the references of `other` that are not already known
+    pub fn merge(&mut self, other: References) {
+        for mention in other.mentions {
+            self.add_mention(&mention.text, mention.user_id.as_deref());
+        }
+        for task in other.tasks {
+            self.add_task(&task.text, task.task_id.as_deref());
+        }
+        for url in other.urls {
+            if !self.urls.contains(&url) {
+                self.urls.push(url);
+            }
+        }
+    }
+
+    /// IDs of the mentioned users that are known or resolved
+    pub fn user_ids(&self) -> Vec<&str> {
+        self.mentions
+            .iter()
+            .filter_map(|mention| mention.user_id.as_deref())
+            .collect()
+    }
+
+    /// IDs of the referenced tasks that are known or resolved
+    pub fn task_ids(&self) -> Vec<&str> {
+        self.tasks
+            .iter()
+            .filter_map(|task| task.task_id.as_deref())
+            .collect()
+    }
+
+    fn add_mention(&mut self, text: &str, user_id: Option<&str>) {
+        // A mention with an ID replaces the same mention written by name
+        let known = self.mentions.iter_mut().find(|mention| {
+            (user_id.is_some() && mention.user_id.as_deref() == user_id)
+                || mention.text.eq_ignore_ascii_case(text)
+        });
+        match known {
+            Some(mention) => {
+                if mention.user_id.is_none() {
+                    mention.user_id = user_id.map(str::to_string);
+                }
+            }
+            None => self.mentions.push(Mention {
+                text: text.to_string(),
+                user_id: user_id.map(str::to_string),
+            }),
+        }
+    }
+
+    fn add_task(&mut self, text: &str, task_id: Option<&str>) {
+        let known = self.tasks.iter_mut().find(|task| {
+            (task_id.is_some() && task.task_id.as_deref() == task_id)
+                || task.text.eq_ignore_ascii_case(text)
+        });
+        match known {
+            Some(task) => {
+                if task.task_id.is_none() {
+                    task.task_id = task_id.map(str::to_string);
+                }
+            }
+            None => self.tasks.push(TaskReference {
+                text: text.to_string(),
+                task_id: task_id.map(str::to_string),
+            }),
+        }
+    }
+}
+
+/// A mention of a user found by [`MentionSearchBuilder`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MentionHit {
+    pub chat_id: String,
+    pub message: ChatMessage,
+}
+
+/// Resolves mentions by name and task codes to IDs
+pub struct ReferenceResolver {
+    client: Arc<YouGileClient>,
+    directory: Arc<Directory>,
+    task_codes: HashMap<String, String>,
+}
+
+impl ReferenceResolver {
+    pub fn new(client: Arc<YouGileClient>, directory: Arc<Directory>) -> Self {
+        Self {
+            client,
+            directory,
+            task_codes: HashMap::new(),
+        }
+    }
+
+    /// Tasks whose codes can be resolved, e.g. the tasks of a board
+    ///
+    /// The API cannot look tasks up by code, so codes of other tasks stay
+    /// unresolved.
+    pub fn with_tasks<'a>(mut self, tasks: impl IntoIterator<Item = &'a Task>) -> Self {
+        for task in tasks {
+            for code in [&task.id_task_project, &task.id_task_common]
+                .into_iter()
+                .flatten()
+            {
+                self.task_codes.insert(code.to_lowercase(), task.id.clone());
+            }
+        }
+        self
+    }
+
+    /// Fill in the user and task IDs of unresolved references
+    ///
+    /// A mention resolves if it equals a user's email, the part of the email
+    /// before `@`, or the user's name without spaces. Ambiguous mentions stay
+    /// unresolved.
+    pub async fn resolve(&self, references: &mut References) -> Result<(), SDKError> {
+        if references
+            .mentions
+            .iter()
+            .any(|mention| mention.user_id.is_none())
+        {
+            let users = self.directory.users().await?;
+            for mention in &mut references.mentions {
+                if mention.user_id.is_none() {
+                    mention.user_id = match_user(&users, &mention.text);
+                }
+            }
+        }
+
+        for task in &mut references.tasks {
+            if task.task_id.is_none() {
+                task.task_id = self.task_codes.get(&task.text.to_lowercase()).cloned();
+            }
+        }
+        Ok(())
+    }
+
+    /// Extract and resolve the references of a chat message
+    pub async fn message(&self, message: &ChatMessage) -> Result<References, SDKError> {
+        let mut references = References::from_message(message);
+        self.resolve(&mut references).await?;
+        Ok(references)
+    }
+
+    /// Extract and resolve the references of a task
+    pub async fn task(&self, task: &Task) -> Result<References, SDKError> {
+        let mut references = References::from_task(task);
+        self.resolve(&mut references).await?;
+        Ok(references)
+    }
+
+    /// Search chats for messages that mention a user
+    ///
+    /// A message mentions the user if its text contains an `@name` that
+    /// resolves to them, see [`ReferenceResolver::resolve`].
+    pub fn mentions_of(&self, user_id: &str) -> MentionSearchBuilder<'_> {
+        MentionSearchBuilder {
+            resolver: self,
+            user_id: user_id.to_string(),
+            since: None,
+            chat_ids: Vec::new(),
+            group_chats: true,
+        }
+    }
+}
+
+/// Search for messages mentioning a user, e.g. to collect "mentions of me"
+pub struct MentionSearchBuilder<'a> {
+    resolver: &'a ReferenceResolver,
+    user_id: String,
+    since: Option<f64>,
+    chat_ids: Vec<String>,
+    group_chats: bool,
+}
+
+impl MentionSearchBuilder<'_> {
+    /// Only messages posted after `since`
+    pub fn since(mut self, since: f64) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Also search a chat, e.g. the chat of a task
+    pub fn chat(mut self, chat_id: impl Into<String>) -> Self {
+        self.chat_ids.push(chat_id.into());
+        self
+    }
+
+    pub fn chats(mut self, chat_ids: impl IntoIterator<Item = String>) -> Self {
+        self.chat_ids.extend(chat_ids);
+        self
+    }
+
+    /// Whether to search every group chat, true by default
+    pub fn group_chats(mut self, include: bool) -> Self {
+        self.group_chats = include;
+        self
+    }
+
+    /// Messages by other users that mention the user, oldest first
+    pub async fn execute(self) -> Result<Vec<MentionHit>, SDKError> {
+        let client = self.resolver.client.clone();
+        let mut chat_ids = self.chat_ids;
+        if self.group_chats {
+            for chat in GroupChatsAPI::new(client.clone()).list_all().await? {
+                if chat.deleted != Some(true) && !chat_ids.contains(&chat.id) {
+                    chat_ids.push(chat.id);
+                }
+            }
+        }
+
+        let chats = ChatsAPI::new(client);
+        let mut hits = Vec::new();
+        for chat_id in chat_ids {
+            let mut search = chats.search(&chat_id);
+            if let Some(since) = self.since {
+                search = search.since(since);
+            }
+
+            for message in search.all().await? {
+                if message.from_user_id == self.user_id
+                    || self.since.is_some_and(|since| message.id <= since)
+                {
+                    continue;
+                }
+                let references = self.resolver.message(&message).await?;
+                if references.user_ids().contains(&self.user_id.as_str()) {
+                    hits.push(MentionHit {
+                        chat_id: chat_id.clone(),
+                        message,
+                    });
+                }
+            }
+        }
+
+        hits.sort_by(|a, b| a.message.id.total_cmp(&b.message.id));
+        Ok(hits)
+    }
+}
+
+fn is_word(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+fn starts_with(chars: &[char], prefix: &str) -> bool {
+    let prefix: Vec<char> = prefix.chars().collect();
+    chars.len() >= prefix.len()
+        && chars
+            .iter()
+            .zip(&prefix)
+            .all(|(ch, expected)| ch.to_ascii_lowercase() == *expected)
+}
+
+/// End of a task code like `ABC-12` whose uppercase letters span `start..letters_end`
+fn task_code_end(chars: &[char], start: usize, letters_end: usize) -> Option<usize> {
+    if chars.get(letters_end) != Some(&'-')
+        || !chars[start..letters_end].iter().all(|ch| ch.is_uppercase())
+    {
+        return None;
+    }
+    let digits_end = (letters_end + 1..chars.len())
+        .find(|&end| !chars[end].is_ascii_digit())
+        .unwrap_or(chars.len());
+    let has_digits = digits_end > letters_end + 1;
+    let ends_word = chars.get(digits_end).is_none_or(|&ch| !is_word(ch));
+    (has_digits && ends_word).then_some(digits_end)
+}
+
+/// Strip punctuation that ends the sentence rather than the URL
+fn trim_url(url: &str) -> &str {
+    let mut url = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '\'']);
+    // Keep a closing parenthesis only if the URL opened one
+    while url.ends_with(')') && url.matches('(').count() < url.matches(')').count() {
+        url = url[..url.len() - 1].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+    }
+    url
+}
+
+fn match_user(users: &[User], mention: &str) -> Option<String> {
+    let mention = mention.to_lowercase();
+    let matches: Vec<&User> = users
+        .iter()
+        .filter(|user| {
+            let email = user.email.to_lowercase();
+            let name: String = user
+                .real_name
+                .to_lowercase()
+                .chars()
+                .filter(|ch| !ch.is_whitespace())
+                .collect();
+            email == mention || email.split('@').next() == Some(mention.as_str()) || name == mention
+        })
+        .collect();
+
+    match matches[..] {
+        [user] => Some(user.id.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task_codes(text: &str) -> Vec<String> {
+        References::extract(text)
+            .tasks
+            .into_iter()
+            .map(|task| task.text)
+            .collect()
+    }
+
+    #[test]
+    fn finds_task_codes() {
+        assert_eq!(
+            task_codes("Fixed ABC-12 and ID-7, see ABC-12 again."),
+            vec!["ABC-12", "ID-7"]
+        );
+        assert_eq!(task_codes("Готово: ЗАД-3, ЗАД-3"), vec!["ЗАД-3"]);
+    }
+
+    #[test]
+    fn ignores_words_that_only_look_like_codes() {
+        assert!(task_codes("utf-8 text, x-1, Covid-19, ABC-, ABC-12a, abc-12, Зад-3").is_empty());
+    }
+
+    #[test]
+    fn finds_mentions() {
+        let references = References::extract("Hi @ivan.petrov, @Anna and @анна. mail@example.com");
+        let names: Vec<&str> = references
+            .mentions
+            .iter()
+            .map(|mention| mention.text.as_str())
+            .collect();
+        assert_eq!(names, vec!["ivan.petrov", "Anna", "анна"]);
+        assert!(references.user_ids().is_empty());
+    }
+
+    #[test]
+    fn finds_urls_without_trailing_punctuation() {
+        let references = References::extract(
+            "See https://example.com/a_(b). Also (http://example.org/x), https://example.com/a_(b)",
+        );
+        assert_eq!(
+            references.urls,
+            vec!["https://example.com/a_(b)", "http://example.org/x"]
+        );
+    }
+
+    #[test]
+    fn html_is_read_as_visible_text() {
+        let references = References::extract_html(
+            "<p><b>@Anna</b>, see ABC-12 and <a href=\"https://example.com/x\">docs</a></p>",
+        );
+        assert_eq!(references.mentions[0].text, "Anna");
+        assert_eq!(references.mentions[0].user_id, None);
+        assert_eq!(references.tasks[0].text, "ABC-12");
+        assert_eq!(references.urls, vec!["https://example.com/x"]);
+    }
+
+    #[test]
+    fn merge_fills_in_ids_without_duplicates() {
+        let mut references = References::extract("@anna ABC-12");
+        let mut other = References::extract("@Anna ABC-12 https://example.com");
+        other.mentions[0].user_id = Some("u1".to_string());
+        other.tasks[0].task_id = Some("t1".to_string());
+        references.merge(other);
+        assert_eq!(references.mentions.len(), 1);
+        assert_eq!(references.user_ids(), vec!["u1"]);
+        assert_eq!(references.task_ids(), vec!["t1"]);
+        assert_eq!(references.urls, vec!["https://example.com"]);
+    }
+
+    #[test]
+    fn resolves_mentions_by_email_and_name() {
+        let user = |id: &str, email: &str, name: &str| User {
+            id: id.to_string(),
+            email: email.to_string(),
+            real_name: name.to_string(),
+            ..Default::default()
+        };
+        let users = [
+            user("u1", "ivan.petrov@example.com", "Иван Петров"),
+            user("u2", "anna@example.com", "Anna Smith"),
+            user("u3", "anna@example.org", "Anna Lee"),
+        ];
+
+        assert_eq!(match_user(&users, "Ivan.Petrov"), Some("u1".to_string()));
+        assert_eq!(match_user(&users, "иванпетров"), Some("u1".to_string()));
+        assert_eq!(match_user(&users, "annasmith"), Some("u2".to_string()));
+        assert_eq!(match_user(&users, "anna"), None);
+    }
+}
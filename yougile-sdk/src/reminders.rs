@@ -0,0 +1,592 @@
+//! Deadline reminders and overdue digests
+//!
+//! [`DeadlineReminders`] scans tasks for deadlines that are due soon or already
+//! passed, and for start dates coming up, and posts a digest grouped by assignee, either into the chat of each
+//! task or into one group chat. Sent reminders are recorded in a state file so
+//! the same reminder is not posted twice:
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use yougile_sdk::YouGileSDK;
+//! use yougile_sdk::reminders::ReminderTarget;
+//!
+//! # async fn example(sdk: YouGileSDK) -> Result<(), yougile_sdk::SDKError> {
+//! let mut reminders = sdk
+//!     .reminders("reminders-state.json")
+//!     .await?
+//!     .window(Duration::from_secs(24 * 60 * 60))
+//!     .target(ReminderTarget::GroupChat("chat-id".into()));
+//! let search = sdk.tasks().search().column_id("column-id");
+//! reminders.run(search, false).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::markup::text_to_html;
+use crate::resources::ChatsAPI;
+use crate::resources::tasks::{TaskSet, TasksAPI};
+use crate::time::{DAY_MS, HOUR_MS, MINUTE_MS, format_date, now_ms};
+use crate::{Directory, SDKError, state};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// Reminders of tasks outside the scanned set are forgotten once their date is
+/// this far in the past
+const STATE_RETENTION_MS: f64 = 90.0 * DAY_MS;
+
+/// Where digest messages are posted
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReminderTarget {
+    /// One message in the chat of every due task
+    TaskChats,
+    /// One digest of all due tasks in a group chat
+    GroupChat(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DueKind {
+    Overdue,
+    DueSoon,
+    /// The start date falls into the reminder window
+    StartsSoon,
+}
+
+impl DueKind {
+    fn key(self) -> &'static str {
+        match self {
+            DueKind::Overdue => "overdue",
+            DueKind::DueSoon => "due",
+            DueKind::StartsSoon => "start",
+        }
+    }
+}
+
+/// A task whose deadline or start date falls into the reminder window, or
+/// whose deadline has passed
+#[derive(Clone, Debug, PartialEq)]
+pub struct DueTask {
+    pub task_id: String,
+    pub title: String,
+    /// Project task code such as `ABC-12`, if known
+    pub code: Option<String>,
+    /// Deadline as set on the task
+    pub deadline: f64,
+    /// Moment the task is due: the deadline itself, or the end of its day in
+    /// the configured UTC offset for deadlines without a time
+    pub due_at: f64,
+    pub with_time: bool,
+    pub start_date: Option<f64>,
+    /// Moment work on the task starts: the start date itself, or the beginning
+    /// of its day in the configured UTC offset for dates without a time
+    pub starts_at: Option<f64>,
+    pub kind: DueKind,
+    pub assigned: Vec<String>,
+}
+
+impl DueTask {
+    fn reminder_key(&self) -> String {
+        let date = match self.kind {
+            DueKind::StartsSoon => self.start_date.unwrap_or(self.deadline),
+            _ => self.deadline,
+        };
+        format!("{}:{}:{}", self.task_id, self.kind.key(), date)
+    }
+
+    /// Moment the reminder is about, used for ordering
+    fn at(&self) -> f64 {
+        match self.kind {
+            DueKind::StartsSoon => self.starts_at.unwrap_or(self.due_at),
+            _ => self.due_at,
+        }
+    }
+
+    /// Deadline, or start date for tasks starting soon, in local time
+    /// `offset` milliseconds ahead of UTC
+    fn due_label(&self, offset: f64) -> String {
+        let date = match self.kind {
+            DueKind::StartsSoon => self.start_date.unwrap_or(self.deadline),
+            _ => self.deadline,
+        };
+        if self.with_time {
+            format_time(date + offset)
+        } else {
+            format_date(date + offset)
+        }
+    }
+
+    fn line(&self, offset: f64) -> String {
+        let kind = match self.kind {
+            DueKind::Overdue => "overdue",
+            DueKind::DueSoon => "due",
+            DueKind::StartsSoon => "starts",
+        };
+        match &self.code {
+            Some(code) => format!(
+                "{} {}: {} {}",
+                code,
+                self.title,
+                kind,
+                self.due_label(offset)
+            ),
+            None => format!("{}: {} {}", self.title, kind, self.due_label(offset)),
+        }
+    }
+}
+
+/// A message posted (or planned in a dry run) by [`DeadlineReminders::run`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DigestMessage {
+    pub chat_id: String,
+    pub text: String,
+    pub text_html: String,
+    pub task_ids: Vec<String>,
+}
+
+/// Persisted reminders, keyed by task, kind and date, with the time they were sent
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReminderState {
+    pub sent: HashMap<String, f64>,
+}
+
+/// Posts digests of due and overdue tasks and remembers which reminders were sent
+pub struct DeadlineReminders {
+    client: Arc<YouGileClient>,
+    directory: Option<Arc<Directory>>,
+    state_path: PathBuf,
+    state: ReminderState,
+    window: f64,
+    target: ReminderTarget,
+    repeat_overdue: Option<f64>,
+    /// Offset of local time from UTC in milliseconds
+    utc_offset: f64,
+}
+
+impl DeadlineReminders {
+    /// Create a reminder job, loading its state from `state_path` if the file exists
+    ///
+    /// By default tasks due within one day are reminded about in their own chats,
+    /// and overdue tasks are reminded about once per deadline.
+    pub async fn load(
+        client: Arc<YouGileClient>,
+        state_path: impl AsRef<Path>,
+    ) -> Result<Self, SDKError> {
+        let state_path = state_path.as_ref().to_path_buf();
+        let state = state::load(&state_path).await?;
+
+        Ok(Self {
+            client,
+            directory: None,
+            state_path,
+            state,
+            window: DAY_MS,
+            target: ReminderTarget::TaskChats,
+            repeat_overdue: None,
+            utc_offset: 0.0,
+        })
+    }
+
+    /// Resolve assignee names through a shared directory instead of showing IDs
+    pub fn directory(mut self, directory: Arc<Directory>) -> Self {
+        self.directory = Some(directory);
+        self
+    }
+
+    /// Remind about tasks due within `window` from now
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window.as_secs_f64() * 1000.0;
+        self
+    }
+
+    pub fn target(mut self, target: ReminderTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Remind about overdue tasks again once `interval` has passed since the last reminder
+    pub fn repeat_overdue(mut self, interval: Duration) -> Self {
+        self.repeat_overdue = Some(interval.as_secs_f64() * 1000.0);
+        self
+    }
+
+    /// Interpret dates in a time zone `minutes` ahead of UTC, UTC by default
+    ///
+    /// Deadlines without a time are due at the end of their day in that zone,
+    /// and dates in messages are shown in it.
+    pub fn utc_offset(mut self, minutes: i32) -> Self {
+        self.utc_offset = minutes as f64 * MINUTE_MS;
+        self
+    }
+
+    pub fn state(&self) -> &ReminderState {
+        &self.state
+    }
+
+    /// Tasks that need a reminder at `now`, most overdue first
+    ///
+    /// Completed, archived and deleted tasks are ignored. A task is due soon or
+    /// overdue by its deadline, even if it has not started yet; otherwise it is
+    /// reminded about when its start date falls into the window. Reminders
+    /// already recorded in the state are left out.
+    pub fn scan(&self, tasks: &[Task], now: f64) -> Vec<DueTask> {
+        let mut due: Vec<DueTask> = self
+            .candidates(tasks, now)
+            .filter(|due| self.needs_reminder(due, now))
+            .collect();
+
+        due.sort_by(|a, b| a.at().total_cmp(&b.at()));
+        due
+    }
+
+    /// Messages for the due tasks, grouped by assignee
+    pub async fn digests(&self, due: &[DueTask]) -> Result<Vec<DigestMessage>, SDKError> {
+        match &self.target {
+            ReminderTarget::TaskChats => {
+                let mut messages = Vec::with_capacity(due.len());
+                for task in due {
+                    let names = self.names(&task.assigned).await?;
+                    let mut text = task.line(self.utc_offset);
+                    if !names.is_empty() {
+                        text.push_str(&format!("\nAssigned: {}", names.join(", ")));
+                    }
+                    messages.push(DigestMessage {
+                        chat_id: task.task_id.clone(),
+                        text_html: text_to_html(&text),
+                        text,
+                        task_ids: vec![task.task_id.clone()],
+                    });
+                }
+                Ok(messages)
+            }
+            ReminderTarget::GroupChat(chat_id) => {
+                if due.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                // Unassigned tasks are listed last
+                let mut groups: BTreeMap<Option<String>, Vec<&DueTask>> = BTreeMap::new();
+                for task in due {
+                    if task.assigned.is_empty() {
+                        groups.entry(None).or_default().push(task);
+                    }
+                    for user_id in &task.assigned {
+                        groups.entry(Some(user_id.clone())).or_default().push(task);
+                    }
+                }
+
+                let mut sections = Vec::new();
+                let mut unassigned = None;
+                for (user_id, tasks) in groups {
+                    let lines: Vec<String> = tasks
+                        .iter()
+                        .map(|task| format!("- {}", task.line(self.utc_offset)))
+                        .collect();
+                    match user_id {
+                        Some(user_id) => {
+                            let name = self.names(std::slice::from_ref(&user_id)).await?;
+                            sections.push(format!("{}:\n{}", name[0], lines.join("\n")));
+                        }
+                        None => unassigned = Some(format!("Unassigned:\n{}", lines.join("\n"))),
+                    }
+                }
+                sections.extend(unassigned);
+
+                let text = format!("Deadline digest\n\n{}", sections.join("\n\n"));
+                Ok(vec![DigestMessage {
+                    chat_id: chat_id.clone(),
+                    text_html: text_to_html(&text),
+                    text,
+                    task_ids: due.iter().map(|task| task.task_id.clone()).collect(),
+                }])
+            }
+        }
+    }
+
+    /// Post reminders for every due task of the set
+    ///
+    /// With `dry_run`, the messages are returned without being posted and the
+    /// state is left unchanged. If posting fails, reminders sent before the
+    /// failure are still recorded.
+    pub async fn run(
+        &mut self,
+        tasks: impl Into<TaskSet>,
+        dry_run: bool,
+    ) -> Result<Vec<DigestMessage>, SDKError> {
+        let now = now_ms();
+        let tasks = TasksAPI::new(self.client.clone())
+            .load(tasks.into())
+            .await?;
+        let due = self.scan(&tasks, now);
+        let messages = self.digests(&due).await?;
+        if dry_run {
+            return Ok(messages);
+        }
+        let pruned = self.prune(&tasks, now);
+
+        let chats = ChatsAPI::new(self.client.clone());
+        let mut result = Ok(());
+        for message in &messages {
+            let sent = chats
+                .send_message(
+                    &message.chat_id,
+                    CreateChatMessage {
+                        text: message.text.clone(),
+                        text_html: message.text_html.clone(),
+                        label: String::new(),
+                    },
+                )
+                .await;
+            if let Err(error) = sent {
+                result = Err(error);
+                break;
+            }
+
+            for task in due
+                .iter()
+                .filter(|task| message.task_ids.contains(&task.task_id))
+            {
+                self.state.sent.insert(task.reminder_key(), now);
+            }
+        }
+
+        if pruned || !messages.is_empty() {
+            state::save(&self.state_path, &self.state).await?;
+        }
+        result.map(|_| messages)
+    }
+
+    /// Forget reminders that can no longer be sent, returning whether any were
+    ///
+    /// Reminders of the scanned tasks are kept only while the task is still
+    /// due the same way, so completed tasks and moved deadlines drop theirs.
+    /// Reminders of other tasks are kept for [`STATE_RETENTION_MS`] after
+    /// their date.
+    fn prune(&mut self, tasks: &[Task], now: f64) -> bool {
+        let scanned: HashSet<&str> = tasks.iter().map(|task| task.id.as_str()).collect();
+        let current: HashSet<String> = self
+            .candidates(tasks, now)
+            .map(|due| due.reminder_key())
+            .collect();
+
+        let before = self.state.sent.len();
+        self.state.sent.retain(|key, _| {
+            let mut parts = key.rsplitn(3, ':');
+            let date = parts.next().and_then(|date| date.parse::<f64>().ok());
+            let task_id = parts.nth(1).unwrap_or_default();
+            if scanned.contains(task_id) {
+                current.contains(key)
+            } else {
+                date.is_some_and(|date| now - date < STATE_RETENTION_MS)
+            }
+        });
+        self.state.sent.len() != before
+    }
+
+    /// Open tasks due at `now`, whether or not they were reminded about
+    fn candidates<'a>(&'a self, tasks: &'a [Task], now: f64) -> impl Iterator<Item = DueTask> + 'a {
+        tasks
+            .iter()
+            .filter(|task| {
+                task.completed != Some(true)
+                    && task.archived != Some(true)
+                    && task.deleted != Some(true)
+            })
+            .filter_map(move |task| self.due_task(task, now))
+    }
+
+    fn due_task(&self, task: &Task, now: f64) -> Option<DueTask> {
+        let deadline = task.deadline.as_ref()?;
+        let with_time = deadline.with_time == Some(true);
+        let due_at = if with_time {
+            deadline.deadline
+        } else {
+            self.day_start(deadline.deadline) + DAY_MS
+        };
+        let starts_at = deadline.start_date.map(|start| {
+            if with_time {
+                start
+            } else {
+                self.day_start(start)
+            }
+        });
+        let kind = if due_at <= now {
+            DueKind::Overdue
+        } else if due_at <= now + self.window {
+            DueKind::DueSoon
+        } else if starts_at.is_some_and(|start| now < start && start <= now + self.window) {
+            DueKind::StartsSoon
+        } else {
+            return None;
+        };
+
+        Some(DueTask {
+            task_id: task.id.clone(),
+            title: task.title.clone(),
+            code: task.id_task_project.clone(),
+            deadline: deadline.deadline,
+            due_at,
+            with_time,
+            start_date: deadline.start_date,
+            starts_at,
+            kind,
+            assigned: task.assigned.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Beginning of the local day containing `timestamp`, as a UTC timestamp
+    fn day_start(&self, timestamp: f64) -> f64 {
+        let local = timestamp + self.utc_offset;
+        (local / DAY_MS).floor() * DAY_MS - self.utc_offset
+    }
+
+    fn needs_reminder(&self, due: &DueTask, now: f64) -> bool {
+        match self.state.sent.get(&due.reminder_key()) {
+            None => true,
+            Some(sent) => {
+                due.kind == DueKind::Overdue
+                    && self
+                        .repeat_overdue
+                        .is_some_and(|interval| now - sent >= interval)
+            }
+        }
+    }
+
+    async fn names(&self, user_ids: &[String]) -> Result<Vec<String>, SDKError> {
+        let mut names = Vec::with_capacity(user_ids.len());
+        for user_id in user_ids {
+            let name = match &self.directory {
+                Some(directory) => directory.user_name(user_id).await?,
+                None => None,
+            };
+            names.push(name.unwrap_or_else(|| user_id.clone()));
+        }
+        Ok(names)
+    }
+}
+
+/// Format a timestamp as a UTC date and time in `YYYY-MM-DD HH:MM` form
+fn format_time(timestamp: f64) -> String {
+    let of_day = timestamp.rem_euclid(DAY_MS);
+    format!(
+        "{} {:02}:{:02}",
+        format_date(timestamp),
+        (of_day / HOUR_MS).floor() as u32,
+        (of_day % HOUR_MS / MINUTE_MS).floor() as u32
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn reminders() -> DeadlineReminders {
+        let client = Arc::new(YouGileClient::new(
+            yougile_api_client::apis::configuration::Configuration::new("token".to_string())
+                .with_base_path("http://127.0.0.1:9".to_string()),
+        ));
+        DeadlineReminders::load(client, "/nonexistent/reminders-state.json")
+            .await
+            .unwrap()
+    }
+
+    fn task(id: &str, start: Option<f64>, deadline: f64, with_time: bool) -> Task {
+        Task {
+            id: id.to_string(),
+            title: id.to_string(),
+            deadline: Some(Deadline {
+                deadline,
+                start_date: start,
+                with_time: Some(with_time),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn start_dates_in_the_window_are_reminded() {
+        let reminders = reminders().await;
+        let now = 10.0 * DAY_MS + 12.0 * HOUR_MS;
+        let tasks = [
+            task("starts", Some(now + HOUR_MS), now + 5.0 * DAY_MS, true),
+            task("started", Some(now - HOUR_MS), now + 5.0 * DAY_MS, true),
+            task("later", Some(now + 2.0 * DAY_MS), now + 5.0 * DAY_MS, true),
+            // Due soon wins over starting soon, even before the start date
+            task("due", Some(now + HOUR_MS), now + 2.0 * HOUR_MS, true),
+            // A start date without time starts at the beginning of its day
+            task(
+                "tomorrow",
+                Some(11.0 * DAY_MS + 20.0 * HOUR_MS),
+                now + 5.0 * DAY_MS,
+                false,
+            ),
+        ];
+
+        let due = reminders.scan(&tasks, now);
+        let kinds: Vec<_> = due
+            .iter()
+            .map(|due| (due.task_id.as_str(), due.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("starts", DueKind::StartsSoon),
+                ("due", DueKind::DueSoon),
+                ("tomorrow", DueKind::StartsSoon),
+            ]
+        );
+        assert_eq!(due[2].starts_at, Some(11.0 * DAY_MS));
+        assert_eq!(due[0].line(0.0), "starts: starts 1970-01-11 13:00");
+    }
+
+    #[tokio::test]
+    async fn dates_without_time_are_due_at_the_end_of_the_local_day() {
+        let reminders = reminders().await.utc_offset(180);
+        let deadline = 10.0 * DAY_MS + 12.0 * HOUR_MS;
+        let tasks = [task("a", None, deadline, false)];
+
+        let due = reminders.scan(&tasks, 10.0 * DAY_MS);
+        assert_eq!(due[0].due_at, 11.0 * DAY_MS - 3.0 * HOUR_MS);
+        assert_eq!(due[0].kind, DueKind::DueSoon);
+        assert_eq!(
+            reminders.scan(&tasks, 11.0 * DAY_MS)[0].kind,
+            DueKind::Overdue
+        );
+    }
+
+    #[tokio::test]
+    async fn prune_forgets_reminders_that_cannot_fire() {
+        let mut reminders = reminders().await;
+        let now = 200.0 * DAY_MS;
+        let tasks = [
+            task("overdue", None, now - DAY_MS, true),
+            task("moved", None, now + 10.0 * DAY_MS, true),
+        ];
+        for key in [
+            format!("overdue:overdue:{}", now - DAY_MS),
+            format!("overdue:due:{}", now - DAY_MS),
+            format!("moved:due:{}", now + HOUR_MS),
+            format!("other:overdue:{}", now - 10.0 * DAY_MS),
+            format!("old:overdue:{}", now - 100.0 * DAY_MS),
+        ] {
+            reminders.state.sent.insert(key, 0.0);
+        }
+
+        assert!(reminders.prune(&tasks, now));
+        let mut kept: Vec<_> = reminders.state.sent.keys().cloned().collect();
+        kept.sort();
+        assert_eq!(
+            kept,
+            [
+                format!("other:overdue:{}", now - 10.0 * DAY_MS),
+                format!("overdue:overdue:{}", now - DAY_MS),
+            ]
+        );
+        assert!(!reminders.prune(&tasks, now));
+    }
+}
@@ -0,0 +1,622 @@
+use super::csv_line;
+use crate::SDKError;
+use crate::time::{DAY_MS, format_date, week_start};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use yougile_api_client::models::*;
+
+/// Width of the bars in ASCII charts, in characters
+const CHART_WIDTH: usize = 50;
+
+/// Symbols used for the columns of a cumulative flow chart
+const FLOW_SYMBOLS: &[char] = &['#', '=', '+', '*', 'o', '%', '@', '~', ':', '.'];
+
+/// State of a task at the time a snapshot was taken
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotTask {
+    pub id: String,
+    pub column_id: Option<String>,
+    /// Creation time
+    pub timestamp: f64,
+    pub completed: bool,
+    pub completed_timestamp: Option<f64>,
+    pub archived: bool,
+    pub plan_hours: Option<f64>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub stickers: TaskStickers,
+}
+
+/// Tasks as they were at one moment
+///
+/// YouGile keeps no history of task movements, so analytics are computed
+/// from snapshots taken periodically (e.g. daily) and stored as JSON.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskSnapshot {
+    pub taken_at: f64,
+    pub tasks: Vec<SnapshotTask>,
+}
+
+impl TaskSnapshot {
+    pub fn new(tasks: &[Task], taken_at: f64) -> Self {
+        Self {
+            taken_at,
+            tasks: tasks
+                .iter()
+                .filter(|task| task.deleted != Some(true))
+                .map(|task| SnapshotTask {
+                    id: task.id.clone(),
+                    column_id: task.column_id.clone(),
+                    timestamp: task.timestamp,
+                    completed: task.completed == Some(true),
+                    completed_timestamp: task.completed_timestamp,
+                    archived: task.archived == Some(true),
+                    plan_hours: task.time_tracking.as_ref().map(|time| time.plan),
+                    stickers: task.stickers.clone().unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, SDKError> {
+        serde_json::to_string(self).map_err(|error| SDKError::Other(error.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SDKError> {
+        serde_json::from_str(json).map_err(|error| SDKError::validation(error.to_string()))
+    }
+}
+
+/// Remaining work of a sprint at one snapshot
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BurndownPoint {
+    pub at: f64,
+    pub remaining_tasks: usize,
+    pub remaining_hours: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Burndown {
+    pub points: Vec<BurndownPoint>,
+}
+
+/// Number of tasks per column at one snapshot
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FlowPoint {
+    pub at: f64,
+    pub columns: BTreeMap<String, usize>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct CumulativeFlow {
+    /// Column IDs in the order they should be stacked
+    pub columns: Vec<String>,
+    pub points: Vec<FlowPoint>,
+}
+
+/// Distribution of durations in days
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Distribution {
+    /// Sorted ascending
+    pub days: Vec<f64>,
+}
+
+/// Completed tasks per week
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Throughput {
+    /// Start of the week (Monday, UTC) and number of completed tasks
+    pub weeks: Vec<(f64, usize)>,
+}
+
+impl Burndown {
+    /// Remaining tasks and planned hours of a sprint sticker state over time
+    pub fn new(snapshots: &[TaskSnapshot], sticker_id: &str, state_id: &str) -> Self {
+        let points = sorted(snapshots)
+            .into_iter()
+            .map(|snapshot| {
+                let remaining: Vec<_> = snapshot
+                    .tasks
+                    .iter()
+                    .filter(|task| !task.completed && !task.archived)
+                    .filter(|task| {
+                        matches!(
+                            task.stickers.get(sticker_id),
+                            Some(StickerValue::StateId(id)) if id == state_id
+                        )
+                    })
+                    .collect();
+                BurndownPoint {
+                    at: snapshot.taken_at,
+                    remaining_tasks: remaining.len(),
+                    remaining_hours: remaining.iter().filter_map(|task| task.plan_hours).sum(),
+                }
+            })
+            .collect();
+
+        Self { points }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = csv_line(&["date", "remaining_tasks", "remaining_hours"]);
+        for point in &self.points {
+            csv.push_str(&csv_line(&[
+                format_date(point.at),
+                point.remaining_tasks.to_string(),
+                format!("{:.2}", point.remaining_hours),
+            ]));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String, SDKError> {
+        to_json(self)
+    }
+
+    /// Remaining tasks per snapshot as horizontal bars
+    pub fn chart(&self) -> String {
+        bar_chart(
+            self.points
+                .iter()
+                .map(|point| (format_date(point.at), point.remaining_tasks as f64)),
+        )
+    }
+}
+
+impl CumulativeFlow {
+    /// Tasks per column over time; columns are stacked in the given order,
+    /// columns not listed follow in order of first appearance
+    pub fn new(snapshots: &[TaskSnapshot], column_order: &[String]) -> Self {
+        let mut columns: Vec<String> = column_order.to_vec();
+        let mut points = Vec::new();
+
+        for snapshot in sorted(snapshots) {
+            let mut counts = BTreeMap::new();
+            for task in snapshot.tasks.iter().filter(|task| !task.archived) {
+                let Some(column_id) = &task.column_id else {
+                    continue;
+                };
+                *counts.entry(column_id.clone()).or_insert(0) += 1;
+                if !columns.contains(column_id) {
+                    columns.push(column_id.clone());
+                }
+            }
+            points.push(FlowPoint {
+                at: snapshot.taken_at,
+                columns: counts,
+            });
+        }
+
+        Self { columns, points }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut header = vec!["date".to_string()];
+        header.extend(self.columns.iter().cloned());
+        let mut csv = csv_line(&header);
+
+        for point in &self.points {
+            let mut row = vec![format_date(point.at)];
+            row.extend(
+                self.columns
+                    .iter()
+                    .map(|column| point.columns.get(column).copied().unwrap_or(0).to_string()),
+            );
+            csv.push_str(&csv_line(&row));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String, SDKError> {
+        to_json(self)
+    }
+
+    /// One stacked bar per snapshot with a symbol per column, followed by a legend
+    ///
+    /// `labels` maps column IDs to display names; missing columns show their ID.
+    pub fn chart(&self, labels: &HashMap<String, String>) -> String {
+        let max = self
+            .points
+            .iter()
+            .map(|point| point.columns.values().sum::<usize>())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let mut chart = String::new();
+        for point in &self.points {
+            let mut bar = String::new();
+            for (index, column) in self.columns.iter().enumerate() {
+                let count = point.columns.get(column).copied().unwrap_or(0);
+                let width = count * CHART_WIDTH / max;
+                bar.extend(std::iter::repeat_n(symbol(index), width));
+            }
+            chart.push_str(&format!("{} |{}\n", format_date(point.at), bar));
+        }
+
+        for (index, column) in self.columns.iter().enumerate() {
+            let label = labels.get(column).unwrap_or(column);
+            chart.push_str(&format!("  {} {}\n", symbol(index), label));
+        }
+        chart
+    }
+}
+
+impl Distribution {
+    /// Days from creation to completion of every completed task
+    pub fn lead_time(tasks: &[Task]) -> Self {
+        Self::from_days(tasks.iter().filter_map(|task| {
+            let completed_at = task.completed_timestamp?;
+            (task.completed == Some(true)).then(|| (completed_at - task.timestamp) / DAY_MS)
+        }))
+    }
+
+    /// Days from the first snapshot showing a task in one of the `in_progress`
+    /// columns to its completion
+    ///
+    /// A task reopened in a later snapshot is not counted until it is
+    /// completed again, and then by its last completion.
+    pub fn cycle_time(snapshots: &[TaskSnapshot], in_progress: &[String]) -> Self {
+        let mut started: HashMap<&str, f64> = HashMap::new();
+        let mut completed: HashMap<&str, f64> = HashMap::new();
+
+        for snapshot in sorted(snapshots) {
+            for task in &snapshot.tasks {
+                let in_column = task
+                    .column_id
+                    .as_ref()
+                    .is_some_and(|column| in_progress.contains(column));
+                if in_column {
+                    started.entry(&task.id).or_insert(snapshot.taken_at);
+                }
+                match task.completed_timestamp {
+                    Some(completed_at) if task.completed => {
+                        completed.insert(&task.id, completed_at);
+                    }
+                    _ => {
+                        completed.remove(task.id.as_str());
+                    }
+                }
+            }
+        }
+
+        Self::from_days(completed.iter().filter_map(|(id, completed_at)| {
+            started
+                .get(id)
+                .map(|started_at| (completed_at - started_at).max(0.0) / DAY_MS)
+        }))
+    }
+
+    fn from_days(days: impl Iterator<Item = f64>) -> Self {
+        let mut days: Vec<f64> = days.collect();
+        days.sort_by(f64::total_cmp);
+        Self { days }
+    }
+
+    pub fn count(&self) -> usize {
+        self.days.len()
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (!self.days.is_empty()).then(|| self.days.iter().sum::<f64>() / self.days.len() as f64)
+    }
+
+    /// Value below which `percent` of the durations fall (nearest rank)
+    pub fn percentile(&self, percent: f64) -> Option<f64> {
+        if self.days.is_empty() {
+            return None;
+        }
+        let rank = (percent.clamp(0.0, 100.0) / 100.0 * self.days.len() as f64).ceil() as usize;
+        Some(self.days[rank.saturating_sub(1)])
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    /// Number of durations per whole day
+    pub fn histogram(&self) -> BTreeMap<u64, usize> {
+        let mut buckets = BTreeMap::new();
+        for days in &self.days {
+            *buckets.entry(days.floor() as u64).or_insert(0) += 1;
+        }
+        buckets
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = csv_line(&["days", "tasks"]);
+        for (days, count) in self.histogram() {
+            csv.push_str(&csv_line(&[days.to_string(), count.to_string()]));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String, SDKError> {
+        to_json(self)
+    }
+
+    /// Histogram with one bar per day
+    pub fn chart(&self) -> String {
+        bar_chart(
+            self.histogram()
+                .into_iter()
+                .map(|(days, count)| (format!("{}d", days), count as f64)),
+        )
+    }
+}
+
+impl Throughput {
+    /// Completed tasks per week, including weeks without completions
+    pub fn new(tasks: &[Task]) -> Self {
+        let mut weeks: BTreeMap<i64, usize> = BTreeMap::new();
+        for task in tasks.iter().filter(|task| task.completed == Some(true)) {
+            if let Some(completed_at) = task.completed_timestamp {
+                *weeks.entry(week_start(completed_at) as i64).or_insert(0) += 1;
+            }
+        }
+
+        let (Some(first), Some(last)) = (weeks.keys().next(), weeks.keys().next_back()) else {
+            return Self::default();
+        };
+        let week_ms = (7.0 * DAY_MS) as i64;
+        let weeks = (*first..=*last)
+            .step_by(week_ms as usize)
+            .map(|week| (week as f64, weeks.get(&week).copied().unwrap_or(0)))
+            .collect();
+
+        Self { weeks }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = csv_line(&["week", "completed"]);
+        for (week, count) in &self.weeks {
+            csv.push_str(&csv_line(&[format_date(*week), count.to_string()]));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String, SDKError> {
+        to_json(self)
+    }
+
+    pub fn chart(&self) -> String {
+        bar_chart(
+            self.weeks
+                .iter()
+                .map(|(week, count)| (format_date(*week), *count as f64)),
+        )
+    }
+}
+
+fn sorted(snapshots: &[TaskSnapshot]) -> Vec<&TaskSnapshot> {
+    let mut sorted: Vec<_> = snapshots.iter().collect();
+    sorted.sort_by(|a, b| a.taken_at.total_cmp(&b.taken_at));
+    sorted
+}
+
+fn symbol(index: usize) -> char {
+    FLOW_SYMBOLS[index % FLOW_SYMBOLS.len()]
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, SDKError> {
+    serde_json::to_string_pretty(value).map_err(|error| SDKError::Other(error.to_string()))
+}
+
+/// Horizontal bars scaled to the largest value, one line per entry
+fn bar_chart(entries: impl Iterator<Item = (String, f64)>) -> String {
+    let entries: Vec<_> = entries.collect();
+    let label_width = entries
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let max = entries.iter().map(|(_, value)| *value).fold(0.0, f64::max);
+
+    let mut chart = String::new();
+    for (label, value) in entries {
+        let width = if max > 0.0 {
+            (value / max * CHART_WIDTH as f64).round() as usize
+        } else {
+            0
+        };
+        chart.push_str(&format!(
+            "{:>label_width$} |{} {}\n",
+            label,
+            "#".repeat(width),
+            value
+        ));
+    }
+    chart
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Monday, 1970-01-05
+    const MONDAY: f64 = 4.0 * DAY_MS;
+
+    fn snapshot_task(id: &str, column: &str, completed_day: Option<f64>) -> SnapshotTask {
+        SnapshotTask {
+            id: id.to_string(),
+            column_id: Some(column.to_string()),
+            timestamp: 0.0,
+            completed: completed_day.is_some(),
+            completed_timestamp: completed_day.map(|day| day * DAY_MS),
+            archived: false,
+            plan_hours: Some(2.0),
+            stickers: HashMap::from([(
+                "sprint".to_string(),
+                StickerValue::StateId("s1".to_string()),
+            )]),
+        }
+    }
+
+    fn snapshot(day: f64, tasks: Vec<SnapshotTask>) -> TaskSnapshot {
+        TaskSnapshot {
+            taken_at: day * DAY_MS,
+            tasks,
+        }
+    }
+
+    fn completed_task(id: &str, created: f64, completed: Option<f64>) -> Task {
+        Task {
+            id: id.to_string(),
+            timestamp: created,
+            completed: Some(completed.is_some()),
+            completed_timestamp: completed,
+            ..Default::default()
+        }
+    }
+
+    /// Three daily snapshots; "b" is completed on day 2 and reopened on day 3
+    fn snapshots() -> Vec<TaskSnapshot> {
+        vec![
+            snapshot(
+                3.0,
+                vec![
+                    snapshot_task("a", "done", Some(2.0)),
+                    snapshot_task("b", "doing", None),
+                    snapshot_task("c", "doing", None),
+                ],
+            ),
+            snapshot(
+                1.0,
+                vec![
+                    snapshot_task("a", "doing", None),
+                    snapshot_task("b", "todo", None),
+                    snapshot_task("c", "todo", None),
+                ],
+            ),
+            snapshot(
+                2.0,
+                vec![
+                    snapshot_task("a", "done", Some(2.0)),
+                    snapshot_task("b", "done", Some(2.0)),
+                    snapshot_task("c", "doing", None),
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn burndown_counts_reopened_tasks_again() {
+        let burndown = Burndown::new(&snapshots(), "sprint", "s1");
+
+        let remaining: Vec<_> = burndown
+            .points
+            .iter()
+            .map(|point| {
+                (
+                    point.at / DAY_MS,
+                    point.remaining_tasks,
+                    point.remaining_hours,
+                )
+            })
+            .collect();
+        assert_eq!(remaining, [(1.0, 3, 6.0), (2.0, 1, 2.0), (3.0, 2, 4.0)]);
+        assert!(
+            Burndown::new(&snapshots(), "sprint", "s2")
+                .points
+                .iter()
+                .all(|point| point.remaining_tasks == 0)
+        );
+        assert!(Burndown::new(&[], "sprint", "s1").points.is_empty());
+    }
+
+    #[test]
+    fn cumulative_flow_stacks_known_columns_first() {
+        let flow = CumulativeFlow::new(&snapshots(), &["todo".to_string(), "doing".to_string()]);
+
+        assert_eq!(flow.columns, ["todo", "doing", "done"]);
+        assert_eq!(
+            flow.points[0].columns,
+            BTreeMap::from([("todo".to_string(), 2), ("doing".to_string(), 1)])
+        );
+        assert_eq!(flow.points[2].columns["doing"], 2);
+        assert_eq!(
+            flow.to_csv(),
+            "date,todo,doing,done\n1970-01-02,2,1,0\n1970-01-03,0,1,2\n1970-01-04,0,2,1\n"
+        );
+
+        let empty = CumulativeFlow::new(&[], &[]);
+        assert!(empty.columns.is_empty() && empty.points.is_empty());
+        assert_eq!(empty.chart(&HashMap::new()), "");
+    }
+
+    #[test]
+    fn cycle_time_skips_tasks_reopened_after_completion() {
+        let in_progress = ["doing".to_string()];
+        let cycle = Distribution::cycle_time(&snapshots(), &in_progress);
+
+        // "a" started on day 1 and finished on day 2; "b" was reopened
+        assert_eq!(cycle.days, [1.0]);
+
+        let mut later = snapshots();
+        later.push(snapshot(5.0, vec![snapshot_task("b", "done", Some(4.5))]));
+        let cycle = Distribution::cycle_time(&later, &in_progress);
+        assert_eq!(cycle.days, [1.0, 1.5]);
+
+        assert_eq!(Distribution::cycle_time(&[], &in_progress).count(), 0);
+    }
+
+    #[test]
+    fn lead_time_statistics() {
+        let lead = Distribution::lead_time(&[
+            completed_task("a", 0.0, Some(4.0 * DAY_MS)),
+            completed_task("b", DAY_MS, Some(2.0 * DAY_MS)),
+            completed_task("c", 0.0, Some(2.5 * DAY_MS)),
+            completed_task("open", 0.0, None),
+            // Reopened tasks keep their completion time but are not completed
+            Task {
+                completed: Some(false),
+                ..completed_task("reopened", 0.0, Some(DAY_MS))
+            },
+        ]);
+
+        assert_eq!(lead.days, [1.0, 2.5, 4.0]);
+        assert_eq!(lead.mean(), Some(2.5));
+        assert_eq!(lead.median(), Some(2.5));
+        assert_eq!(lead.percentile(100.0), Some(4.0));
+        assert_eq!(lead.percentile(0.0), Some(1.0));
+        assert_eq!(lead.histogram(), BTreeMap::from([(1, 1), (2, 1), (4, 1)]));
+
+        let empty = Distribution::lead_time(&[]);
+        assert_eq!(empty.mean(), None);
+        assert_eq!(empty.median(), None);
+        assert_eq!(empty.to_csv(), "days,tasks\n");
+    }
+
+    #[test]
+    fn throughput_fills_weeks_without_completions() {
+        let throughput = Throughput::new(&[
+            completed_task("a", 0.0, Some(MONDAY + DAY_MS)),
+            completed_task("b", 0.0, Some(MONDAY + 6.5 * DAY_MS)),
+            completed_task("c", 0.0, Some(MONDAY + 15.0 * DAY_MS)),
+            completed_task("open", 0.0, None),
+            Task {
+                completed: Some(false),
+                ..completed_task("reopened", 0.0, Some(MONDAY))
+            },
+        ]);
+
+        assert_eq!(
+            throughput.weeks,
+            [
+                (MONDAY, 2),
+                (MONDAY + 7.0 * DAY_MS, 0),
+                (MONDAY + 14.0 * DAY_MS, 1)
+            ]
+        );
+        assert!(Throughput::new(&[]).weeks.is_empty());
+        assert_eq!(Throughput::new(&[]).chart(), "");
+    }
+
+    #[test]
+    fn snapshots_round_trip_through_json() {
+        let snapshots = snapshots();
+        let json = snapshots[0].to_json().unwrap();
+
+        assert_eq!(TaskSnapshot::from_json(&json).unwrap(), snapshots[0]);
+        assert!(TaskSnapshot::from_json("{}").is_err());
+    }
+}
@@ -0,0 +1,383 @@
+use super::csv_line;
+use crate::permissions::{Capability, EffectivePermissions, SystemRolePresets};
+use crate::{MemberRole, SDKError};
+use serde::{Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use yougile_api_client::models::*;
+
+/// Role names treated as observers when looking for observers with write rights
+const OBSERVER_NAMES: [&str; 2] = ["observer", "наблюдатель"];
+
+/// Something in an audit entry that deserves a closer look
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AuditFlag {
+    /// The user is a company administrator
+    CompanyAdmin,
+    /// An observer role, by system role or by name, that grants write rights or
+    /// belongs to a company administrator
+    ObserverWithWriteRights,
+    /// The user is mapped to a custom role that no longer exists
+    DeletedRole,
+    /// The role grants different rights on some boards, see
+    /// [`AuditEntry::board_capabilities`]
+    BoardOverrides,
+}
+
+impl AuditFlag {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditFlag::CompanyAdmin => "company-admin",
+            AuditFlag::ObserverWithWriteRights => "observer-with-write-rights",
+            AuditFlag::DeletedRole => "deleted-role",
+            AuditFlag::BoardOverrides => "board-overrides",
+        }
+    }
+}
+
+impl fmt::Display for AuditFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for AuditFlag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Rights of one user in one project
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AuditEntry {
+    pub user_id: String,
+    /// Real name, or the email if the user has none; the ID for unknown users
+    pub user_name: String,
+    pub project_id: String,
+    pub project_title: String,
+    /// System role or custom role ID as stored in the project
+    pub role: String,
+    /// Name of the custom role, if it exists
+    pub role_name: Option<String>,
+    pub company_admin: bool,
+    /// The capabilities come from the [`SystemRolePresets`] of a system role
+    /// rather than from the role itself
+    pub approximated: bool,
+    /// Capabilities on boards without an override
+    pub capabilities: Vec<Capability>,
+    /// Capabilities on the boards whose override grants different rights,
+    /// keyed by board ID
+    pub board_capabilities: BTreeMap<String, Vec<Capability>>,
+    pub flags: Vec<AuditFlag>,
+}
+
+impl AuditEntry {
+    /// Whether the user has `capability` on boards without an override
+    pub fn has(&self, capability: Capability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+
+    /// Whether the user has `capability` on the given board
+    pub fn has_on_board(&self, board_id: &str, capability: Capability) -> bool {
+        self.board_capabilities
+            .get(board_id)
+            .unwrap_or(&self.capabilities)
+            .contains(&capability)
+    }
+
+    /// Whether the user has `capability` on at least one board
+    pub fn has_anywhere(&self, capability: Capability) -> bool {
+        self.has(capability)
+            || self
+                .board_capabilities
+                .values()
+                .any(|capabilities| capabilities.contains(&capability))
+    }
+}
+
+/// Who can do what in which project, expanded from project members and roles
+///
+/// The rights of system roles are approximated by the given
+/// [`SystemRolePresets`] and such entries are marked as approximated. Board
+/// overrides of custom roles are expanded per board. Task capabilities refer to
+/// the rights over all tasks.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct PermissionAudit {
+    pub entries: Vec<AuditEntry>,
+}
+
+impl PermissionAudit {
+    /// Build the audit from projects, their custom roles keyed by project ID, and users
+    ///
+    /// Deleted projects are skipped. Entries are sorted by project and user name.
+    pub fn new(
+        projects: &[Project],
+        roles: &HashMap<String, Vec<ProjectRole>>,
+        users: &[User],
+        system_roles: &SystemRolePresets,
+    ) -> Self {
+        let users: HashMap<&str, &User> =
+            users.iter().map(|user| (user.id.as_str(), user)).collect();
+        let mut entries = Vec::new();
+
+        for project in projects
+            .iter()
+            .filter(|project| project.deleted != Some(true))
+        {
+            let project_roles = roles
+                .get(&project.id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            for (user_id, role) in project.users.iter().flatten() {
+                let user = users.get(user_id.as_str());
+                let member_role = MemberRole::from(role.as_str());
+                let role_name = match &member_role {
+                    MemberRole::Custom(id) => project_roles
+                        .iter()
+                        .find(|project_role| project_role.id == *id)
+                        .map(|project_role| project_role.name.clone()),
+                    _ => None,
+                };
+                let company_admin = user.is_some_and(|user| user.is_admin == Some(true));
+
+                let mut flags = Vec::new();
+                if company_admin {
+                    flags.push(AuditFlag::CompanyAdmin);
+                }
+                let mut capabilities = Vec::new();
+                let mut board_capabilities = BTreeMap::new();
+                match EffectivePermissions::of_member(
+                    &member_role,
+                    project_roles,
+                    Some(system_roles),
+                ) {
+                    Some(permissions) => {
+                        capabilities = permissions.capabilities();
+                        for board_id in permissions.project().children.keys() {
+                            let on_board = permissions.for_board(board_id).capabilities();
+                            if on_board != capabilities {
+                                board_capabilities.insert(board_id.clone(), on_board);
+                            }
+                        }
+                    }
+                    None => flags.push(AuditFlag::DeletedRole),
+                }
+                if !board_capabilities.is_empty() {
+                    flags.push(AuditFlag::BoardOverrides);
+                }
+                let observer = member_role == MemberRole::Observer
+                    || role_name.as_deref().is_some_and(|name| {
+                        OBSERVER_NAMES.contains(&name.trim().to_lowercase().as_str())
+                    });
+                let write = capabilities
+                    .iter()
+                    .chain(board_capabilities.values().flatten())
+                    .any(Capability::is_write);
+                if observer && (company_admin || write) {
+                    flags.push(AuditFlag::ObserverWithWriteRights);
+                }
+
+                entries.push(AuditEntry {
+                    user_id: user_id.clone(),
+                    user_name: user
+                        .map(|user| {
+                            if user.real_name.is_empty() {
+                                user.email.clone()
+                            } else {
+                                user.real_name.clone()
+                            }
+                        })
+                        .unwrap_or_else(|| user_id.clone()),
+                    project_id: project.id.clone(),
+                    project_title: project.title.clone(),
+                    role: member_role.to_string(),
+                    role_name,
+                    company_admin,
+                    approximated: !matches!(member_role, MemberRole::Custom(_)),
+                    capabilities,
+                    board_capabilities,
+                    flags,
+                });
+            }
+        }
+
+        entries.sort_by(|a, b| {
+            (&a.project_title, &a.project_id, &a.user_name, &a.user_id).cmp(&(
+                &b.project_title,
+                &b.project_id,
+                &b.user_name,
+                &b.user_id,
+            ))
+        });
+        Self { entries }
+    }
+
+    /// Entries whose user has `capability` on at least one board, e.g. who can
+    /// delete boards
+    pub fn who_can(&self, capability: Capability) -> Vec<&AuditEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.has_anywhere(capability))
+            .collect()
+    }
+
+    /// Entries with at least one flag
+    pub fn flagged(&self) -> Vec<&AuditEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.flags.is_empty())
+            .collect()
+    }
+
+    /// One row per user and project with a `yes`/`no` column per capability
+    ///
+    /// Boards with an override get a row of their own with `board_id` set; the
+    /// row without it holds the rights on every other board.
+    pub fn to_csv(&self) -> String {
+        let mut header = vec![
+            "user_id",
+            "user",
+            "project_id",
+            "project",
+            "board_id",
+            "role",
+            "role_name",
+            "approximated",
+            "flags",
+        ];
+        header.extend(Capability::ALL.iter().map(Capability::as_str));
+        let mut csv = csv_line(&header);
+
+        for entry in &self.entries {
+            let boards = std::iter::once(("", &entry.capabilities)).chain(
+                entry
+                    .board_capabilities
+                    .iter()
+                    .map(|(board_id, capabilities)| (board_id.as_str(), capabilities)),
+            );
+            for (board_id, capabilities) in boards {
+                let mut row = vec![
+                    entry.user_id.clone(),
+                    entry.user_name.clone(),
+                    entry.project_id.clone(),
+                    entry.project_title.clone(),
+                    board_id.to_string(),
+                    entry.role.clone(),
+                    entry.role_name.clone().unwrap_or_default(),
+                    if entry.approximated { "yes" } else { "no" }.to_string(),
+                    entry
+                        .flags
+                        .iter()
+                        .map(AuditFlag::as_str)
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ];
+                row.extend(Capability::ALL.iter().map(|capability| {
+                    if capabilities.contains(capability) {
+                        "yes"
+                    } else {
+                        "no"
+                    }
+                    .to_string()
+                }));
+                csv.push_str(&csv_line(&row));
+            }
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String, SDKError> {
+        serde_json::to_string_pretty(self).map_err(|error| SDKError::Other(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permissions::Preset;
+    use yougile_api_client::models::projects::ProjectPermissionChildValue;
+
+    fn audit() -> PermissionAudit {
+        let mut permissions = Preset::ReadOnly.permissions();
+        permissions.children.insert(
+            "board-1".to_string(),
+            ProjectPermissionChildValue::RawValue(
+                serde_json::to_value(Preset::Full.permissions().boards).unwrap(),
+            ),
+        );
+        let role = ProjectRole {
+            id: "role-1".to_string(),
+            name: "Observer".to_string(),
+            description: None,
+            permissions,
+        };
+        let mut project = Project::new("project-1".to_string(), "Project".to_string(), 0.0);
+        project.users = Some(HashMap::from([
+            ("user-1".to_string(), "role-1".to_string()),
+            ("user-2".to_string(), "worker".to_string()),
+        ]));
+        let users = [
+            User::new(
+                "user-1".to_string(),
+                "ann@example.com".to_string(),
+                "Ann".to_string(),
+                String::new(),
+                0.0,
+            ),
+            User::new(
+                "user-2".to_string(),
+                "bob@example.com".to_string(),
+                "Bob".to_string(),
+                String::new(),
+                0.0,
+            ),
+        ];
+        let presets = SystemRolePresets::new(Preset::Full, Preset::Contributor, Preset::ReadOnly);
+        PermissionAudit::new(
+            &[project],
+            &HashMap::from([("project-1".to_string(), vec![role])]),
+            &users,
+            &presets,
+        )
+    }
+
+    #[test]
+    fn board_overrides_are_expanded() {
+        let audit = audit();
+        let ann = &audit.entries[0];
+
+        assert!(!ann.approximated);
+        assert!(!ann.has(Capability::DeleteBoard));
+        assert!(ann.has_on_board("board-1", Capability::DeleteBoard));
+        assert!(!ann.has_on_board("board-2", Capability::DeleteBoard));
+        assert_eq!(
+            ann.flags,
+            [
+                AuditFlag::BoardOverrides,
+                AuditFlag::ObserverWithWriteRights
+            ]
+        );
+        assert_eq!(audit.who_can(Capability::DeleteBoard), [ann]);
+    }
+
+    #[test]
+    fn system_roles_are_marked_as_approximated() {
+        let audit = audit();
+        let bob = &audit.entries[1];
+
+        assert!(bob.approximated);
+        assert!(bob.board_capabilities.is_empty());
+        assert_eq!(
+            bob.capabilities,
+            EffectivePermissions::new(Preset::Contributor.permissions()).capabilities()
+        );
+
+        let csv = audit.to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 4);
+        assert!(rows[1].starts_with("user-1,Ann,project-1,Project,,role-1,Observer,no,"));
+        assert!(rows[2].starts_with("user-1,Ann,project-1,Project,board-1,"));
+        assert!(rows[3].starts_with("user-2,Bob,project-1,Project,,worker,,yes,"));
+    }
+}
@@ -0,0 +1,32 @@
+//! Reports over tasks, chats and permissions with CSV and JSON export
+
+mod analytics;
+mod audit;
+mod reactions;
+mod time_tracking;
+
+pub use analytics::{
+    Burndown, BurndownPoint, CumulativeFlow, Distribution, FlowPoint, SnapshotTask, TaskSnapshot,
+    Throughput,
+};
+pub use audit::{AuditEntry, AuditFlag, PermissionAudit};
+pub use reactions::ReactionTally;
+pub use time_tracking::{GroupBy, TaskTime, TimeGroup, TimeReport, TimeReportBuilder};
+
+/// Join fields into a CSV line, quoting fields that need it
+pub(crate) fn csv_line<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push('\n');
+    line
+}
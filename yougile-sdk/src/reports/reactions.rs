@@ -0,0 +1,183 @@
+use super::csv_line;
+use crate::SDKError;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use yougile_api_client::models::*;
+
+/// Reactions counted per user over a period, e.g. for a kudos bot
+///
+/// A reaction is received by the author of the message and given by the user
+/// who reacted. Messages count by the time they were posted.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ReactionTally {
+    pub from: f64,
+    pub to: Option<f64>,
+    /// Messages in the period with at least one reaction
+    pub messages: usize,
+    pub received: BTreeMap<String, BTreeMap<React, usize>>,
+    pub given: BTreeMap<String, BTreeMap<React, usize>>,
+}
+
+impl ReactionTally {
+    /// An empty tally of messages posted in `[from, to)`
+    pub fn new(from: f64, to: Option<f64>) -> Self {
+        Self {
+            from,
+            to,
+            ..Default::default()
+        }
+    }
+
+    /// Count the reactions of a message if it was posted in the period
+    pub fn add_message(&mut self, message: &ChatMessage) {
+        let in_period = message.id >= self.from && self.to.is_none_or(|to| message.id < to);
+        if !in_period || message.deleted == Some(true) || message.reactions.is_empty() {
+            return;
+        }
+
+        self.messages += 1;
+        for (react, users) in message.reactions.iter() {
+            for user_id in users {
+                *self
+                    .received
+                    .entry(message.from_user_id.clone())
+                    .or_default()
+                    .entry(react.clone())
+                    .or_default() += 1;
+                *self
+                    .given
+                    .entry(user_id.clone())
+                    .or_default()
+                    .entry(react.clone())
+                    .or_default() += 1;
+            }
+        }
+    }
+
+    pub fn add_messages(&mut self, messages: &[ChatMessage]) {
+        for message in messages {
+            self.add_message(message);
+        }
+    }
+
+    pub fn received(&self, user_id: &str, react: &React) -> usize {
+        count(&self.received, user_id, react)
+    }
+
+    pub fn given(&self, user_id: &str, react: &React) -> usize {
+        count(&self.given, user_id, react)
+    }
+
+    /// Users by how often they received a reaction, most first
+    pub fn ranking(&self, react: &React) -> Vec<(String, usize)> {
+        let mut ranking: Vec<(String, usize)> = self
+            .received
+            .iter()
+            .filter_map(|(user_id, reacts)| Some((user_id.clone(), *reacts.get(react)?)))
+            .collect();
+        ranking.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranking
+    }
+
+    /// One row per user and reaction with the received and given counts
+    pub fn to_csv(&self) -> String {
+        let mut rows: BTreeSet<(&String, &React)> = BTreeSet::new();
+        for counts in [&self.received, &self.given] {
+            for (user_id, reacts) in counts {
+                rows.extend(reacts.keys().map(|react| (user_id, react)));
+            }
+        }
+
+        let mut csv = csv_line(&["user_id", "reaction", "received", "given"]);
+        for (user_id, react) in rows {
+            csv.push_str(&csv_line(&[
+                user_id.clone(),
+                react.to_string(),
+                self.received(user_id, react).to_string(),
+                self.given(user_id, react).to_string(),
+            ]));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String, SDKError> {
+        serde_json::to_string_pretty(self).map_err(|error| SDKError::Other(error.to_string()))
+    }
+}
+
+fn count(counts: &BTreeMap<String, BTreeMap<React, usize>>, user_id: &str, react: &React) -> usize {
+    counts
+        .get(user_id)
+        .and_then(|reacts| reacts.get(react))
+        .copied()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: f64, author: &str, reactions: &[(React, &[&str])]) -> ChatMessage {
+        let mut message = ChatMessage {
+            id,
+            from_user_id: author.to_string(),
+            ..Default::default()
+        };
+        for (react, users) in reactions {
+            message.reactions.add_reaction(
+                react.clone(),
+                users.iter().map(|user| user.to_string()).collect(),
+            );
+        }
+        message
+    }
+
+    #[test]
+    fn counts_received_and_given_reactions_in_the_period() {
+        let crab = React::Other("🦀".to_string());
+        let mut tally = ReactionTally::new(100.0, Some(200.0));
+        tally.add_messages(&[
+            message(100.0, "anna", &[(React::Rocket, &["ivan", "olga"])]),
+            message(
+                150.0,
+                "ivan",
+                &[(React::Rocket, &["anna"]), (crab.clone(), &["anna"])],
+            ),
+            message(160.0, "olga", &[(React::Rocket, &["anna"])]),
+            message(170.0, "olga", &[]),
+            // Outside the period
+            message(99.0, "anna", &[(React::Rocket, &["ivan"])]),
+            message(200.0, "anna", &[(React::Rocket, &["ivan"])]),
+        ]);
+        let mut deleted = message(180.0, "anna", &[(React::Rocket, &["ivan"])]);
+        deleted.deleted = Some(true);
+        tally.add_message(&deleted);
+
+        assert_eq!(tally.messages, 3);
+        assert_eq!(tally.received("anna", &React::Rocket), 2);
+        assert_eq!(tally.given("anna", &React::Rocket), 2);
+        assert_eq!(tally.given("anna", &crab), 1);
+        assert_eq!(tally.received("nobody", &React::Rocket), 0);
+        assert_eq!(
+            tally.ranking(&React::Rocket),
+            [
+                ("anna".to_string(), 2),
+                ("ivan".to_string(), 1),
+                ("olga".to_string(), 1)
+            ]
+        );
+        assert_eq!(tally.ranking(&crab), [("ivan".to_string(), 1)]);
+    }
+
+    #[test]
+    fn csv_lists_every_user_and_reaction() {
+        let mut tally = ReactionTally::new(0.0, None);
+        tally.add_message(&message(1.0, "anna", &[(React::Rocket, &["ivan"])]));
+
+        assert_eq!(
+            tally.to_csv(),
+            "user_id,reaction,received,given\nanna,🚀,1,0\nivan,🚀,0,1\n"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use super::csv_line;
+use crate::SDKError;
+use crate::directory::Directory;
+use crate::resources::TasksAPI;
+use crate::resources::tasks::TaskSet;
+use crate::time::{HOUR_MS, SECOND_MS, now_ms, stopwatch_seconds};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// How tasks are grouped in a time report
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum GroupBy {
+    /// One group per assignee; tasks with several assignees count for each of them
+    Assignee,
+    Column,
+    Board,
+    /// One group per state of a sprint or custom sticker, by sticker ID
+    Sticker(String),
+}
+
+/// Time tracked on a single task, in hours
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TaskTime {
+    pub task_id: String,
+    pub title: String,
+    pub plan_hours: f64,
+    pub work_hours: f64,
+    /// Stopwatch value at report time, including a running stopwatch
+    pub stopwatch_hours: f64,
+    pub stopwatch_running: bool,
+}
+
+/// Totals for one group of tasks, in hours
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct TimeGroup {
+    /// ID of the user, column, board or sticker state; `None` for tasks without one
+    pub key: Option<String>,
+    pub label: String,
+    pub tasks: usize,
+    pub plan_hours: f64,
+    pub work_hours: f64,
+    pub stopwatch_hours: f64,
+}
+
+impl TimeGroup {
+    /// Planned hours not yet worked, negative if the plan was exceeded
+    pub fn remaining_hours(&self) -> f64 {
+        self.plan_hours - self.work_hours
+    }
+
+    fn add(&mut self, time: &TaskTime) {
+        self.tasks += 1;
+        self.plan_hours += time.plan_hours;
+        self.work_hours += time.work_hours;
+        self.stopwatch_hours += time.stopwatch_hours;
+    }
+}
+
+/// Planned vs. worked hours over a set of tasks
+#[derive(Clone, Debug, Serialize)]
+pub struct TimeReport {
+    pub group_by: GroupBy,
+    /// Timestamp used for running stopwatches
+    pub generated_at: f64,
+    /// Groups sorted by label
+    pub groups: Vec<TimeGroup>,
+    /// Totals over all tasks, each task counted once
+    pub total: TimeGroup,
+    pub tasks: Vec<TaskTime>,
+}
+
+impl TimeReport {
+    /// One line per group followed by a total line
+    pub fn to_csv(&self) -> String {
+        let mut csv = csv_line(&[
+            "group",
+            "key",
+            "tasks",
+            "plan_hours",
+            "work_hours",
+            "remaining_hours",
+            "stopwatch_hours",
+        ]);
+        for group in self.groups.iter().chain([&self.total]) {
+            csv.push_str(&csv_line(&[
+                group.label.clone(),
+                group.key.clone().unwrap_or_default(),
+                group.tasks.to_string(),
+                format_hours(group.plan_hours),
+                format_hours(group.work_hours),
+                format_hours(group.remaining_hours()),
+                format_hours(group.stopwatch_hours),
+            ]));
+        }
+        csv
+    }
+
+    /// One line per task
+    pub fn tasks_to_csv(&self) -> String {
+        let mut csv = csv_line(&[
+            "task_id",
+            "title",
+            "plan_hours",
+            "work_hours",
+            "stopwatch_hours",
+            "stopwatch_running",
+        ]);
+        for task in &self.tasks {
+            csv.push_str(&csv_line(&[
+                task.task_id.clone(),
+                task.title.clone(),
+                format_hours(task.plan_hours),
+                format_hours(task.work_hours),
+                format_hours(task.stopwatch_hours),
+                task.stopwatch_running.to_string(),
+            ]));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> Result<String, SDKError> {
+        serde_json::to_string_pretty(self).map_err(|error| SDKError::Other(error.to_string()))
+    }
+}
+
+/// Builder for a [`TimeReport`]
+pub struct TimeReportBuilder {
+    client: Arc<YouGileClient>,
+    directory: Option<Arc<Directory>>,
+    tasks: TaskSet,
+    group_by: GroupBy,
+    now: Option<f64>,
+}
+
+impl TimeReportBuilder {
+    pub(crate) fn new(
+        client: Arc<YouGileClient>,
+        directory: Option<Arc<Directory>>,
+        tasks: TaskSet,
+    ) -> Self {
+        Self {
+            client,
+            directory,
+            tasks,
+            group_by: GroupBy::Assignee,
+            now: None,
+        }
+    }
+
+    /// How to group tasks, by assignee if not set
+    pub fn group_by(mut self, group_by: GroupBy) -> Self {
+        self.group_by = group_by;
+        self
+    }
+
+    /// Directory used to resolve group labels; a fresh one is loaded if not set
+    pub fn directory(mut self, directory: Arc<Directory>) -> Self {
+        self.directory = Some(directory);
+        self
+    }
+
+    /// Time used for running stopwatches, defaults to the current time
+    pub fn now(mut self, timestamp: f64) -> Self {
+        self.now = Some(timestamp);
+        self
+    }
+
+    pub async fn execute(self) -> Result<TimeReport, SDKError> {
+        let now = self.now.unwrap_or_else(now_ms);
+        let directory = self
+            .directory
+            .unwrap_or_else(|| Arc::new(Directory::new(self.client.clone())));
+        let tasks = TasksAPI::new(self.client).load(self.tasks).await?;
+
+        let board_of_column: HashMap<String, String> = match self.group_by {
+            GroupBy::Board => directory
+                .columns()
+                .await?
+                .iter()
+                .map(|column| (column.id.clone(), column.board_id.clone()))
+                .collect(),
+            _ => HashMap::new(),
+        };
+
+        let mut groups: HashMap<Option<String>, TimeGroup> = HashMap::new();
+        let mut total = TimeGroup {
+            label: "Total".to_string(),
+            ..Default::default()
+        };
+        let mut times = Vec::with_capacity(tasks.len());
+
+        for task in &tasks {
+            let time = task_time(task, now);
+            total.add(&time);
+            for key in group_keys(task, &self.group_by, &board_of_column) {
+                groups
+                    .entry(key.clone())
+                    .or_insert_with(|| TimeGroup {
+                        key,
+                        ..Default::default()
+                    })
+                    .add(&time);
+            }
+            times.push(time);
+        }
+
+        let mut groups: Vec<TimeGroup> = groups.into_values().collect();
+        for group in &mut groups {
+            group.label = label(&directory, &self.group_by, group.key.as_deref()).await?;
+        }
+        groups.sort_by(|a, b| a.label.cmp(&b.label));
+
+        Ok(TimeReport {
+            group_by: self.group_by,
+            generated_at: now,
+            groups,
+            total,
+            tasks: times,
+        })
+    }
+}
+
+fn task_time(task: &Task, now: f64) -> TaskTime {
+    let (plan_hours, work_hours) = task
+        .time_tracking
+        .as_ref()
+        .map_or((0.0, 0.0), |time| (time.plan, time.work));
+
+    TaskTime {
+        task_id: task.id.clone(),
+        title: task.title.clone(),
+        plan_hours,
+        work_hours,
+        stopwatch_hours: task.stopwatch.as_ref().map_or(0.0, |stopwatch| {
+            stopwatch_seconds(stopwatch, now) * SECOND_MS / HOUR_MS
+        }),
+        stopwatch_running: task
+            .stopwatch
+            .as_ref()
+            .is_some_and(|stopwatch| stopwatch.running),
+    }
+}
+
+fn group_keys(
+    task: &Task,
+    group_by: &GroupBy,
+    board_of_column: &HashMap<String, String>,
+) -> Vec<Option<String>> {
+    match group_by {
+        GroupBy::Assignee => match task.assigned.as_deref() {
+            Some(assigned) if !assigned.is_empty() => assigned.iter().cloned().map(Some).collect(),
+            _ => vec![None],
+        },
+        GroupBy::Column => vec![task.column_id.clone()],
+        GroupBy::Board => vec![
+            task.column_id
+                .as_ref()
+                .and_then(|column_id| board_of_column.get(column_id))
+                .cloned(),
+        ],
+        GroupBy::Sticker(sticker_id) => {
+            let state = task
+                .stickers
+                .as_ref()
+                .and_then(|stickers| stickers.get(sticker_id));
+            match state {
+                Some(StickerValue::StateId(state_id)) if state_id != UNSET => {
+                    vec![Some(state_id.clone())]
+                }
+                _ => vec![None],
+            }
+        }
+    }
+}
+
+async fn label(
+    directory: &Directory,
+    group_by: &GroupBy,
+    key: Option<&str>,
+) -> Result<String, SDKError> {
+    let Some(key) = key else {
+        return Ok("(none)".to_string());
+    };
+
+    let name = match group_by {
+        GroupBy::Assignee => directory.user_name(key).await?,
+        GroupBy::Column => directory.column_title(key).await?,
+        GroupBy::Board => directory.board_title(key).await?,
+        GroupBy::Sticker(sticker_id) => directory.sticker_state_name(sticker_id, key).await?,
+    };
+    Ok(name.unwrap_or_else(|| key.to_string()))
+}
+
+fn format_hours(hours: f64) -> String {
+    format!("{:.2}", hours)
+}
@@ -1,4 +1,6 @@
 use crate::SDKError;
+use crate::markup::{html_to_text, markdown_to_html, text_to_html};
+use crate::reports::ReactionTally;
 use std::sync::Arc;
 
 use yougile_api_client::YouGileClient;
@@ -26,6 +28,35 @@ impl ChatsAPI {
             .map_err(SDKError::from)
     }
 
+    /// Send a Markdown message, rendered to HTML with [`crate::markup::markdown_to_html`]
+    ///
+    /// The plain `text` of the message is the rendered HTML converted back to text.
+    pub async fn send_markdown(&self, chat_id: &str, markdown: &str) -> Result<ChatId, SDKError> {
+        let html = markdown_to_html(markdown);
+        self.send_message(
+            chat_id,
+            CreateChatMessage {
+                text: html_to_text(&html),
+                text_html: html,
+                label: String::new(),
+            },
+        )
+        .await
+    }
+
+    /// Send a plain text message, escaped for the HTML version
+    pub async fn send_text(&self, chat_id: &str, text: &str) -> Result<ChatId, SDKError> {
+        self.send_message(
+            chat_id,
+            CreateChatMessage {
+                text: text.to_string(),
+                text_html: text_to_html(text),
+                label: String::new(),
+            },
+        )
+        .await
+    }
+
     /// Get a specific chat message
     pub async fn get_message(&self, chat_id: &str, id: f64) -> Result<ChatMessage, SDKError> {
         self.client
@@ -47,6 +78,104 @@ impl ChatsAPI {
             .map_err(SDKError::from)
     }
 
+    /// Add a reaction of the API key's user to a message
+    ///
+    /// The API takes one reaction per update. The current reactions are read
+    /// first so a reaction that is already there is not sent again; `user_id`
+    /// is the ID of the API key's user.
+    pub async fn react(
+        &self,
+        chat_id: &str,
+        id: f64,
+        user_id: &str,
+        react: React,
+    ) -> Result<(), SDKError> {
+        let reacts = self
+            .get_message(chat_id, id)
+            .await?
+            .reactions
+            .of_user(user_id);
+        if reacts.contains(&react) {
+            return Ok(());
+        }
+        self.set_reaction(chat_id, id, react).await
+    }
+
+    /// Remove a reaction of the API key's user from a message
+    ///
+    /// The API has no call to remove a single reaction, so this sends an empty
+    /// reaction, which clears every reaction of the user on the message.
+    pub async fn unreact(
+        &self,
+        chat_id: &str,
+        id: f64,
+        user_id: &str,
+        react: &React,
+    ) -> Result<(), SDKError> {
+        let reacts = self
+            .get_message(chat_id, id)
+            .await?
+            .reactions
+            .of_user(user_id);
+        if !reacts.contains(react) {
+            return Ok(());
+        }
+        self.set_reaction(chat_id, id, React::Other(String::new()))
+            .await
+    }
+
+    /// Reactions on a message with their users, most frequent first
+    pub async fn reactions_summary(
+        &self,
+        chat_id: &str,
+        id: f64,
+    ) -> Result<Vec<ReactionCount>, SDKError> {
+        let message = self.get_message(chat_id, id).await?;
+        let mut summary: Vec<ReactionCount> = message
+            .reactions
+            .iter()
+            .filter(|(_, users)| !users.is_empty())
+            .map(|(react, users)| ReactionCount {
+                react: react.clone(),
+                user_ids: users.to_vec(),
+            })
+            .collect();
+        summary.sort_by(|a, b| {
+            b.user_ids
+                .len()
+                .cmp(&a.user_ids.len())
+                .then_with(|| a.react.cmp(&b.react))
+        });
+        Ok(summary)
+    }
+
+    /// Count reactions in chats on messages posted in `[from, to)`
+    pub async fn tally_reactions(
+        &self,
+        chat_ids: &[String],
+        from: f64,
+        to: Option<f64>,
+    ) -> Result<ReactionTally, SDKError> {
+        let mut tally = ReactionTally::new(from, to);
+        for chat_id in chat_ids {
+            tally.add_messages(&self.search(chat_id).since(from).all().await?);
+        }
+        Ok(tally)
+    }
+
+    async fn set_reaction(&self, chat_id: &str, id: f64, react: React) -> Result<(), SDKError> {
+        self.update_message(
+            chat_id,
+            id,
+            UpdateChatMessage {
+                react: Some(react),
+                ..Default::default()
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Search for chat messages using a fluent API
     pub fn search(&self, chat_id: &str) -> ChatMessageSearchBuilder {
         ChatMessageSearchBuilder::new(self.client.clone(), chat_id.to_string())
@@ -63,6 +192,19 @@ impl ChatsAPI {
     }
 }
 
+/// Users who gave one reaction to a message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReactionCount {
+    pub react: React,
+    pub user_ids: Vec<String>,
+}
+
+impl ReactionCount {
+    pub fn count(&self) -> usize {
+        self.user_ids.len()
+    }
+}
+
 /// Search builder for chat messages with fluent API
 #[derive(Clone)]
 pub struct ChatMessageSearchBuilder {
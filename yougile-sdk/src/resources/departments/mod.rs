@@ -1,3 +1,7 @@
+mod tree;
+
+pub use tree::DepartmentTree;
+
 use crate::SDKError;
 use std::sync::Arc;
 use yougile_api_client::YouGileClient;
@@ -57,4 +61,26 @@ impl DepartmentsAPI {
     pub async fn list(&self) -> Result<DepartmentList, SDKError> {
         self.search(None, Some(100.0), Some(0.0), None, None).await
     }
+
+    /// List all departments with automatic pagination
+    pub async fn list_all(&self) -> Result<Vec<Department>, SDKError> {
+        let mut all_departments = Vec::new();
+        let mut offset = 0.0;
+        let limit = 100.0;
+
+        loop {
+            let result = self
+                .search(None, Some(limit), Some(offset), None, None)
+                .await?;
+            let count = result.content.len() as f64;
+            all_departments.extend(result.content);
+
+            if count < limit {
+                break;
+            }
+            offset += limit;
+        }
+
+        Ok(all_departments)
+    }
 }
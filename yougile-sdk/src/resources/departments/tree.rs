@@ -0,0 +1,480 @@
+use super::DepartmentsAPI;
+use crate::SDKError;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use yougile_api_client::models::*;
+
+/// Department hierarchy built from the flat department list
+///
+/// Deleted departments are left out. Departments whose parent is unknown or
+/// deleted become top-level departments. A parent chain that loops back on
+/// itself is broken at the department with the smallest ID, which becomes
+/// top-level and is reported in [`DepartmentTree::cycles`].
+#[derive(Clone, Debug, Default)]
+pub struct DepartmentTree {
+    departments: HashMap<String, Department>,
+    parents: HashMap<String, String>,
+    children: HashMap<String, Vec<String>>,
+    roots: Vec<String>,
+    cycles: Vec<Vec<String>>,
+}
+
+impl DepartmentTree {
+    pub fn new(departments: impl IntoIterator<Item = Department>) -> Self {
+        let departments: HashMap<String, Department> = departments
+            .into_iter()
+            .filter(|department| department.deleted != Some(true))
+            .map(|department| (department.id.clone(), department))
+            .collect();
+        let mut parents: HashMap<String, String> = departments
+            .values()
+            .filter_map(|department| {
+                let parent_id = parent_of(department)?;
+                departments
+                    .contains_key(parent_id)
+                    .then(|| (department.id.clone(), parent_id.to_string()))
+            })
+            .collect();
+        let cycles = break_cycles(&mut parents);
+
+        let mut tree = Self {
+            departments,
+            parents,
+            cycles,
+            ..Default::default()
+        };
+        tree.link();
+        tree
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Department> {
+        self.departments.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.departments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.departments.is_empty()
+    }
+
+    /// Top-level departments, by title
+    pub fn roots(&self) -> Vec<&Department> {
+        self.lookup(&self.roots)
+    }
+
+    pub fn parent(&self, id: &str) -> Option<&Department> {
+        self.get(self.parents.get(id)?)
+    }
+
+    /// Direct sub-departments, by title
+    pub fn children(&self, id: &str) -> Vec<&Department> {
+        self.children
+            .get(id)
+            .map(|children| self.lookup(children))
+            .unwrap_or_default()
+    }
+
+    /// Parents of a department, nearest first
+    pub fn ancestors(&self, id: &str) -> Vec<&Department> {
+        let mut ancestors = Vec::new();
+        let mut current = self.parent(id);
+        while let Some(department) = current {
+            ancestors.push(department);
+            current = self.parent(&department.id);
+        }
+        ancestors
+    }
+
+    /// All sub-departments below a department, parents before children
+    pub fn descendants(&self, id: &str) -> Vec<&Department> {
+        let mut descendants = Vec::new();
+        let mut stack: Vec<&String> = self.children.get(id).into_iter().flatten().rev().collect();
+        while let Some(child_id) = stack.pop() {
+            descendants.extend(self.get(child_id));
+            stack.extend(self.children.get(child_id).into_iter().flatten().rev());
+        }
+        descendants
+    }
+
+    /// Number of parents above a department; top-level departments have depth 0
+    pub fn depth(&self, id: &str) -> usize {
+        self.ancestors(id).len()
+    }
+
+    /// Titles from the top-level department down to this one, such as `Sales / EMEA`
+    pub fn path(&self, id: &str) -> Option<String> {
+        let department = self.get(id)?;
+        let mut titles: Vec<&str> = self
+            .ancestors(id)
+            .iter()
+            .rev()
+            .map(|ancestor| ancestor.title.as_str())
+            .collect();
+        titles.push(&department.title);
+        Some(titles.join(" / "))
+    }
+
+    /// Depth-first iterator over every department with its depth
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Department)> {
+        let mut stack: Vec<(usize, &String)> = self.roots.iter().rev().map(|id| (0, id)).collect();
+        std::iter::from_fn(move || {
+            let (depth, id) = stack.pop()?;
+            stack.extend(
+                self.children
+                    .get(id)
+                    .into_iter()
+                    .flatten()
+                    .rev()
+                    .map(|child_id| (depth + 1, child_id)),
+            );
+            self.get(id).map(|department| (depth, department))
+        })
+    }
+
+    /// Parent chains that looped, each listed from the department where it was broken
+    pub fn cycles(&self) -> &[Vec<String>] {
+        &self.cycles
+    }
+
+    /// Members of the department itself with their roles
+    pub fn members(&self, id: &str) -> BTreeMap<String, String> {
+        self.get(id)
+            .and_then(|department| department.users.as_ref())
+            .map(|users| {
+                users
+                    .iter()
+                    .map(|(user_id, role)| (user_id.clone(), role.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Members of the department and all of its sub-departments
+    ///
+    /// A user in several of them keeps the role of the department nearest to
+    /// this one.
+    pub fn all_members(&self, id: &str) -> BTreeMap<String, String> {
+        let mut members = self.members(id);
+        let mut level: Vec<&String> = self.children.get(id).into_iter().flatten().collect();
+        while !level.is_empty() {
+            let mut next = Vec::new();
+            for child_id in level {
+                for (user_id, role) in self.members(child_id) {
+                    members.entry(user_id).or_insert(role);
+                }
+                next.extend(self.children.get(child_id).into_iter().flatten());
+            }
+            level = next;
+        }
+        members
+    }
+
+    /// Departments the user is a direct member of
+    pub fn departments_of(&self, user_id: &str) -> Vec<&Department> {
+        self.iter()
+            .map(|(_, department)| department)
+            .filter(|department| {
+                department
+                    .users
+                    .as_ref()
+                    .is_some_and(|users| users.contains_key(user_id))
+            })
+            .collect()
+    }
+
+    /// Check that a department can be moved under `parent_id`, or to the top level with `None`
+    pub fn check_move(&self, id: &str, parent_id: Option<&str>) -> Result<(), SDKError> {
+        if self.get(id).is_none() {
+            return Err(SDKError::not_found(format!("department {}", id)));
+        }
+        let Some(parent_id) = parent_id else {
+            return Ok(());
+        };
+        if self.get(parent_id).is_none() {
+            return Err(SDKError::not_found(format!("department {}", parent_id)));
+        }
+        if parent_id == id
+            || self
+                .ancestors(parent_id)
+                .iter()
+                .any(|ancestor| ancestor.id == id)
+        {
+            return Err(SDKError::validation(format!(
+                "Moving department {} under {} would create a cycle",
+                id, parent_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Move a department in this tree, see [`DepartmentsAPI::move_department`]
+    /// to move it in YouGile
+    pub fn move_to(&mut self, id: &str, parent_id: Option<&str>) -> Result<(), SDKError> {
+        self.check_move(id, parent_id)?;
+        match parent_id {
+            Some(parent_id) => self.parents.insert(id.to_string(), parent_id.to_string()),
+            None => self.parents.remove(id),
+        };
+        if let Some(department) = self.departments.get_mut(id) {
+            department.parent_id = parent_id.map(str::to_string);
+        }
+        self.link();
+        Ok(())
+    }
+
+    /// Render the org chart as indented text, one department per line
+    pub fn render(&self) -> String {
+        self.to_string()
+    }
+
+    /// Render the org chart as a Graphviz DOT graph
+    pub fn to_dot(&self) -> String {
+        let mut dot =
+            String::from("digraph departments {\n    rankdir=TB;\n    node [shape=box];\n");
+        for (_, department) in self.iter() {
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\\n{}\"];\n",
+                escape_dot(&department.id),
+                escape_dot(&department.title),
+                member_count(department)
+            ));
+        }
+        for (_, department) in self.iter() {
+            if let Some(parent_id) = self.parents.get(&department.id) {
+                dot.push_str(&format!(
+                    "    \"{}\" -> \"{}\";\n",
+                    escape_dot(parent_id),
+                    escape_dot(&department.id)
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn lookup(&self, ids: &[String]) -> Vec<&Department> {
+        ids.iter().filter_map(|id| self.get(id)).collect()
+    }
+
+    /// Rebuild children and roots from the parent links
+    fn link(&mut self) {
+        self.children.clear();
+        self.roots.clear();
+        for id in self.departments.keys() {
+            match self.parents.get(id) {
+                Some(parent_id) => self
+                    .children
+                    .entry(parent_id.clone())
+                    .or_default()
+                    .push(id.clone()),
+                None => self.roots.push(id.clone()),
+            }
+        }
+
+        let departments = &self.departments;
+        let by_title =
+            |a: &String, b: &String| (&departments[a].title, a).cmp(&(&departments[b].title, b));
+        self.roots.sort_by(by_title);
+        for children in self.children.values_mut() {
+            children.sort_by(by_title);
+        }
+    }
+}
+
+impl fmt::Display for DepartmentTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_node(
+            f: &mut fmt::Formatter<'_>,
+            tree: &DepartmentTree,
+            department: &Department,
+            prefix: &str,
+            last: bool,
+            root: bool,
+        ) -> fmt::Result {
+            let branch = match (root, last) {
+                (true, _) => "",
+                (false, true) => "└── ",
+                (false, false) => "├── ",
+            };
+            writeln!(
+                f,
+                "{}{}{} ({})",
+                prefix,
+                branch,
+                department.title,
+                member_count(department)
+            )?;
+
+            let child_prefix = match (root, last) {
+                (true, _) => prefix.to_string(),
+                (false, true) => format!("{}    ", prefix),
+                (false, false) => format!("{}│   ", prefix),
+            };
+            let children = tree.children(&department.id);
+            for (index, child) in children.iter().enumerate() {
+                write_node(
+                    f,
+                    tree,
+                    child,
+                    &child_prefix,
+                    index + 1 == children.len(),
+                    false,
+                )?;
+            }
+            Ok(())
+        }
+
+        for root in self.roots() {
+            write_node(f, self, root, "", true, true)?;
+        }
+        Ok(())
+    }
+}
+
+impl DepartmentsAPI {
+    /// Load every department into a [`DepartmentTree`]
+    pub async fn tree(&self) -> Result<DepartmentTree, SDKError> {
+        Ok(DepartmentTree::new(self.list_all().await?))
+    }
+
+    /// Move a department under another one, or to the top level with `None`
+    ///
+    /// Fails without changing anything if the move would create a cycle.
+    pub async fn move_department(&self, id: &str, parent_id: Option<&str>) -> Result<(), SDKError> {
+        self.tree().await?.check_move(id, parent_id)?;
+        self.update(
+            id,
+            UpdateDepartment {
+                parent_id: Some(parent_id.unwrap_or(UNSET).to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+fn parent_of(department: &Department) -> Option<&str> {
+    department
+        .parent_id
+        .as_deref()
+        .filter(|parent_id| !parent_id.is_empty() && *parent_id != UNSET)
+}
+
+/// Remove one link from every parent chain that loops, returning the loops found
+fn break_cycles(parents: &mut HashMap<String, String>) -> Vec<Vec<String>> {
+    let mut ids: Vec<String> = parents.keys().cloned().collect();
+    ids.sort();
+
+    let mut done: HashMap<String, bool> = HashMap::new();
+    let mut cycles = Vec::new();
+    for id in ids {
+        let mut path: Vec<String> = Vec::new();
+        let mut current = Some(id);
+        while let Some(id) = current {
+            match done.get(&id) {
+                Some(true) => break,
+                Some(false) => {
+                    let start = path.iter().position(|step| *step == id).unwrap_or(0);
+                    let mut cycle = path[start..].to_vec();
+                    let smallest = (0..cycle.len())
+                        .min_by(|a, b| cycle[*a].cmp(&cycle[*b]))
+                        .unwrap_or(0);
+                    cycle.rotate_left(smallest);
+                    parents.remove(&cycle[0]);
+                    cycles.push(cycle);
+                    break;
+                }
+                None => {
+                    done.insert(id.clone(), false);
+                    current = parents.get(&id).cloned();
+                    path.push(id);
+                }
+            }
+        }
+        for step in path {
+            done.insert(step, true);
+        }
+    }
+    cycles
+}
+
+fn member_count(department: &Department) -> String {
+    match department.users.as_ref().map_or(0, |users| users.len()) {
+        1 => "1 member".to_string(),
+        count => format!("{} members", count),
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parents(links: &[(&str, &str)]) -> HashMap<String, String> {
+        links
+            .iter()
+            .map(|(id, parent_id)| (id.to_string(), parent_id.to_string()))
+            .collect()
+    }
+
+    fn department(id: &str, parent_id: &str) -> Department {
+        Department {
+            parent_id: Some(parent_id.to_string()),
+            ..Department::new(id.to_string(), id.to_uppercase())
+        }
+    }
+
+    #[test]
+    fn chains_without_loops_are_kept() {
+        let mut links = parents(&[("b", "a"), ("c", "b"), ("d", "a")]);
+        let expected = links.clone();
+
+        assert!(break_cycles(&mut links).is_empty());
+        assert_eq!(links, expected);
+    }
+
+    #[test]
+    fn loop_is_broken_at_the_smallest_id() {
+        let mut links = parents(&[("c", "b"), ("b", "d"), ("d", "c"), ("e", "d")]);
+
+        assert_eq!(break_cycles(&mut links), [["b", "d", "c"]]);
+        assert_eq!(links, parents(&[("c", "b"), ("d", "c"), ("e", "d")]));
+    }
+
+    #[test]
+    fn every_loop_is_reported_once() {
+        let mut links = parents(&[("a", "a"), ("x", "y"), ("y", "x"), ("z", "x")]);
+
+        assert_eq!(
+            break_cycles(&mut links),
+            [
+                vec!["a".to_string()],
+                vec!["x".to_string(), "y".to_string()]
+            ]
+        );
+        assert_eq!(links, parents(&[("y", "x"), ("z", "x")]));
+    }
+
+    #[test]
+    fn tree_turns_broken_loops_into_roots() {
+        let tree = DepartmentTree::new([
+            department("a", UNSET),
+            department("b", "c"),
+            department("c", "b"),
+            department("d", "missing"),
+        ]);
+
+        assert_eq!(tree.cycles(), [["b", "c"]]);
+        let roots: Vec<&str> = tree.roots().iter().map(|root| root.id.as_str()).collect();
+        assert_eq!(roots, ["a", "b", "d"]);
+        assert_eq!(tree.path("c").as_deref(), Some("B / C"));
+        assert!(tree.check_move("b", Some("c")).is_err());
+    }
+}
@@ -1,9 +1,322 @@
 use crate::SDKError;
+use crate::markup::{escape, text_to_html};
+use crate::resources::{ChatsAPI, TasksAPI};
+use std::path::Path;
 use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt};
 
 use yougile_api_client::YouGileClient;
 use yougile_api_client::models::*;
 
+/// Largest file accepted by default, in bytes
+const DEFAULT_MAX_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Size of the chunks read from a file or stream
+const READ_CHUNK: usize = 64 * 1024;
+
+const DEFAULT_MIME: &str = "application/octet-stream";
+
+/// MIME types by lowercase file extension
+const MIME_TYPES: &[(&str, &str)] = &[
+    ("7z", "application/x-7z-compressed"),
+    ("avi", "video/x-msvideo"),
+    ("bmp", "image/bmp"),
+    ("csv", "text/csv"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("heic", "image/heic"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("json", "application/json"),
+    ("log", "text/plain"),
+    ("md", "text/markdown"),
+    ("mov", "video/quicktime"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("odp", "application/vnd.oasis.opendocument.presentation"),
+    ("ods", "application/vnd.oasis.opendocument.spreadsheet"),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("ogg", "audio/ogg"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("ppt", "application/vnd.ms-powerpoint"),
+    (
+        "pptx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+    ("rar", "application/vnd.rar"),
+    ("rtf", "application/rtf"),
+    ("svg", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("txt", "text/plain"),
+    ("wav", "audio/wav"),
+    ("webm", "video/webm"),
+    ("webp", "image/webp"),
+    ("xls", "application/vnd.ms-excel"),
+    (
+        "xlsx",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    ),
+    ("xml", "application/xml"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("zip", "application/zip"),
+];
+
+/// Leading bytes of common formats, for files without a known extension
+const SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+];
+
+/// Guess the MIME type of a file from its name, then from its first bytes
+fn detect_mime(file_name: &str, data: &[u8]) -> &'static str {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    if let Some(extension) = extension
+        && let Some((_, mime)) = MIME_TYPES.iter().find(|(known, _)| *known == extension)
+    {
+        return mime;
+    }
+    SIGNATURES
+        .iter()
+        .find(|(signature, _)| data.starts_with(signature))
+        .map_or(DEFAULT_MIME, |(_, mime)| mime)
+}
+
+/// Progress of an upload, passed to [`FileUploadBuilder::on_read_progress`]
+///
+/// The file is read into memory completely before it is sent in a single
+/// request, so progress is reported while reading and once more after the
+/// upload; there are no reports while the request is sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UploadProgress {
+    /// Bytes read so far
+    pub read: u64,
+    /// Size of the file, if known in advance
+    pub total: Option<u64>,
+    /// Set on the last report, after the server accepted the file
+    pub uploaded: bool,
+}
+
+/// An uploaded file, ready to be linked from tasks and chats
+#[derive(Clone, Debug, PartialEq)]
+pub struct UploadedFile {
+    pub name: String,
+    pub mime: String,
+    pub size: u64,
+    /// URL relative to the YouGile server
+    pub url: String,
+    pub full_url: String,
+}
+
+impl UploadedFile {
+    /// The full URL if the server returned one, otherwise the relative one
+    pub fn link(&self) -> &str {
+        if self.full_url.is_empty() {
+            &self.url
+        } else {
+            &self.full_url
+        }
+    }
+
+    fn link_html(&self) -> String {
+        format!(
+            "<a href=\"{}\">{}</a>",
+            escape(self.link()),
+            escape(&self.name)
+        )
+    }
+}
+
+/// Where [`FilesAPI::attach_to_task`] puts the link to a file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskAttachment {
+    /// Append a paragraph with the link to the description
+    Description,
+    /// Post a message with the link into the task chat
+    Chat,
+}
+
+/// Builder for uploads from a path, a reader or bytes
+///
+/// The API takes the whole file in one request, so files and readers are read
+/// into memory before they are sent.
+pub struct FileUploadBuilder {
+    client: Arc<YouGileClient>,
+    name: Option<String>,
+    mime: Option<String>,
+    max_size: Option<u64>,
+    progress: Option<Box<dyn Fn(UploadProgress) + Send + Sync>>,
+}
+
+impl FileUploadBuilder {
+    pub fn new(client: Arc<YouGileClient>) -> Self {
+        Self {
+            client,
+            name: None,
+            mime: None,
+            max_size: Some(DEFAULT_MAX_SIZE),
+            progress: None,
+        }
+    }
+
+    /// File name shown in YouGile, instead of the name of the path
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// MIME type to send instead of the detected one
+    pub fn mime(mut self, mime: impl Into<String>) -> Self {
+        self.mime = Some(mime.into());
+        self
+    }
+
+    /// Reject files larger than `max_size` bytes, 100 MiB by default
+    pub fn max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    pub fn no_size_limit(mut self) -> Self {
+        self.max_size = None;
+        self
+    }
+
+    /// Call `progress` while the file is read and after it was uploaded
+    pub fn on_read_progress(
+        mut self,
+        progress: impl Fn(UploadProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Upload the file at `path`
+    pub async fn path(self, path: impl AsRef<Path>) -> Result<UploadedFile, SDKError> {
+        let path = path.as_ref();
+        let open_error = |error: std::io::Error| {
+            if error.kind() == std::io::ErrorKind::NotFound {
+                SDKError::not_found(format!("file {}", path.display()))
+            } else {
+                SDKError::Other(format!("Cannot read {}: {}", path.display(), error))
+            }
+        };
+
+        let file = tokio::fs::File::open(path).await.map_err(open_error)?;
+        let size = file.metadata().await.map_err(open_error)?.len();
+        self.check_size(size)?;
+
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or_else(|| SDKError::validation(format!("{} is not a file", path.display())))?;
+        self.read_all(file, &name, Some(size)).await
+    }
+
+    /// Read `reader` to the end into memory, then upload it under `name`
+    pub async fn buffered(
+        self,
+        reader: impl AsyncRead + Unpin,
+        name: &str,
+    ) -> Result<UploadedFile, SDKError> {
+        self.read_all(reader, name, None).await
+    }
+
+    pub async fn bytes(self, data: Vec<u8>, name: &str) -> Result<UploadedFile, SDKError> {
+        let size = data.len() as u64;
+        self.check_size(size)?;
+        self.report(size, Some(size), false);
+        self.send(data, name).await
+    }
+
+    async fn read_all(
+        self,
+        mut reader: impl AsyncRead + Unpin,
+        name: &str,
+        total: Option<u64>,
+    ) -> Result<UploadedFile, SDKError> {
+        let mut data = Vec::with_capacity(total.unwrap_or(0) as usize);
+        let mut chunk = vec![0; READ_CHUNK];
+        loop {
+            let read = reader
+                .read(&mut chunk)
+                .await
+                .map_err(|error| SDKError::Other(format!("Cannot read {}: {}", name, error)))?;
+            if read == 0 {
+                break;
+            }
+            data.extend_from_slice(&chunk[..read]);
+            self.check_size(data.len() as u64)?;
+            self.report(data.len() as u64, total, false);
+        }
+        self.send(data, name).await
+    }
+
+    async fn send(self, data: Vec<u8>, name: &str) -> Result<UploadedFile, SDKError> {
+        if data.is_empty() {
+            return Err(SDKError::validation(format!("{} is empty", name)));
+        }
+
+        let name = self.name.clone().unwrap_or_else(|| name.to_string());
+        let mime = match &self.mime {
+            Some(mime) => mime.clone(),
+            None => detect_mime(&name, &data).to_string(),
+        };
+        let size = data.len() as u64;
+        let upload = self
+            .client
+            .upload_file_with_type(data, &name, &mime)
+            .await?;
+        self.report(size, Some(size), true);
+
+        Ok(UploadedFile {
+            name,
+            mime,
+            size,
+            url: upload.url,
+            full_url: upload.full_url,
+        })
+    }
+
+    fn check_size(&self, size: u64) -> Result<(), SDKError> {
+        match self.max_size {
+            Some(max_size) if size > max_size => Err(SDKError::validation(format!(
+                "File is larger than {} bytes",
+                max_size
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn report(&self, read: u64, total: Option<u64>, uploaded: bool) {
+        if let Some(progress) = &self.progress {
+            progress(UploadProgress {
+                read,
+                total,
+                uploaded,
+            });
+        }
+    }
+}
+
 /// API for working with files
 pub struct FilesAPI {
     client: Arc<YouGileClient>,
@@ -25,4 +338,101 @@ impl FilesAPI {
             .await
             .map_err(SDKError::from)
     }
+
+    /// Upload with MIME detection, a size limit and progress reports
+    pub fn upload(&self) -> FileUploadBuilder {
+        FileUploadBuilder::new(self.client.clone())
+    }
+
+    /// Upload the file at `path` with the default settings
+    pub async fn upload_path(&self, path: impl AsRef<Path>) -> Result<UploadedFile, SDKError> {
+        self.upload().path(path).await
+    }
+
+    /// Read `reader` into memory and upload it with the default settings
+    pub async fn upload_buffered(
+        &self,
+        reader: impl AsyncRead + Unpin,
+        name: &str,
+    ) -> Result<UploadedFile, SDKError> {
+        self.upload().buffered(reader, name).await
+    }
+
+    /// Link an uploaded file from a task
+    pub async fn attach_to_task(
+        &self,
+        task_id: &str,
+        file: &UploadedFile,
+        attachment: TaskAttachment,
+    ) -> Result<(), SDKError> {
+        match attachment {
+            TaskAttachment::Description => {
+                let tasks = TasksAPI::new(self.client.clone());
+                let description = tasks.get(task_id).await?.description.unwrap_or_default();
+                tasks
+                    .update(
+                        task_id,
+                        UpdateTask {
+                            description: Some(format!(
+                                "{}<p>{}</p>",
+                                description,
+                                file.link_html()
+                            )),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+            }
+            TaskAttachment::Chat => {
+                self.attach_to_chat(task_id, file, None).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Post a message with a link to an uploaded file, returning the message ID
+    ///
+    /// Works for group chats and, with the task ID as chat ID, for task chats.
+    pub async fn attach_to_chat(
+        &self,
+        chat_id: &str,
+        file: &UploadedFile,
+        caption: Option<&str>,
+    ) -> Result<f64, SDKError> {
+        let (text, text_html) = match caption {
+            Some(caption) => (
+                format!("{}\n{}", caption, file.link()),
+                format!("{}<br>{}", text_to_html(caption), file.link_html()),
+            ),
+            None => (file.link().to_string(), file.link_html()),
+        };
+        let message = ChatsAPI::new(self.client.clone())
+            .send_message(
+                chat_id,
+                CreateChatMessage {
+                    text,
+                    text_html,
+                    label: String::new(),
+                },
+            )
+            .await?;
+        Ok(message.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mime_comes_from_the_extension_first() {
+        assert_eq!(detect_mime("Report.PDF", b""), "application/pdf");
+        assert_eq!(detect_mime("notes.txt", b"%PDF-1.7"), "text/plain");
+    }
+
+    #[test]
+    fn mime_falls_back_to_the_signature() {
+        assert_eq!(detect_mime("scan", b"\x89PNG\r\n\x1a\n...."), "image/png");
+        assert_eq!(detect_mime("data.bin", b"\x00\x01"), DEFAULT_MIME);
+    }
 }
@@ -52,6 +52,199 @@ impl GroupChatsAPI {
     pub async fn list_all(&self) -> Result<Vec<GroupChat>, SDKError> {
         self.search().all().await
     }
+
+    /// Members, their roles and the role configs of a chat
+    pub async fn members(&self, chat_id: &str) -> Result<GroupChatMembers, SDKError> {
+        Ok(GroupChatMembers::from(&self.get(chat_id).await?))
+    }
+
+    /// Add a user to a chat with a role defined in the chat
+    pub async fn add_member(
+        &self,
+        chat_id: &str,
+        user_id: &str,
+        role_id: &str,
+    ) -> Result<(), SDKError> {
+        self.edit_members(chat_id, |members| members.add_member(user_id, role_id))
+            .await
+    }
+
+    /// Remove a user and their role from a chat
+    pub async fn remove_member(&self, chat_id: &str, user_id: &str) -> Result<(), SDKError> {
+        self.edit_members(chat_id, |members| members.remove_member(user_id))
+            .await
+    }
+
+    /// Change the role of a member
+    pub async fn set_role(
+        &self,
+        chat_id: &str,
+        user_id: &str,
+        role_id: &str,
+    ) -> Result<(), SDKError> {
+        self.edit_members(chat_id, |members| members.set_role(user_id, role_id))
+            .await
+    }
+
+    /// Add a role to a chat or replace the config of an existing one
+    pub async fn define_role(
+        &self,
+        chat_id: &str,
+        role_id: &str,
+        config: RoleConfig,
+    ) -> Result<(), SDKError> {
+        self.edit_members(chat_id, |members| {
+            members.define_role(role_id, config);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Remove a role that no member has
+    pub async fn remove_role(&self, chat_id: &str, role_id: &str) -> Result<(), SDKError> {
+        self.edit_members(chat_id, |members| members.remove_role(role_id))
+            .await
+    }
+
+    /// Apply a change to the members of a chat and save all three maps together
+    async fn edit_members(
+        &self,
+        chat_id: &str,
+        change: impl FnOnce(&mut GroupChatMembers) -> Result<(), SDKError>,
+    ) -> Result<(), SDKError> {
+        let mut members = self.members(chat_id).await?;
+        change(&mut members)?;
+        members.validate()?;
+
+        self.update(chat_id, members.into_update()).await?;
+        Ok(())
+    }
+}
+
+/// The users, roles and role configs of a group chat, kept consistent
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GroupChatMembers {
+    pub users: Vec<String>,
+    pub user_role_map: UserRoleMap,
+    pub role_config_map: RoleConfigMap,
+}
+
+impl From<&GroupChat> for GroupChatMembers {
+    fn from(chat: &GroupChat) -> Self {
+        Self {
+            users: chat.users.clone(),
+            user_role_map: chat.user_role_map.clone(),
+            role_config_map: chat.role_config_map.clone(),
+        }
+    }
+}
+
+impl GroupChatMembers {
+    pub fn is_member(&self, user_id: &str) -> bool {
+        self.users.iter().any(|user| user == user_id)
+    }
+
+    /// Role ID of a member, if one is assigned
+    pub fn role_of(&self, user_id: &str) -> Option<&str> {
+        self.user_role_map
+            .get(user_id)
+            .map(String::as_str)
+            .filter(|role_id| *role_id != UNSET)
+    }
+
+    /// Members with the given role
+    pub fn with_role(&self, role_id: &str) -> Vec<&str> {
+        self.users
+            .iter()
+            .filter(|user| self.role_of(user) == Some(role_id))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Add a user with a role, or change the role of an existing member
+    pub fn add_member(&mut self, user_id: &str, role_id: &str) -> Result<(), SDKError> {
+        self.require_role(role_id)?;
+        if !self.is_member(user_id) {
+            self.users.push(user_id.to_string());
+        }
+        self.user_role_map
+            .insert(user_id.to_string(), role_id.to_string());
+        Ok(())
+    }
+
+    /// Remove a member; their role is unset with [`UNSET`] so the update clears it
+    pub fn remove_member(&mut self, user_id: &str) -> Result<(), SDKError> {
+        if !self.is_member(user_id) {
+            return Err(SDKError::not_found(format!("chat member {}", user_id)));
+        }
+        self.users.retain(|user| user != user_id);
+        self.user_role_map
+            .insert(user_id.to_string(), UNSET.to_string());
+        Ok(())
+    }
+
+    pub fn set_role(&mut self, user_id: &str, role_id: &str) -> Result<(), SDKError> {
+        if !self.is_member(user_id) {
+            return Err(SDKError::not_found(format!("chat member {}", user_id)));
+        }
+        self.add_member(user_id, role_id)
+    }
+
+    pub fn define_role(&mut self, role_id: &str, config: RoleConfig) {
+        self.role_config_map.insert(role_id.to_string(), config);
+    }
+
+    pub fn remove_role(&mut self, role_id: &str) -> Result<(), SDKError> {
+        self.require_role(role_id)?;
+        let holders = self.with_role(role_id);
+        if !holders.is_empty() {
+            return Err(SDKError::validation(format!(
+                "Role {} is still assigned to {} member(s)",
+                role_id,
+                holders.len()
+            )));
+        }
+        self.role_config_map.remove(role_id);
+        Ok(())
+    }
+
+    /// Check that every mapped user is a member and every mapped role exists
+    pub fn validate(&self) -> Result<(), SDKError> {
+        for (user_id, role_id) in &self.user_role_map {
+            if role_id == UNSET {
+                continue;
+            }
+            if !self.is_member(user_id) {
+                return Err(SDKError::validation(format!(
+                    "User {} has a role but is not a chat member",
+                    user_id
+                )));
+            }
+            self.require_role(role_id)?;
+        }
+        Ok(())
+    }
+
+    /// An update that writes all three maps at once
+    pub fn into_update(self) -> UpdateGroupChat {
+        UpdateGroupChat {
+            users: Some(self.users),
+            user_role_map: Some(self.user_role_map),
+            role_config_map: Some(self.role_config_map),
+            ..Default::default()
+        }
+    }
+
+    fn require_role(&self, role_id: &str) -> Result<(), SDKError> {
+        if self.role_config_map.contains_key(role_id) {
+            Ok(())
+        } else {
+            Err(SDKError::validation(format!(
+                "Role {} is not defined in the chat",
+                role_id
+            )))
+        }
+    }
 }
 
 /// Search builder for group chats with fluent API
@@ -128,3 +321,90 @@ impl GroupChatSearchBuilder {
         Ok(all_chats)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members() -> GroupChatMembers {
+        GroupChatMembers {
+            users: vec!["u1".to_string(), "u2".to_string()],
+            user_role_map: UserRoleMap::from([
+                ("u1".to_string(), "admin".to_string()),
+                ("u2".to_string(), "member".to_string()),
+            ]),
+            role_config_map: RoleConfigMap::from([
+                ("admin".to_string(), RoleConfig::new("Admin".to_string())),
+                ("member".to_string(), RoleConfig::new("Member".to_string())),
+                ("guest".to_string(), RoleConfig::new("Guest".to_string())),
+            ]),
+        }
+    }
+
+    #[test]
+    fn remove_member_unsets_their_role() {
+        let mut members = members();
+        members.remove_member("u2").unwrap();
+
+        assert_eq!(members.users, ["u1"]);
+        assert_eq!(members.role_of("u2"), None);
+        assert!(members.validate().is_ok());
+        let update = members.clone().into_update();
+        assert_eq!(update.user_role_map.unwrap()["u2"], UNSET);
+
+        assert!(matches!(
+            members.remove_member("u2"),
+            Err(SDKError::NotFound(_))
+        ));
+
+        // Adding the user back replaces the unset role
+        members.add_member("u2", "guest").unwrap();
+        assert_eq!(members.role_of("u2"), Some("guest"));
+    }
+
+    #[test]
+    fn set_role_requires_a_member_and_a_defined_role() {
+        let mut members = members();
+        members.set_role("u2", "guest").unwrap();
+        assert_eq!(members.with_role("guest"), ["u2"]);
+
+        assert!(matches!(
+            members.set_role("u3", "guest"),
+            Err(SDKError::NotFound(_))
+        ));
+        assert!(matches!(
+            members.set_role("u1", "owner"),
+            Err(SDKError::ValidationError(_))
+        ));
+        assert_eq!(members.role_of("u1"), Some("admin"));
+    }
+
+    #[test]
+    fn remove_role_only_without_holders() {
+        let mut members = members();
+        assert!(matches!(
+            members.remove_role("member"),
+            Err(SDKError::ValidationError(_))
+        ));
+        assert!(matches!(
+            members.remove_role("owner"),
+            Err(SDKError::ValidationError(_))
+        ));
+
+        members.remove_role("guest").unwrap();
+        assert!(!members.role_config_map.contains_key("guest"));
+
+        members.remove_member("u2").unwrap();
+        members.remove_role("member").unwrap();
+        assert!(members.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_roles_of_non_members() {
+        let mut members = members();
+        members
+            .user_role_map
+            .insert("u3".to_string(), "member".to_string());
+        assert!(members.validate().is_err());
+    }
+}
@@ -6,6 +6,7 @@ pub mod departments;
 pub mod files;
 pub mod group_chats;
 pub mod projects;
+pub mod sprints;
 pub mod stickers;
 pub mod tasks;
 pub mod users;
@@ -13,12 +14,13 @@ pub mod webhooks;
 
 pub use auth::AuthAPI;
 pub use boards::BoardsAPI;
-pub use chats::ChatsAPI;
+pub use chats::{ChatsAPI, ReactionCount};
 pub use columns::ColumnsAPI;
 pub use departments::DepartmentsAPI;
 pub use files::FilesAPI;
-pub use group_chats::GroupChatsAPI;
+pub use group_chats::{GroupChatMembers, GroupChatsAPI};
 pub use projects::ProjectsAPI;
+pub use sprints::SprintsAPI;
 pub use stickers::StickersAPI;
 pub use tasks::TasksAPI;
 pub use users::UsersAPI;
@@ -0,0 +1,352 @@
+use super::ProjectsAPI;
+use crate::SDKError;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// Role of a user in a project: a system role or the ID of a custom role
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MemberRole {
+    Worker,
+    Admin,
+    Observer,
+    Custom(String),
+}
+
+impl MemberRole {
+    pub fn as_str(&self) -> &str {
+        match self {
+            MemberRole::Worker => "worker",
+            MemberRole::Admin => "admin",
+            MemberRole::Observer => "observer",
+            MemberRole::Custom(id) => id,
+        }
+    }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, MemberRole::Custom(_))
+    }
+}
+
+impl From<&str> for MemberRole {
+    fn from(value: &str) -> Self {
+        match value {
+            "worker" => MemberRole::Worker,
+            "admin" => MemberRole::Admin,
+            "observer" => MemberRole::Observer,
+            other => MemberRole::Custom(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for MemberRole {
+    fn from(value: String) -> Self {
+        MemberRole::from(value.as_str())
+    }
+}
+
+impl fmt::Display for MemberRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Settings for [`ProjectMembers::copy_from`]
+#[derive(Clone, Debug, Default)]
+pub struct CopyMembers {
+    overwrite: bool,
+    fallback: Option<MemberRole>,
+    dry_run: bool,
+}
+
+impl CopyMembers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also change the role of users who are already members
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Role for users whose custom role has no role of the same name in the target
+    ///
+    /// Without a fallback, such users are skipped.
+    pub fn fallback(mut self, role: MemberRole) -> Self {
+        self.fallback = Some(role);
+        self
+    }
+
+    /// Only report the planned changes
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+/// Changes made (or planned) by [`ProjectMembers::copy_from`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemberCopyReport {
+    pub dry_run: bool,
+    pub added: Vec<(String, MemberRole)>,
+    pub changed: Vec<(String, MemberRole)>,
+    /// Users left out, with the reason
+    pub skipped: Vec<(String, String)>,
+}
+
+/// Result of adding a user to one of several projects
+#[derive(Debug)]
+pub struct ProjectAssignment {
+    pub project_id: String,
+    pub role: MemberRole,
+    pub result: Result<(), SDKError>,
+}
+
+/// Handle for the members of a single project
+pub struct ProjectMembers {
+    client: Arc<YouGileClient>,
+    project_id: String,
+}
+
+impl ProjectMembers {
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
+
+    /// Members and their roles
+    pub async fn list(&self) -> Result<HashMap<String, MemberRole>, SDKError> {
+        let project = self.client.get_project(&self.project_id).await?;
+        Ok(project
+            .users
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(user_id, role)| (user_id, MemberRole::from(role)))
+            .collect())
+    }
+
+    pub async fn role_of(&self, user_id: &str) -> Result<Option<MemberRole>, SDKError> {
+        Ok(self.list().await?.remove(user_id))
+    }
+
+    /// Add a user, or change their role if they are already a member
+    pub async fn add(&self, user_id: &str, role: impl Into<MemberRole>) -> Result<(), SDKError> {
+        let role = role.into();
+        self.require_role(&role).await?;
+        self.send([(user_id.to_string(), role.as_str().to_string())])
+            .await
+    }
+
+    /// Add several users in one update
+    pub async fn add_many(&self, members: &[(String, MemberRole)]) -> Result<(), SDKError> {
+        let roles = self.custom_role_ids().await?;
+        for (_, role) in members {
+            check_role(role, &roles, &self.project_id)?;
+        }
+        self.send(
+            members
+                .iter()
+                .map(|(user_id, role)| (user_id.clone(), role.as_str().to_string())),
+        )
+        .await
+    }
+
+    pub async fn remove(&self, user_id: &str) -> Result<(), SDKError> {
+        if self.role_of(user_id).await?.is_none() {
+            return Err(SDKError::not_found(format!(
+                "member {} of project {}",
+                user_id, self.project_id
+            )));
+        }
+        self.send([(user_id.to_string(), UNSET.to_string())]).await
+    }
+
+    /// Change the role of an existing member
+    pub async fn change_role(
+        &self,
+        user_id: &str,
+        role: impl Into<MemberRole>,
+    ) -> Result<(), SDKError> {
+        if self.role_of(user_id).await?.is_none() {
+            return Err(SDKError::not_found(format!(
+                "member {} of project {}",
+                user_id, self.project_id
+            )));
+        }
+        self.add(user_id, role).await
+    }
+
+    /// Copy the members of another project into this one
+    ///
+    /// Custom roles belong to a single project, so they are matched to the role
+    /// of the same name in this project.
+    pub async fn copy_from(
+        &self,
+        source_project_id: &str,
+        options: CopyMembers,
+    ) -> Result<MemberCopyReport, SDKError> {
+        let source = ProjectMembers {
+            client: self.client.clone(),
+            project_id: source_project_id.to_string(),
+        };
+        let source_members = source.list().await?;
+        let current = self.list().await?;
+
+        let projects = ProjectsAPI::new(self.client.clone());
+        let source_roles = projects.list_roles_all(source_project_id).await?;
+        let target_roles = projects.list_roles_all(&self.project_id).await?;
+
+        let mut report = MemberCopyReport {
+            dry_run: options.dry_run,
+            ..Default::default()
+        };
+        let mut users: Vec<(&String, &MemberRole)> = source_members.iter().collect();
+        users.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (user_id, role) in users {
+            let role = match role {
+                MemberRole::Custom(role_id) => {
+                    let name = source_roles
+                        .iter()
+                        .find(|source| source.id == *role_id)
+                        .map(|source| source.name.as_str());
+                    let matching = target_roles
+                        .iter()
+                        .find(|target| Some(target.name.as_str()) == name);
+                    match (matching, &options.fallback) {
+                        (Some(target), _) => MemberRole::Custom(target.id.clone()),
+                        (None, Some(fallback)) => fallback.clone(),
+                        (None, None) => {
+                            report.skipped.push((
+                                user_id.clone(),
+                                format!("no role named '{}'", name.unwrap_or(role_id)),
+                            ));
+                            continue;
+                        }
+                    }
+                }
+                system => system.clone(),
+            };
+
+            match current.get(user_id) {
+                None => report.added.push((user_id.clone(), role)),
+                Some(existing) if *existing == role => {}
+                Some(_) if options.overwrite => report.changed.push((user_id.clone(), role)),
+                Some(existing) => report
+                    .skipped
+                    .push((user_id.clone(), format!("already a member as {}", existing))),
+            }
+        }
+
+        let has_changes = !report.added.is_empty() || !report.changed.is_empty();
+        if has_changes && !options.dry_run {
+            self.send(
+                report
+                    .added
+                    .iter()
+                    .chain(&report.changed)
+                    .map(|(user_id, role)| (user_id.clone(), role.as_str().to_string())),
+            )
+            .await?;
+        }
+        Ok(report)
+    }
+
+    /// Members whose custom role does not exist in the project
+    pub async fn invalid_roles(&self) -> Result<Vec<(String, MemberRole)>, SDKError> {
+        let roles = self.custom_role_ids().await?;
+        let mut invalid: Vec<(String, MemberRole)> = self
+            .list()
+            .await?
+            .into_iter()
+            .filter(|(_, role)| check_role(role, &roles, &self.project_id).is_err())
+            .collect();
+        invalid.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(invalid)
+    }
+
+    async fn require_role(&self, role: &MemberRole) -> Result<(), SDKError> {
+        if !role.is_custom() {
+            return Ok(());
+        }
+        check_role(role, &self.custom_role_ids().await?, &self.project_id)
+    }
+
+    async fn custom_role_ids(&self) -> Result<Vec<String>, SDKError> {
+        Ok(ProjectsAPI::new(self.client.clone())
+            .list_roles_all(&self.project_id)
+            .await?
+            .into_iter()
+            .map(|role| role.id)
+            .collect())
+    }
+
+    async fn send(
+        &self,
+        users: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<(), SDKError> {
+        self.client
+            .update_project(
+                &self.project_id,
+                UpdateProject {
+                    users: Some(users.into_iter().collect()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+impl ProjectsAPI {
+    /// Handle for the members of a project
+    pub fn members(&self, project_id: &str) -> ProjectMembers {
+        ProjectMembers {
+            client: self.client.clone(),
+            project_id: project_id.to_string(),
+        }
+    }
+
+    /// Add a user to several projects, continuing past projects that fail
+    pub async fn add_to_projects(
+        &self,
+        user_id: &str,
+        assignments: &[(String, MemberRole)],
+    ) -> Vec<ProjectAssignment> {
+        let mut results = Vec::with_capacity(assignments.len());
+        for (project_id, role) in assignments {
+            let result = self.members(project_id).add(user_id, role.clone()).await;
+            if let Err(error) = &result {
+                log::warn!(
+                    "Adding {} to project {} failed: {}",
+                    user_id,
+                    project_id,
+                    error
+                );
+            }
+            results.push(ProjectAssignment {
+                project_id: project_id.clone(),
+                role: role.clone(),
+                result,
+            });
+        }
+        results
+    }
+}
+
+fn check_role(
+    role: &MemberRole,
+    custom_roles: &[String],
+    project_id: &str,
+) -> Result<(), SDKError> {
+    match role {
+        MemberRole::Custom(id) if !custom_roles.contains(id) => Err(SDKError::validation(format!(
+            "Role {} does not exist in project {}",
+            id, project_id
+        ))),
+        _ => Ok(()),
+    }
+}
@@ -1,4 +1,12 @@
+mod members;
+
+pub use members::{CopyMembers, MemberCopyReport, MemberRole, ProjectAssignment, ProjectMembers};
+
 use crate::SDKError;
+use crate::permissions::SystemRolePresets;
+use crate::reports::PermissionAudit;
+use crate::resources::UsersAPI;
+use std::collections::HashMap;
 use std::sync::Arc;
 use yougile_api_client::YouGileClient;
 use yougile_api_client::models::*;
@@ -49,6 +57,30 @@ impl ProjectsAPI {
         self.search().all().await
     }
 
+    /// Audit who can do what across all projects, see [`PermissionAudit`]
+    ///
+    /// The rights of the system roles are approximated by `system_roles`.
+    pub async fn permission_audit(
+        &self,
+        system_roles: &SystemRolePresets,
+    ) -> Result<PermissionAudit, SDKError> {
+        let projects = self.list_all().await?;
+        let mut roles = HashMap::new();
+        for project in projects
+            .iter()
+            .filter(|project| project.deleted != Some(true))
+        {
+            roles.insert(project.id.clone(), self.list_roles_all(&project.id).await?);
+        }
+        let users = UsersAPI::new(self.client.clone()).search().all().await?;
+        Ok(PermissionAudit::new(
+            &projects,
+            &roles,
+            &users,
+            system_roles,
+        ))
+    }
+
     // Project Role methods
 
     /// Create a project role
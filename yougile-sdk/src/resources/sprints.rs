@@ -0,0 +1,441 @@
+use crate::SDKError;
+use crate::resources::StickersAPI;
+use crate::resources::tasks::{BulkOptions, BulkReport, TaskSearchBuilder, TaskSet, TasksAPI};
+use crate::time::now_ms;
+use std::sync::Arc;
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// A sprint: one state of a sprint sticker
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sprint {
+    pub sticker_id: String,
+    pub sticker_name: String,
+    pub state_id: String,
+    pub name: String,
+    pub begin: Option<f64>,
+    pub end: Option<f64>,
+}
+
+impl Sprint {
+    /// True if `now` lies between the begin and end of the sprint
+    pub fn is_active(&self, now: f64) -> bool {
+        self.begin.is_some_and(|begin| begin <= now) && self.end.is_none_or(|end| now < end)
+    }
+
+    /// True if the sprint has ended before `now`
+    pub fn is_closed(&self, now: f64) -> bool {
+        self.end.is_some_and(|end| end <= now)
+    }
+
+    /// Planned length of the sprint in milliseconds
+    pub fn length(&self) -> Option<f64> {
+        Some(self.end? - self.begin?)
+    }
+}
+
+/// Outcome of rolling a sprint over to the next one
+#[derive(Debug)]
+pub struct SprintRollover {
+    pub closed: Sprint,
+    /// The following sprint; a dry run that would create it only plans it,
+    /// with an empty state ID
+    pub next: Sprint,
+    /// Whether `next` was created by the rollover
+    pub created: bool,
+    /// Unfinished tasks moved to the next sprint, `None` in a dry run when the
+    /// next sprint does not exist yet and there is no state to move them to
+    pub moved: Option<BulkReport>,
+    /// Summary of the closed sprint taken right before its unfinished tasks moved
+    pub summary: SprintSummary,
+}
+
+/// Tasks and time of a single sprint
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SprintSummary {
+    pub completed: Vec<String>,
+    /// Unfinished tasks that carry over to the next sprint
+    pub carried_over: Vec<String>,
+    /// Tasks created after the sprint began
+    ///
+    /// The API does not record when a task joined the sprint, so this goes by
+    /// the creation time of the task; older tasks added later are not listed.
+    pub added_mid_sprint: Vec<String>,
+    pub plan_hours: f64,
+    pub work_hours: f64,
+}
+
+impl SprintSummary {
+    pub fn total(&self) -> usize {
+        self.completed.len() + self.carried_over.len()
+    }
+
+    /// Share of completed tasks in percent
+    pub fn completion(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.completed.len() as f64 * 100.0 / total as f64,
+        }
+    }
+}
+
+/// API for running sprints on top of sprint stickers
+pub struct SprintsAPI {
+    client: Arc<YouGileClient>,
+}
+
+impl SprintsAPI {
+    pub fn new(client: Arc<YouGileClient>) -> Self {
+        Self { client }
+    }
+
+    /// Sprints of every sprint sticker on a board, ordered by begin date
+    pub async fn list(&self, board_id: &str) -> Result<Vec<Sprint>, SDKError> {
+        let stickers = StickersAPI::new(self.client.clone())
+            .list_sprint_by_board(board_id)
+            .await?;
+
+        let mut sprints: Vec<Sprint> = stickers
+            .iter()
+            .filter(|sticker| sticker.data.deleted != Some(true))
+            .flat_map(sprints_of)
+            .collect();
+        sort_by_begin(&mut sprints);
+        Ok(sprints)
+    }
+
+    /// Sprints of one sprint sticker, ordered by begin date
+    pub async fn list_for_sticker(&self, sticker_id: &str) -> Result<Vec<Sprint>, SDKError> {
+        let sticker = StickersAPI::new(self.client.clone())
+            .get_sprint(sticker_id)
+            .await?;
+        Ok(sprints_of(&sticker))
+    }
+
+    /// The running sprint, or the last one that has started
+    pub async fn current(&self, sticker_id: &str) -> Result<Option<Sprint>, SDKError> {
+        let now = now_ms();
+        let sprints = self.list_for_sticker(sticker_id).await?;
+
+        if let Some(active) = sprints.iter().find(|sprint| sprint.is_active(now)) {
+            return Ok(Some(active.clone()));
+        }
+        Ok(sprints
+            .into_iter()
+            .rev()
+            .find(|sprint| sprint.begin.is_some_and(|begin| begin <= now)))
+    }
+
+    /// Create the sprint following the last one
+    ///
+    /// It begins when the last sprint ends (or now) and lasts as long as the
+    /// last sprint. Without a name, the number at the end of the last name is
+    /// incremented, e.g. "Sprint 12" becomes "Sprint 13".
+    pub async fn create_next(
+        &self,
+        sticker_id: &str,
+        name: Option<String>,
+    ) -> Result<Sprint, SDKError> {
+        let sprints = self.list_for_sticker(sticker_id).await?;
+        let mut next = plan_next(sticker_id, &sprints, name);
+
+        let id = StickersAPI::new(self.client.clone())
+            .create_sprint_state(
+                sticker_id,
+                SprintStateData {
+                    name: next.name.clone(),
+                    begin: next.begin,
+                    end: next.end,
+                    deleted: None,
+                },
+            )
+            .await?;
+        next.state_id = id.id;
+
+        Ok(next)
+    }
+
+    /// Move every unfinished task of one sprint to another
+    pub async fn move_unfinished(
+        &self,
+        sticker_id: &str,
+        from_state_id: &str,
+        to_state_id: &str,
+        options: BulkOptions,
+    ) -> Result<BulkReport, SDKError> {
+        let unfinished: Vec<Task> = self
+            .tasks(sticker_id, from_state_id)
+            .await?
+            .into_iter()
+            .filter(|task| task.completed != Some(true))
+            .collect();
+
+        TasksAPI::new(self.client.clone())
+            .bulk_set_sticker(
+                TaskSet::Tasks(unfinished),
+                sticker_id,
+                StickerValue::StateId(to_state_id.to_string()),
+                options,
+            )
+            .await
+    }
+
+    /// End a sprint now unless it has already ended
+    pub async fn close(&self, sticker_id: &str, state_id: &str) -> Result<(), SDKError> {
+        let stickers = StickersAPI::new(self.client.clone());
+        let state = stickers
+            .get_sprint_state(sticker_id, state_id, None)
+            .await?;
+
+        let now = now_ms();
+        if state.data.end.is_some_and(|end| end <= now) {
+            return Ok(());
+        }
+
+        stickers
+            .update_sprint_state(
+                sticker_id,
+                state_id,
+                SprintStateUpdate {
+                    end: Some(now),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Close the current sprint and move its unfinished tasks to the next one,
+    /// creating the next sprint if it does not exist yet
+    pub async fn roll_over(
+        &self,
+        sticker_id: &str,
+        options: BulkOptions,
+    ) -> Result<SprintRollover, SDKError> {
+        let current = self
+            .current(sticker_id)
+            .await?
+            .ok_or_else(|| SDKError::not_found(format!("running sprint of {}", sticker_id)))?;
+
+        let sprints = self.list_for_sticker(sticker_id).await?;
+        let following = sprints
+            .iter()
+            .skip_while(|sprint| sprint.state_id != current.state_id)
+            .nth(1)
+            .cloned();
+
+        let summary = self.summary(sticker_id, &current.state_id).await?;
+
+        let dry_run = options.is_dry_run();
+        let (next, created) = match following {
+            Some(next) => (next, false),
+            // A dry run plans the next sprint without creating it, its state ID stays empty
+            None if dry_run => (plan_next(sticker_id, &sprints, None), false),
+            None => (self.create_next(sticker_id, None).await?, true),
+        };
+
+        let moved = if next.state_id.is_empty() {
+            None
+        } else {
+            Some(
+                self.move_unfinished(sticker_id, &current.state_id, &next.state_id, options)
+                    .await?,
+            )
+        };
+        if !dry_run {
+            self.close(sticker_id, &current.state_id).await?;
+        }
+
+        Ok(SprintRollover {
+            closed: current,
+            next,
+            created,
+            moved,
+            summary,
+        })
+    }
+
+    /// Completed, carried over and added tasks of a sprint with their time
+    ///
+    /// Unfinished tasks count as carried over. After [`SprintsAPI::roll_over`]
+    /// they belong to the next sprint, so use [`SprintRollover::summary`] for
+    /// the closed sprint instead.
+    pub async fn summary(
+        &self,
+        sticker_id: &str,
+        state_id: &str,
+    ) -> Result<SprintSummary, SDKError> {
+        let state = StickersAPI::new(self.client.clone())
+            .get_sprint_state(sticker_id, state_id, None)
+            .await?;
+        let tasks = self.tasks(sticker_id, state_id).await?;
+
+        let mut summary = SprintSummary::default();
+        for task in tasks {
+            if let Some(time) = &task.time_tracking {
+                summary.plan_hours += time.plan;
+                summary.work_hours += time.work;
+            }
+            if state.data.begin.is_some_and(|begin| task.timestamp > begin) {
+                summary.added_mid_sprint.push(task.id.clone());
+            }
+            if task.completed == Some(true) {
+                summary.completed.push(task.id);
+            } else {
+                summary.carried_over.push(task.id);
+            }
+        }
+
+        Ok(summary)
+    }
+
+    async fn tasks(&self, sticker_id: &str, state_id: &str) -> Result<Vec<Task>, SDKError> {
+        TaskSearchBuilder::new(self.client.clone())
+            .sticker_id(sticker_id)
+            .sticker_state_id(state_id)
+            .all()
+            .await
+    }
+}
+
+/// The sprint that would follow the last one, without a state ID
+fn plan_next(sticker_id: &str, sprints: &[Sprint], name: Option<String>) -> Sprint {
+    let last = sprints.last();
+    let begin = last.and_then(|sprint| sprint.end).unwrap_or_else(now_ms);
+
+    Sprint {
+        sticker_id: sticker_id.to_string(),
+        sticker_name: last
+            .map(|sprint| sprint.sticker_name.clone())
+            .unwrap_or_default(),
+        state_id: String::new(),
+        name: name.unwrap_or_else(|| match last {
+            Some(sprint) => next_name(&sprint.name),
+            None => "Sprint 1".to_string(),
+        }),
+        begin: Some(begin),
+        end: last.and_then(Sprint::length).map(|length| begin + length),
+    }
+}
+
+fn sprints_of(sticker: &SprintSticker) -> Vec<Sprint> {
+    let mut sprints: Vec<Sprint> = sticker
+        .states
+        .iter()
+        .flatten()
+        .filter(|state| state.data.deleted != Some(true))
+        .map(|state| Sprint {
+            sticker_id: sticker.id.clone(),
+            sticker_name: sticker.data.name.clone(),
+            state_id: state.id.clone(),
+            name: state.data.name.clone(),
+            begin: state.data.begin,
+            end: state.data.end,
+        })
+        .collect();
+    sort_by_begin(&mut sprints);
+    sprints
+}
+
+/// Order sprints by begin date; sprints without dates keep their order after
+/// the dated ones
+fn sort_by_begin(sprints: &mut [Sprint]) {
+    sprints.sort_by(|a, b| match (a.begin, b.begin) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+}
+
+/// Increment the number at the end of a sprint name
+fn next_name(name: &str) -> String {
+    let prefix = name.trim_end_matches(|ch: char| ch.is_ascii_digit());
+    match name[prefix.len()..].parse::<u64>() {
+        Ok(number) => format!("{}{}", prefix, number + 1),
+        Err(_) => format!("{} 2", name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(id: &str, begin: Option<f64>, end: Option<f64>) -> SprintStickerState {
+        SprintStickerState {
+            id: id.to_string(),
+            data: SprintStateData {
+                begin,
+                end,
+                ..SprintStateData::new(format!("Sprint {}", id))
+            },
+        }
+    }
+
+    fn sticker(states: Vec<SprintStickerState>) -> SprintSticker {
+        SprintSticker {
+            states: Some(states),
+            ..SprintSticker::new("sticker".to_string(), "Sprints".to_string())
+        }
+    }
+
+    #[test]
+    fn next_name_increments_the_trailing_number() {
+        assert_eq!(next_name("Sprint 12"), "Sprint 13");
+        assert_eq!(next_name("S9"), "S10");
+        assert_eq!(next_name("Sprint"), "Sprint 2");
+        assert_eq!(next_name("2024 Q1 sprint"), "2024 Q1 sprint 2");
+    }
+
+    #[test]
+    fn sprints_are_ordered_with_undated_ones_last() {
+        let mut deleted = state("4", Some(0.0), None);
+        deleted.data.deleted = Some(true);
+        let sprints = sprints_of(&sticker(vec![
+            state("3", None, None),
+            state("2", Some(200.0), Some(300.0)),
+            deleted,
+            state("5", None, None),
+            state("1", Some(100.0), Some(200.0)),
+        ]));
+
+        let ids: Vec<&str> = sprints
+            .iter()
+            .map(|sprint| sprint.state_id.as_str())
+            .collect();
+        assert_eq!(ids, ["1", "2", "3", "5"]);
+        assert_eq!(sprints[0].sticker_name, "Sprints");
+    }
+
+    #[test]
+    fn plan_next_continues_after_the_last_sprint() {
+        let sprints = sprints_of(&sticker(vec![
+            state("11", Some(100.0), Some(200.0)),
+            state("12", Some(200.0), Some(350.0)),
+        ]));
+
+        let next = plan_next("sticker", &sprints, None);
+        assert_eq!(next.name, "Sprint 13");
+        assert_eq!(next.begin, Some(350.0));
+        assert_eq!(next.end, Some(500.0));
+        assert!(next.state_id.is_empty());
+
+        let named = plan_next("sticker", &sprints, Some("Release".to_string()));
+        assert_eq!(named.name, "Release");
+    }
+
+    #[test]
+    fn plan_next_without_dates_begins_now() {
+        let before = now_ms();
+        let undated = sprints_of(&sticker(vec![state("1", None, None)]));
+
+        let next = plan_next("sticker", &undated, None);
+        assert_eq!(next.name, "Sprint 2");
+        assert!(next.begin.is_some_and(|begin| begin >= before));
+        assert_eq!(next.end, None);
+
+        let first = plan_next("sticker", &[], None);
+        assert_eq!(first.name, "Sprint 1");
+        assert_eq!(first.sticker_name, "");
+    }
+}
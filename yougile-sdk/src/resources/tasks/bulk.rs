@@ -0,0 +1,639 @@
+use super::{TaskSearchBuilder, TasksAPI};
+use crate::SDKError;
+use crate::throttle::Throttle;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// Tasks targeted by a bulk operation
+#[derive(Clone)]
+pub enum TaskSet {
+    /// Explicit task IDs; each task is fetched to check its current state
+    Ids(Vec<String>),
+    /// Every task matching a search, fetched with automatic pagination
+    Search(TaskSearchBuilder),
+    /// Tasks that were already loaded, e.g. by a query
+    Tasks(Vec<Task>),
+}
+
+impl From<Vec<String>> for TaskSet {
+    fn from(ids: Vec<String>) -> Self {
+        TaskSet::Ids(ids)
+    }
+}
+
+impl From<&[&str]> for TaskSet {
+    fn from(ids: &[&str]) -> Self {
+        TaskSet::Ids(ids.iter().map(|id| id.to_string()).collect())
+    }
+}
+
+impl From<Vec<Task>> for TaskSet {
+    fn from(tasks: Vec<Task>) -> Self {
+        TaskSet::Tasks(tasks)
+    }
+}
+
+impl From<TaskSearchBuilder> for TaskSet {
+    fn from(search: TaskSearchBuilder) -> Self {
+        TaskSet::Search(search)
+    }
+}
+
+/// Settings for bulk task operations
+#[derive(Clone, Debug)]
+pub struct BulkOptions {
+    concurrency: usize,
+    rate_limit: bool,
+    dry_run: bool,
+}
+
+impl BulkOptions {
+    pub fn new() -> Self {
+        Self {
+            concurrency: 4,
+            rate_limit: true,
+            dry_run: false,
+        }
+    }
+
+    /// Maximum number of requests in flight at once
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Keep to the company rate limit of 50 requests per minute, on by default
+    ///
+    /// The limit is shared with the other throttled operations using the same API
+    /// key: bulk operations, subtask trees and dependency walks. Other SDK calls
+    /// are not counted against it.
+    pub fn rate_limit(mut self, rate_limit: bool) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Only log the planned changes without sending any updates
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
+
+impl Default for BulkOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Result of a bulk operation for a single task
+#[derive(Debug)]
+pub enum BulkOutcome {
+    /// The update was sent successfully
+    Updated,
+    /// The task was already in the target state
+    Skipped,
+    /// Dry run: the update that would have been sent
+    Planned(Box<UpdateTask>),
+    /// Fetching or updating the task failed
+    Failed(SDKError),
+}
+
+#[derive(Debug)]
+pub struct BulkEntry {
+    pub task_id: String,
+    pub title: Option<String>,
+    pub outcome: BulkOutcome,
+}
+
+/// Per-task report of a bulk operation
+#[derive(Debug, Default)]
+pub struct BulkReport {
+    pub dry_run: bool,
+    pub entries: Vec<BulkEntry>,
+}
+
+impl BulkReport {
+    pub fn updated(&self) -> impl Iterator<Item = &BulkEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.outcome, BulkOutcome::Updated))
+    }
+
+    pub fn skipped(&self) -> impl Iterator<Item = &BulkEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.outcome, BulkOutcome::Skipped))
+    }
+
+    pub fn planned(&self) -> impl Iterator<Item = &BulkEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.outcome, BulkOutcome::Planned(_)))
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &BulkEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.outcome, BulkOutcome::Failed(_)))
+    }
+
+    /// True if no task failed
+    pub fn is_success(&self) -> bool {
+        self.failed().next().is_none()
+    }
+}
+
+impl fmt::Display for BulkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            let title = entry.title.as_deref().unwrap_or("?");
+            match &entry.outcome {
+                BulkOutcome::Updated => writeln!(f, "updated  {} {}", entry.task_id, title)?,
+                BulkOutcome::Skipped => writeln!(f, "skipped  {} {}", entry.task_id, title)?,
+                BulkOutcome::Planned(update) => writeln!(
+                    f,
+                    "planned  {} {}: {}",
+                    entry.task_id,
+                    title,
+                    serde_json::to_string(update).unwrap_or_default()
+                )?,
+                BulkOutcome::Failed(error) => {
+                    writeln!(f, "failed   {} {}: {}", entry.task_id, title, error)?
+                }
+            }
+        }
+
+        write!(
+            f,
+            "{} updated, {} skipped, {} planned, {} failed",
+            self.updated().count(),
+            self.skipped().count(),
+            self.planned().count(),
+            self.failed().count()
+        )
+    }
+}
+
+impl TasksAPI {
+    /// Apply the same update to every task of the set
+    ///
+    /// Tasks whose fields already match the update are skipped.
+    pub async fn bulk_update(
+        &self,
+        tasks: impl Into<TaskSet>,
+        update: UpdateTask,
+        options: BulkOptions,
+    ) -> Result<BulkReport, SDKError> {
+        self.run_bulk(tasks.into(), options, |task| {
+            (!update_is_noop(task, &update)).then(|| update.clone())
+        })
+        .await
+    }
+
+    /// Move every task of the set to the archive
+    pub async fn bulk_archive(
+        &self,
+        tasks: impl Into<TaskSet>,
+        options: BulkOptions,
+    ) -> Result<BulkReport, SDKError> {
+        self.run_bulk(tasks.into(), options, |task| {
+            (task.archived != Some(true)).then(|| UpdateTask {
+                archived: Some(true),
+                ..Default::default()
+            })
+        })
+        .await
+    }
+
+    /// Mark every task of the set as completed
+    pub async fn bulk_complete(
+        &self,
+        tasks: impl Into<TaskSet>,
+        options: BulkOptions,
+    ) -> Result<BulkReport, SDKError> {
+        self.run_bulk(tasks.into(), options, |task| {
+            (task.completed != Some(true)).then(|| UpdateTask {
+                completed: Some(true),
+                ..Default::default()
+            })
+        })
+        .await
+    }
+
+    /// Add a user to the assignees of every task of the set, keeping existing assignees
+    pub async fn bulk_assign(
+        &self,
+        tasks: impl Into<TaskSet>,
+        user_id: &str,
+        options: BulkOptions,
+    ) -> Result<BulkReport, SDKError> {
+        self.run_bulk(tasks.into(), options, |task| {
+            let mut assigned = task.assigned.clone().unwrap_or_default();
+            if assigned.iter().any(|id| id == user_id) {
+                return None;
+            }
+            assigned.push(user_id.to_string());
+            Some(UpdateTask {
+                assigned: Some(assigned),
+                ..Default::default()
+            })
+        })
+        .await
+    }
+
+    /// Set a sticker value on every task of the set
+    pub async fn bulk_set_sticker(
+        &self,
+        tasks: impl Into<TaskSet>,
+        sticker_id: &str,
+        value: StickerValue,
+        options: BulkOptions,
+    ) -> Result<BulkReport, SDKError> {
+        self.run_bulk(tasks.into(), options, |task| {
+            let current = task
+                .stickers
+                .as_ref()
+                .and_then(|stickers| stickers.get(sticker_id));
+            (current != Some(&value)).then(|| UpdateTask {
+                stickers: Some(TaskStickers::from([(
+                    sticker_id.to_string(),
+                    value.clone(),
+                )])),
+                ..Default::default()
+            })
+        })
+        .await
+    }
+
+    async fn run_bulk<F>(
+        &self,
+        tasks: TaskSet,
+        options: BulkOptions,
+        plan: F,
+    ) -> Result<BulkReport, SDKError>
+    where
+        F: Fn(&Task) -> Option<UpdateTask>,
+    {
+        let throttle = Arc::new(if options.rate_limit {
+            Throttle::new(&self.client, options.concurrency)
+        } else {
+            Throttle::unlimited(options.concurrency)
+        });
+
+        let mut report = BulkReport {
+            dry_run: options.dry_run,
+            entries: Vec::new(),
+        };
+
+        let loaded = match tasks {
+            TaskSet::Search(search) => search.all().await?.into_iter().map(Ok).collect(),
+            TaskSet::Ids(ids) => fetch_tasks(&self.client, &throttle, ids).await,
+            TaskSet::Tasks(tasks) => tasks.into_iter().map(Ok).collect(),
+        };
+
+        let mut pending = JoinSet::new();
+        // Spawned updates by tokio task, to report those that panic or are aborted
+        let mut running = HashMap::new();
+
+        for (index, loaded) in loaded.into_iter().enumerate() {
+            let task = match loaded {
+                Ok(task) => task,
+                Err((task_id, error)) => {
+                    report.entries.push(BulkEntry {
+                        task_id,
+                        title: None,
+                        outcome: BulkOutcome::Failed(error),
+                    });
+                    continue;
+                }
+            };
+
+            let Some(update) = plan(&task) else {
+                report.entries.push(BulkEntry {
+                    task_id: task.id,
+                    title: Some(task.title),
+                    outcome: BulkOutcome::Skipped,
+                });
+                continue;
+            };
+
+            if options.dry_run {
+                log::info!(
+                    "[dry run] {} {}: {}",
+                    task.id,
+                    task.title,
+                    serde_json::to_string(&update).unwrap_or_default()
+                );
+                report.entries.push(BulkEntry {
+                    task_id: task.id,
+                    title: Some(task.title),
+                    outcome: BulkOutcome::Planned(Box::new(update)),
+                });
+                continue;
+            }
+
+            let client = self.client.clone();
+            let throttle = throttle.clone();
+            let spawned = (index, task.id.clone(), task.title.clone());
+            let handle = pending.spawn(async move {
+                let result = {
+                    let _permit = throttle.acquire().await;
+                    client.update_task(&task.id, update).await
+                };
+                let outcome = match result {
+                    Ok(_) => BulkOutcome::Updated,
+                    Err(error) => BulkOutcome::Failed(error.into()),
+                };
+                (
+                    index,
+                    BulkEntry {
+                        task_id: task.id,
+                        title: Some(task.title),
+                        outcome,
+                    },
+                )
+            });
+            running.insert(handle.id(), spawned);
+        }
+
+        let mut finished = Vec::new();
+        while let Some(joined) = pending.join_next().await {
+            let (index, entry) = match joined {
+                Ok(finished) => finished,
+                Err(error) => {
+                    let Some((index, task_id, title)) = running.remove(&error.id()) else {
+                        log::error!("Bulk update was aborted: {}", error);
+                        continue;
+                    };
+                    let outcome = BulkOutcome::Failed(SDKError::Other(format!(
+                        "update was aborted: {}",
+                        error
+                    )));
+                    (
+                        index,
+                        BulkEntry {
+                            task_id,
+                            title: Some(title),
+                            outcome,
+                        },
+                    )
+                }
+            };
+            if let BulkOutcome::Failed(error) = &entry.outcome {
+                log::warn!("Bulk update of task {} failed: {}", entry.task_id, error);
+            }
+            finished.push((index, entry));
+        }
+        finished.sort_by_key(|(index, _)| *index);
+        report
+            .entries
+            .extend(finished.into_iter().map(|(_, entry)| entry));
+
+        Ok(report)
+    }
+}
+
+impl TasksAPI {
+    /// Load every task of the set, failing on the first task that cannot be fetched
+    pub(crate) async fn load(&self, tasks: TaskSet) -> Result<Vec<Task>, SDKError> {
+        match tasks {
+            TaskSet::Search(search) => search.all().await,
+            TaskSet::Tasks(tasks) => Ok(tasks),
+            TaskSet::Ids(ids) => {
+                let throttle = Arc::new(Throttle::new(&self.client, 4));
+                fetch_tasks(&self.client, &throttle, ids)
+                    .await
+                    .into_iter()
+                    .map(|result| result.map_err(|(_, error)| error))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Fetch tasks concurrently, keeping the input order and per-task errors
+pub(super) async fn fetch_tasks(
+    client: &Arc<YouGileClient>,
+    throttle: &Arc<Throttle>,
+    ids: Vec<String>,
+) -> Vec<Result<Task, (String, SDKError)>> {
+    let mut pending = JoinSet::new();
+    let mut running = HashMap::new();
+
+    for (index, id) in ids.into_iter().enumerate() {
+        let client = client.clone();
+        let throttle = throttle.clone();
+        let spawned = (index, id.clone());
+        let handle = pending.spawn(async move {
+            let _permit = throttle.acquire().await;
+            let result = client
+                .get_task(&id)
+                .await
+                .map_err(|error| (id, SDKError::from(error)));
+            (index, result)
+        });
+        running.insert(handle.id(), spawned);
+    }
+
+    let mut fetched = Vec::new();
+    while let Some(joined) = pending.join_next().await {
+        match joined {
+            Ok(result) => fetched.push(result),
+            Err(error) => match running.remove(&error.id()) {
+                Some((index, id)) => {
+                    let error = SDKError::Other(format!("fetch was aborted: {}", error));
+                    fetched.push((index, Err((id, error))));
+                }
+                None => log::error!("Task fetch was aborted: {}", error),
+            },
+        }
+    }
+    fetched.sort_by_key(|(index, _)| *index);
+    fetched.into_iter().map(|(_, result)| result).collect()
+}
+
+/// True if every field set in `update` already has that value on the task
+fn update_is_noop(task: &Task, update: &UpdateTask) -> bool {
+    fn same<T: PartialEq>(wanted: &Option<T>, current: Option<&T>) -> bool {
+        wanted.as_ref().is_none_or(|wanted| Some(wanted) == current)
+    }
+
+    let stickers_match = update.stickers.as_ref().is_none_or(|wanted| {
+        wanted.iter().all(|(id, value)| {
+            let current = task.stickers.as_ref().and_then(|stickers| stickers.get(id));
+            match current {
+                Some(current) => sticker_raw(current) == sticker_raw(value),
+                None => sticker_raw(value) == UNSET,
+            }
+        })
+    });
+
+    update.deadline.is_none()
+        && update.time_tracking.is_none()
+        && update.timer.is_none()
+        && update.stopwatch.is_none()
+        && update.subtasks.is_none()
+        && update.checklists.is_none()
+        && stickers_match
+        && same(&update.deleted, task.deleted.as_ref())
+        && same(&update.title, Some(&task.title))
+        && same(&update.column_id, task.column_id.as_ref())
+        && same(&update.description, task.description.as_ref())
+        && same(&update.archived, task.archived.as_ref())
+        && same(&update.completed, task.completed.as_ref())
+        && same(&update.assigned, task.assigned.as_ref())
+        && same(&update.color, task.color.as_ref())
+        && same(&update.id_task_common, task.id_task_common.as_ref())
+        && same(&update.id_task_project, task.id_task_project.as_ref())
+}
+
+/// A sticker value as the API sends it, whichever variant it was parsed into
+fn sticker_raw(value: &StickerValue) -> String {
+    match value {
+        StickerValue::StateId(raw) | StickerValue::Text(raw) => raw.clone(),
+        StickerValue::Number(number) => number.to_string(),
+        StickerValue::Empty => "empty".to_string(),
+        StickerValue::Unassigned => UNSET.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task() -> Task {
+        Task {
+            id: "task-1".to_string(),
+            title: "Write docs".to_string(),
+            completed: Some(false),
+            id_task_project: Some("DOC-1".to_string()),
+            stickers: Some(TaskStickers::from([
+                ("size".to_string(), StickerValue::StateId("xl".to_string())),
+                ("points".to_string(), StickerValue::StateId("3".to_string())),
+            ])),
+            ..Default::default()
+        }
+    }
+
+    fn stickers(values: &[(&str, StickerValue)]) -> UpdateTask {
+        UpdateTask {
+            stickers: Some(
+                values
+                    .iter()
+                    .map(|(id, value)| (id.to_string(), value.clone()))
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unchanged_fields_are_a_noop() {
+        let task = task();
+
+        assert!(update_is_noop(&task, &UpdateTask::default()));
+        assert!(update_is_noop(
+            &task,
+            &UpdateTask {
+                completed: Some(false),
+                title: Some("Write docs".to_string()),
+                ..Default::default()
+            }
+        ));
+        assert!(!update_is_noop(
+            &task,
+            &UpdateTask {
+                completed: Some(true),
+                ..Default::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn task_codes_are_compared() {
+        let task = task();
+
+        assert!(update_is_noop(
+            &task,
+            &UpdateTask {
+                id_task_project: Some("DOC-1".to_string()),
+                ..Default::default()
+            }
+        ));
+        assert!(!update_is_noop(
+            &task,
+            &UpdateTask {
+                id_task_project: Some("DOC-2".to_string()),
+                ..Default::default()
+            }
+        ));
+        assert!(!update_is_noop(
+            &task,
+            &UpdateTask {
+                id_task_common: Some("ID-7".to_string()),
+                ..Default::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn stickers_are_compared_by_raw_value() {
+        let task = task();
+
+        assert!(update_is_noop(
+            &task,
+            &stickers(&[("size", StickerValue::Text("xl".to_string()))])
+        ));
+        assert!(update_is_noop(
+            &task,
+            &stickers(&[("points", StickerValue::Number(3.0))])
+        ));
+        assert!(update_is_noop(
+            &task,
+            &stickers(&[("owner", StickerValue::StateId(UNSET.to_string()))])
+        ));
+        assert!(!update_is_noop(
+            &task,
+            &stickers(&[("size", StickerValue::StateId("s".to_string()))])
+        ));
+        assert!(!update_is_noop(
+            &task,
+            &stickers(&[("size", StickerValue::Unassigned)])
+        ));
+    }
+
+    #[test]
+    fn report_counts_outcomes() {
+        let entry = |task_id: &str, outcome| BulkEntry {
+            task_id: task_id.to_string(),
+            title: None,
+            outcome,
+        };
+        let report = BulkReport {
+            dry_run: false,
+            entries: vec![
+                entry("a", BulkOutcome::Updated),
+                entry("b", BulkOutcome::Skipped),
+                entry("c", BulkOutcome::Failed(SDKError::validation("rejected"))),
+            ],
+        };
+
+        assert!(!report.is_success());
+        assert_eq!(report.updated().count(), 1);
+        assert_eq!(report.failed().next().unwrap().task_id, "c");
+        assert!(
+            report
+                .to_string()
+                .ends_with("1 updated, 1 skipped, 0 planned, 1 failed")
+        );
+    }
+}
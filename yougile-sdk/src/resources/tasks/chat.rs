@@ -0,0 +1,338 @@
+use super::TasksAPI;
+use crate::SDKError;
+use crate::markup::{html_to_text, markdown_to_html, text_to_html};
+use crate::resources::ChatsAPI;
+use crate::time::now_ms;
+use futures_core::Stream;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+use std::time::Duration;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// Default pause between polls of [`ChatFollower`]
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Request for messages newer than the last one a follower has seen
+type PendingFetch = Pin<Box<dyn Future<Output = Result<Vec<ChatMessage>, SDKError>> + Send>>;
+
+/// Handle for the chat of a single task
+///
+/// The chat of a task uses the task ID as its chat ID. Messages are identified
+/// by their `id`, which is also the time they were posted in milliseconds.
+#[derive(Clone)]
+pub struct TaskChat {
+    client: Arc<YouGileClient>,
+    task_id: String,
+}
+
+impl TaskChat {
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    /// Every message of the chat, oldest first
+    pub async fn history(&self) -> Result<Vec<ChatMessage>, SDKError> {
+        let mut messages = self.chats().list_messages_all(&self.task_id).await?;
+        sort_by_id(&mut messages);
+        Ok(messages)
+    }
+
+    /// The last `count` messages of the chat, oldest first
+    pub async fn tail(&self, count: usize) -> Result<Vec<ChatMessage>, SDKError> {
+        let mut messages = self.history().await?;
+        let skip = messages.len().saturating_sub(count);
+        Ok(messages.split_off(skip))
+    }
+
+    /// Messages posted after `since`, oldest first
+    pub async fn since(&self, since: f64) -> Result<Vec<ChatMessage>, SDKError> {
+        let mut messages: Vec<ChatMessage> = self
+            .chats()
+            .search(&self.task_id)
+            .since(since)
+            .all()
+            .await?
+            .into_iter()
+            .filter(|message| message.id > since)
+            .collect();
+        sort_by_id(&mut messages);
+        Ok(messages)
+    }
+
+    pub async fn get(&self, message_id: f64) -> Result<ChatMessage, SDKError> {
+        self.chats().get_message(&self.task_id, message_id).await
+    }
+
+    /// Post a plain text message, returning the ID of the new message
+    pub async fn post(&self, text: &str) -> Result<f64, SDKError> {
+        self.send(CreateChatMessage {
+            text: text.to_string(),
+            text_html: text_to_html(text),
+            label: String::new(),
+        })
+        .await
+    }
+
+    /// Post a Markdown message, returning its ID
+    pub async fn post_markdown(&self, markdown: &str) -> Result<f64, SDKError> {
+        let html = markdown_to_html(markdown);
+        self.send(CreateChatMessage {
+            text: html_to_text(&html),
+            text_html: html,
+            label: String::new(),
+        })
+        .await
+    }
+
+    /// Post a message with explicit HTML and label, returning its ID
+    pub async fn send(&self, message: CreateChatMessage) -> Result<f64, SDKError> {
+        Ok(self.chats().send_message(&self.task_id, message).await?.id)
+    }
+
+    /// Update a message
+    ///
+    /// The API can change the label, reactions and deleted flag of a message,
+    /// but not its text.
+    pub async fn edit(&self, message_id: f64, update: UpdateChatMessage) -> Result<(), SDKError> {
+        self.chats()
+            .update_message(&self.task_id, message_id, update)
+            .await?;
+        Ok(())
+    }
+
+    /// Set the label of a message
+    pub async fn set_label(&self, message_id: f64, label: &str) -> Result<(), SDKError> {
+        self.edit(
+            message_id,
+            UpdateChatMessage {
+                label: Some(label.to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Add a reaction of the API key's user, see [`ChatsAPI::react`]
+    pub async fn react(
+        &self,
+        message_id: f64,
+        user_id: &str,
+        react: React,
+    ) -> Result<(), SDKError> {
+        self.chats()
+            .react(&self.task_id, message_id, user_id, react)
+            .await
+    }
+
+    pub async fn unreact(
+        &self,
+        message_id: f64,
+        user_id: &str,
+        react: &React,
+    ) -> Result<(), SDKError> {
+        self.chats()
+            .unreact(&self.task_id, message_id, user_id, react)
+            .await
+    }
+
+    pub async fn delete(&self, message_id: f64) -> Result<(), SDKError> {
+        self.edit(
+            message_id,
+            UpdateChatMessage {
+                deleted: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// IDs of the users subscribed to the chat
+    pub async fn subscribers(&self) -> Result<Vec<String>, SDKError> {
+        self.client
+            .get_task_chat_subscribers(&self.task_id)
+            .await
+            .map_err(SDKError::from)
+    }
+
+    /// Subscribe users to the chat, keeping the current subscribers
+    pub async fn subscribe(&self, user_ids: &[String]) -> Result<(), SDKError> {
+        let mut subscribers = self.subscribers().await?;
+        for user_id in user_ids {
+            if !subscribers.contains(user_id) {
+                subscribers.push(user_id.clone());
+            }
+        }
+        self.set_subscribers(subscribers).await
+    }
+
+    pub async fn unsubscribe(&self, user_ids: &[String]) -> Result<(), SDKError> {
+        let mut subscribers = self.subscribers().await?;
+        subscribers.retain(|user_id| !user_ids.contains(user_id));
+        self.set_subscribers(subscribers).await
+    }
+
+    /// Wait for messages posted from now on
+    pub fn follow(&self) -> ChatFollower {
+        self.follow_since(now_ms())
+    }
+
+    /// Wait for messages posted after `since`, starting with the ones already there
+    pub fn follow_since(&self, since: f64) -> ChatFollower {
+        ChatFollower {
+            chat: self.clone(),
+            last_id: since,
+            interval: FOLLOW_INTERVAL,
+            pending: VecDeque::new(),
+            fetch: None,
+            sleep: None,
+        }
+    }
+
+    async fn set_subscribers(&self, subscribers: Vec<String>) -> Result<(), SDKError> {
+        self.client
+            .update_task_chat_subscribers(
+                &self.task_id,
+                TaskChatSubscribers {
+                    content: Some(subscribers),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn chats(&self) -> ChatsAPI {
+        ChatsAPI::new(self.client.clone())
+    }
+}
+
+/// A stream that polls a task chat and yields each new message once
+///
+/// The stream never ends on its own; a failed poll yields the error and the
+/// next poll retries from the last message seen.
+///
+/// ```rust,no_run
+/// use futures_util::StreamExt;
+///
+/// # async fn example(sdk: yougile_sdk::YouGileSDK) -> Result<(), yougile_sdk::SDKError> {
+/// let mut follower = sdk.tasks().chat("task-id").follow();
+/// while let Some(message) = follower.next().await {
+///     let message = message?;
+///     log::info!("{}: {}", message.from_user_id, message.text);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ChatFollower {
+    chat: TaskChat,
+    last_id: f64,
+    interval: Duration,
+    pending: VecDeque<ChatMessage>,
+    fetch: Option<PendingFetch>,
+    /// Pause after a poll without new messages
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl ChatFollower {
+    /// Pause between polls, five seconds by default
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// ID of the last message seen, usable to resume with [`TaskChat::follow_since`]
+    pub fn last_id(&self) -> f64 {
+        self.last_id
+    }
+}
+
+impl Stream for ChatFollower {
+    type Item = Result<ChatMessage, SDKError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(message) = this.pending.pop_front() {
+                return Poll::Ready(Some(Ok(message)));
+            }
+            if let Some(sleep) = &mut this.sleep {
+                ready!(sleep.as_mut().poll(cx));
+                this.sleep = None;
+            }
+
+            let fetch = this.fetch.get_or_insert_with(|| {
+                let chat = this.chat.clone();
+                let since = this.last_id;
+                Box::pin(async move { chat.since(since).await })
+            });
+            let messages = ready!(fetch.as_mut().poll(cx));
+            this.fetch = None;
+
+            match messages {
+                Ok(messages) => {
+                    if let Some(last) = messages.last() {
+                        this.last_id = last.id;
+                    }
+                    if messages.is_empty() {
+                        this.sleep = Some(Box::pin(tokio::time::sleep(this.interval)));
+                    }
+                    this.pending.extend(messages);
+                }
+                Err(error) => {
+                    this.sleep = Some(Box::pin(tokio::time::sleep(this.interval)));
+                    return Poll::Ready(Some(Err(error)));
+                }
+            }
+        }
+    }
+}
+
+impl TasksAPI {
+    /// Handle for the chat of a task
+    pub fn chat(&self, task_id: &str) -> TaskChat {
+        TaskChat {
+            client: self.client.clone(),
+            task_id: task_id.to_string(),
+        }
+    }
+}
+
+fn sort_by_id(messages: &mut [ChatMessage]) {
+    messages.sort_by(|a, b| a.id.total_cmp(&b.id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn follower_yields_poll_errors_and_keeps_its_position() {
+        let client = Arc::new(YouGileClient::new(
+            yougile_api_client::apis::configuration::Configuration::new("token".to_string())
+                .with_base_path("http://127.0.0.1:9".to_string()),
+        ));
+        let mut follower = TasksAPI::new(client)
+            .chat("task-id")
+            .follow_since(42.0)
+            .interval(Duration::from_millis(1));
+
+        assert!(
+            follower
+                .next()
+                .await
+                .is_some_and(|message| message.is_err())
+        );
+        assert!(
+            follower
+                .next()
+                .await
+                .is_some_and(|message| message.is_err())
+        );
+        assert_eq!(follower.last_id(), 42.0);
+    }
+}
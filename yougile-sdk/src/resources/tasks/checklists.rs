@@ -0,0 +1,344 @@
+use super::TasksAPI;
+use crate::SDKError;
+use std::fmt;
+use std::sync::Arc;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// Completed and total checklist items
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChecklistProgress {
+    pub completed: usize,
+    pub total: usize,
+}
+
+impl ChecklistProgress {
+    /// Progress of a single checklist
+    pub fn of_list(checklist: &CheckList) -> Self {
+        Self {
+            completed: checklist
+                .items
+                .iter()
+                .filter(|item| item.is_completed)
+                .count(),
+            total: checklist.items.len(),
+        }
+    }
+
+    /// Progress over several checklists
+    pub fn of_lists<'a>(checklists: impl IntoIterator<Item = &'a CheckList>) -> Self {
+        checklists
+            .into_iter()
+            .map(Self::of_list)
+            .fold(Self::default(), |sum, progress| Self {
+                completed: sum.completed + progress.completed,
+                total: sum.total + progress.total,
+            })
+    }
+
+    /// Progress over all checklists of a task
+    pub fn of_task(task: &Task) -> Self {
+        Self::of_lists(task.checklists.iter().flatten())
+    }
+
+    /// Percentage of completed items, `None` if there are no items
+    pub fn percent(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.completed as f64 * 100.0 / self.total as f64)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.total > 0 && self.completed == self.total
+    }
+}
+
+impl fmt::Display for ChecklistProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.completed, self.total)
+    }
+}
+
+/// Handle for editing the checklists of a single task
+///
+/// The API only accepts the whole checklist array, so every change is a
+/// read-modify-write. Right before writing, the task is fetched again; if its
+/// checklists differ from what this handle last saw, the change is re-applied
+/// on top of the fresh copy, locating lists and items by title. Toggling an
+/// item whose state changed in the meantime fails instead.
+pub struct Checklists {
+    client: Arc<YouGileClient>,
+    task_id: String,
+    seen: Option<Vec<CheckList>>,
+}
+
+/// A list or item addressed by position, with the title seen at that position
+#[derive(Clone)]
+struct Anchor {
+    index: usize,
+    title: String,
+}
+
+impl Anchor {
+    /// Position of the anchor in `titles`, preferring the original index
+    fn locate<'a>(&self, mut titles: impl Iterator<Item = &'a str> + Clone) -> Option<usize> {
+        if titles.clone().nth(self.index) == Some(self.title.as_str()) {
+            return Some(self.index);
+        }
+        titles.position(|title| title == self.title)
+    }
+}
+
+impl Checklists {
+    pub(crate) fn new(client: Arc<YouGileClient>, task_id: impl Into<String>) -> Self {
+        Self {
+            client,
+            task_id: task_id.into(),
+            seen: None,
+        }
+    }
+
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    /// Current checklists, loaded on first use
+    pub async fn lists(&mut self) -> Result<&[CheckList], SDKError> {
+        if self.seen.is_none() {
+            self.refresh().await?;
+        }
+        Ok(self.seen.as_deref().unwrap_or_default())
+    }
+
+    /// Reload the checklists from the server
+    pub async fn refresh(&mut self) -> Result<&[CheckList], SDKError> {
+        let current = self.fetch().await?;
+        Ok(self.seen.insert(current))
+    }
+
+    /// Progress over all checklists of the task
+    pub async fn progress(&mut self) -> Result<ChecklistProgress, SDKError> {
+        Ok(ChecklistProgress::of_lists(self.lists().await?))
+    }
+
+    /// Append a new empty checklist, returning its index
+    pub async fn add_list(&mut self, title: impl Into<String>) -> Result<usize, SDKError> {
+        let title = title.into();
+        self.commit(move |lists| {
+            lists.push(CheckList::new(title.clone(), Vec::new()));
+            Ok(lists.len() - 1)
+        })
+        .await
+    }
+
+    /// Append an unchecked item to a checklist, returning its index
+    pub async fn add_item(
+        &mut self,
+        list: usize,
+        title: impl Into<String>,
+    ) -> Result<usize, SDKError> {
+        let list = self.list_anchor(list).await?;
+        let title = title.into();
+        self.commit(move |lists| {
+            let list_index = locate_list(lists, &list)?;
+            let items = &mut lists[list_index].items;
+            items.push(CheckListItem::new(title.clone(), false));
+            Ok(items.len() - 1)
+        })
+        .await
+    }
+
+    /// Flip the completion of an item as last seen, returning its new state
+    ///
+    /// Fails without writing if someone else changed the item's state since it
+    /// was read; the handle then holds the fresh checklists.
+    pub async fn toggle_item(&mut self, list: usize, item: usize) -> Result<bool, SDKError> {
+        let (list_anchor, item_anchor) = self.item_anchor(list, item).await?;
+        let seen_completed = self.lists().await?[list].items[item].is_completed;
+        self.commit(move |lists| {
+            let entry = locate_item(lists, &list_anchor, &item_anchor)?;
+            if entry.is_completed != seen_completed {
+                return Err(SDKError::validation(format!(
+                    "item '{}' of checklist '{}' was changed concurrently",
+                    item_anchor.title, list_anchor.title
+                )));
+            }
+            entry.is_completed = !seen_completed;
+            Ok(entry.is_completed)
+        })
+        .await
+    }
+
+    /// Set the completion of an item
+    pub async fn set_item(
+        &mut self,
+        list: usize,
+        item: usize,
+        completed: bool,
+    ) -> Result<(), SDKError> {
+        let (list, item) = self.item_anchor(list, item).await?;
+        self.commit(move |lists| {
+            locate_item(lists, &list, &item)?.is_completed = completed;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn rename_item(
+        &mut self,
+        list: usize,
+        item: usize,
+        title: impl Into<String>,
+    ) -> Result<(), SDKError> {
+        let (list, item) = self.item_anchor(list, item).await?;
+        let title = title.into();
+        self.commit(move |lists| {
+            locate_item(lists, &list, &item)?.title = title.clone();
+            Ok(())
+        })
+        .await
+    }
+
+    /// Remove an item, returning it
+    pub async fn remove_item(
+        &mut self,
+        list: usize,
+        item: usize,
+    ) -> Result<CheckListItem, SDKError> {
+        let (list, item) = self.item_anchor(list, item).await?;
+        self.commit(move |lists| {
+            let list_index = locate_list(lists, &list)?;
+            let item_index = locate_item_index(&lists[list_index].items, &list, &item)?;
+            Ok(lists[list_index].items.remove(item_index))
+        })
+        .await
+    }
+
+    /// Move an item to another position within its checklist
+    pub async fn reorder(&mut self, list: usize, from: usize, to: usize) -> Result<(), SDKError> {
+        let (list, item) = self.item_anchor(list, from).await?;
+        self.commit(move |lists| {
+            let list_index = locate_list(lists, &list)?;
+            let items = &mut lists[list_index].items;
+            let from = locate_item_index(items, &list, &item)?;
+            let moved = items.remove(from);
+            items.insert(to.min(items.len()), moved);
+            Ok(())
+        })
+        .await
+    }
+
+    async fn fetch(&self) -> Result<Vec<CheckList>, SDKError> {
+        let task = self.client.get_task(&self.task_id).await?;
+        Ok(task.checklists.unwrap_or_default())
+    }
+
+    async fn list_anchor(&mut self, list: usize) -> Result<Anchor, SDKError> {
+        let task_id = self.task_id.clone();
+        let lists = self.lists().await?;
+        let checklist = lists.get(list).ok_or_else(|| {
+            SDKError::not_found(format!("checklist #{} of task {}", list, task_id))
+        })?;
+        Ok(Anchor {
+            index: list,
+            title: checklist.title.clone(),
+        })
+    }
+
+    async fn item_anchor(
+        &mut self,
+        list: usize,
+        item: usize,
+    ) -> Result<(Anchor, Anchor), SDKError> {
+        let list_anchor = self.list_anchor(list).await?;
+        let task_id = self.task_id.clone();
+        let lists = self.lists().await?;
+        let entry = lists[list].items.get(item).ok_or_else(|| {
+            SDKError::not_found(format!(
+                "item #{} of checklist '{}' in task {}",
+                item, list_anchor.title, task_id
+            ))
+        })?;
+        let item_anchor = Anchor {
+            index: item,
+            title: entry.title.clone(),
+        };
+        Ok((list_anchor, item_anchor))
+    }
+
+    /// Apply a change to the latest checklists and write them back
+    async fn commit<T, F>(&mut self, change: F) -> Result<T, SDKError>
+    where
+        F: Fn(&mut Vec<CheckList>) -> Result<T, SDKError>,
+    {
+        let mut lists = self.fetch().await?;
+        if self.seen.as_ref().is_some_and(|seen| *seen != lists) {
+            log::warn!(
+                "Checklists of task {} changed since they were read, merging",
+                self.task_id
+            );
+        }
+
+        let fresh = lists.clone();
+        let result = match change(&mut lists) {
+            Ok(result) => result,
+            Err(error) => {
+                self.seen = Some(fresh);
+                return Err(error);
+            }
+        };
+
+        self.client
+            .update_task(
+                &self.task_id,
+                UpdateTask {
+                    checklists: Some(lists.clone()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        self.seen = Some(lists);
+
+        Ok(result)
+    }
+}
+
+fn locate_list(lists: &[CheckList], list: &Anchor) -> Result<usize, SDKError> {
+    list.locate(lists.iter().map(|checklist| checklist.title.as_str()))
+        .ok_or_else(|| {
+            SDKError::not_found(format!(
+                "checklist '{}' was removed concurrently",
+                list.title
+            ))
+        })
+}
+
+fn locate_item_index(
+    items: &[CheckListItem],
+    list: &Anchor,
+    item: &Anchor,
+) -> Result<usize, SDKError> {
+    item.locate(items.iter().map(|item| item.title.as_str()))
+        .ok_or_else(|| {
+            SDKError::not_found(format!(
+                "item '{}' of checklist '{}' was removed concurrently",
+                item.title, list.title
+            ))
+        })
+}
+
+fn locate_item<'a>(
+    lists: &'a mut [CheckList],
+    list: &Anchor,
+    item: &Anchor,
+) -> Result<&'a mut CheckListItem, SDKError> {
+    let list_index = locate_list(lists, list)?;
+    let item_index = locate_item_index(&lists[list_index].items, list, item)?;
+    Ok(&mut lists[list_index].items[item_index])
+}
+
+impl TasksAPI {
+    /// Handle for editing the checklists of a task
+    pub fn checklists(&self, task_id: &str) -> Checklists {
+        Checklists::new(self.client.clone(), task_id)
+    }
+}
@@ -0,0 +1,149 @@
+use super::TasksAPI;
+use super::bulk::fetch_tasks;
+use crate::SDKError;
+use crate::dependencies::DependencyGraph;
+use crate::resources::{BoardsAPI, ColumnsAPI};
+use crate::throttle::Throttle;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use yougile_api_client::models::*;
+
+/// Number of task fetches in flight while following links
+const LINK_CONCURRENCY: usize = 4;
+
+impl TasksAPI {
+    /// Dependency graph of all tasks on a board
+    pub async fn dependencies_for_board(
+        &self,
+        board_id: &str,
+    ) -> Result<DependencyGraph, SDKError> {
+        Ok(DependencyGraph::new(self.board_tasks(board_id).await?))
+    }
+
+    /// Dependency graph of all tasks on every board of a project
+    pub async fn dependencies_for_project(
+        &self,
+        project_id: &str,
+    ) -> Result<DependencyGraph, SDKError> {
+        let boards = BoardsAPI::new(self.client.clone())
+            .list_for_project(project_id)
+            .await?;
+
+        let mut tasks = Vec::new();
+        for board in boards {
+            tasks.extend(self.board_tasks(&board.id).await?);
+        }
+        Ok(DependencyGraph::new(tasks))
+    }
+
+    /// Make `to_id` depend on `from_id` by adding it to the deadline links of `from_id`
+    ///
+    /// Fails if `from_id` has no deadline to hold the link, or if `from_id`
+    /// already depends on `to_id`, which would create a cycle.
+    pub async fn link(&self, from_id: &str, to_id: &str) -> Result<(), SDKError> {
+        if from_id == to_id {
+            return Err(SDKError::validation("A task cannot depend on itself"));
+        }
+
+        let task = self.client.get_task(from_id).await?;
+        let Some(deadline) = task.deadline else {
+            return Err(SDKError::validation(format!(
+                "Task {} has no deadline to hold links",
+                from_id
+            )));
+        };
+        let mut links = deadline.links.clone().unwrap_or_default();
+        if links.iter().any(|id| id == to_id) {
+            return Ok(());
+        }
+        if self.downstream(to_id).await?.depends_on(from_id, to_id) {
+            return Err(SDKError::validation(format!(
+                "Task {} already depends on {}, linking them would create a cycle",
+                from_id, to_id
+            )));
+        }
+        links.push(to_id.to_string());
+        self.set_links(from_id, &deadline, links).await
+    }
+
+    /// Remove `to_id` from the deadline links of `from_id`
+    pub async fn unlink(&self, from_id: &str, to_id: &str) -> Result<(), SDKError> {
+        let task = self.client.get_task(from_id).await?;
+        let Some(deadline) = task.deadline else {
+            return Ok(());
+        };
+        let mut links = deadline.links.clone().unwrap_or_default();
+        let before = links.len();
+        links.retain(|id| id != to_id);
+        if links.len() == before {
+            return Ok(());
+        }
+        self.set_links(from_id, &deadline, links).await
+    }
+
+    async fn board_tasks(&self, board_id: &str) -> Result<Vec<Task>, SDKError> {
+        let columns = ColumnsAPI::new(self.client.clone())
+            .list_by_board(board_id)
+            .await?;
+
+        let mut tasks = Vec::new();
+        for column in columns {
+            tasks.extend(self.list_by_column(&column.id).await?);
+        }
+        Ok(tasks)
+    }
+
+    /// Dependency graph of a task and every task that follows it through links,
+    /// loaded level by level
+    async fn downstream(&self, task_id: &str) -> Result<DependencyGraph, SDKError> {
+        let throttle = Arc::new(Throttle::new(&self.client, LINK_CONCURRENCY));
+        let mut seen = HashSet::from([task_id.to_string()]);
+        let mut level = vec![task_id.to_string()];
+        let mut tasks = Vec::new();
+        while !level.is_empty() {
+            let mut next = Vec::new();
+            for result in fetch_tasks(&self.client, &throttle, level).await {
+                let task = result.map_err(|(_, error)| error)?;
+                for link in task
+                    .deadline
+                    .iter()
+                    .flat_map(|deadline| deadline.links.iter().flatten())
+                {
+                    if seen.insert(link.clone()) {
+                        next.push(link.clone());
+                    }
+                }
+                tasks.push(task);
+            }
+            level = next;
+        }
+        Ok(DependencyGraph::new(tasks))
+    }
+
+    /// Replace the links of a deadline, keeping its dates and settings
+    async fn set_links(
+        &self,
+        task_id: &str,
+        deadline: &Deadline,
+        links: Vec<String>,
+    ) -> Result<(), SDKError> {
+        self.client
+            .update_task(
+                task_id,
+                UpdateTask {
+                    deadline: Some(UpdateDeadline {
+                        deadline: Some(deadline.deadline),
+                        start_date: deadline.start_date,
+                        with_time: deadline.with_time,
+                        blocked_points: deadline.blocked_points.clone(),
+                        links: Some(links),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+}
@@ -1,4 +1,25 @@
+mod bulk;
+mod chat;
+mod checklists;
+mod links;
+mod moving;
+mod timers;
+mod tree;
+
+pub use bulk::{BulkEntry, BulkOptions, BulkOutcome, BulkReport, TaskSet};
+pub use chat::{ChatFollower, TaskChat};
+pub use checklists::{ChecklistProgress, Checklists};
+pub use moving::{
+    ColumnMapping, ColumnMoveReport, DroppedSticker, MoveFailure, MoveReport, StickerRemap,
+};
+pub use timers::{StopwatchSwitch, TaskStopwatch, TaskTimer};
+pub use tree::{SkipReason, SkippedLink, TaskNode, TaskTree, TreeRollup};
+
 use crate::SDKError;
+use crate::directory::Directory;
+use crate::query::{TaskQuery, TaskQueryBuilder};
+use crate::reports::{TaskSnapshot, TimeReportBuilder};
+use crate::time::now_ms;
 use std::sync::Arc;
 
 use yougile_api_client::YouGileClient;
@@ -6,11 +27,29 @@ use yougile_api_client::models::*;
 /// API for working with tasks
 pub struct TasksAPI {
     client: Arc<YouGileClient>,
+    directory: Option<Arc<Directory>>,
+    me: Option<String>,
 }
 
 impl TasksAPI {
     pub fn new(client: Arc<YouGileClient>) -> Self {
-        Self { client }
+        Self {
+            client,
+            directory: None,
+            me: None,
+        }
+    }
+
+    /// Use a shared directory to resolve names instead of loading them per call
+    pub fn with_directory(mut self, directory: Arc<Directory>) -> Self {
+        self.directory = Some(directory);
+        self
+    }
+
+    /// User ID that `assignee:me` refers to in queries
+    pub fn with_me(mut self, user_id: impl Into<String>) -> Self {
+        self.me = Some(user_id.into());
+        self
     }
 
     /// Get a specific task by ID
@@ -39,6 +78,32 @@ impl TasksAPI {
         TaskSearchBuilder::new(self.client.clone())
     }
 
+    /// Search for tasks with the query language, e.g. `assignee:me due<7d -completed`
+    ///
+    /// See [`crate::query`] for the supported terms. `assignee:me` needs the user
+    /// set with [`TasksAPI::with_me`] or `user_id` on [`crate::YouGileSDK::builder`].
+    pub fn query(&self, query: &str) -> TaskQueryBuilder {
+        let mut builder = TaskQueryBuilder::new(self.client.clone(), TaskQuery::parse(query));
+        if let Some(directory) = &self.directory {
+            builder = builder.directory(directory.clone());
+        }
+        if let Some(user_id) = &self.me {
+            builder = builder.me(user_id.clone());
+        }
+        builder
+    }
+
+    /// Report planned vs. worked hours over a set of tasks
+    pub fn time_report(&self, tasks: impl Into<TaskSet>) -> TimeReportBuilder {
+        TimeReportBuilder::new(self.client.clone(), self.directory.clone(), tasks.into())
+    }
+
+    /// Capture the current state of a set of tasks for analytics
+    pub async fn snapshot(&self, tasks: impl Into<TaskSet>) -> Result<TaskSnapshot, SDKError> {
+        let tasks = self.load(tasks.into()).await?;
+        Ok(TaskSnapshot::new(&tasks, now_ms()))
+    }
+
     /// List all tasks (with default parameters)
     pub async fn list(&self) -> Result<TaskList, SDKError> {
         self.search().execute().await
@@ -0,0 +1,574 @@
+use super::TasksAPI;
+use crate::SDKError;
+use crate::resources::{ColumnsAPI, StickersAPI};
+use std::collections::HashMap;
+
+use yougile_api_client::models::*;
+
+/// How to pick the destination column when a task changes boards
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnMapping {
+    /// Always move into this column of the target board
+    Column(String),
+    /// Move into the column with the same title as the source column,
+    /// falling back to the first column of the target board
+    ByTitle,
+    /// Source column ID to target column ID, falling back to `ByTitle`
+    Map(HashMap<String, String>),
+}
+
+/// Outcome of moving a single task
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MoveReport {
+    pub task_id: String,
+    pub from_column_id: Option<String>,
+    pub to_column_id: String,
+    /// Stickers available on both boards, left untouched
+    pub kept_stickers: Vec<String>,
+    /// Stickers replaced by a same-named sticker of the target board
+    pub remapped_stickers: Vec<StickerRemap>,
+    /// Sticker values that have no counterpart on the target board
+    pub dropped_stickers: Vec<DroppedSticker>,
+}
+
+impl MoveReport {
+    /// True if no sticker data was lost during the move
+    pub fn is_lossless(&self) -> bool {
+        self.dropped_stickers.is_empty()
+    }
+}
+
+/// A sticker value carried over to a different sticker on the target board
+#[derive(Clone, Debug, PartialEq)]
+pub struct StickerRemap {
+    pub from_sticker_id: String,
+    pub to_sticker_id: String,
+    pub from_value: StickerValue,
+    pub to_value: StickerValue,
+}
+
+/// A sticker value removed from the task because the target board can't hold it
+#[derive(Clone, Debug, PartialEq)]
+pub struct DroppedSticker {
+    pub sticker_id: String,
+    pub sticker_name: Option<String>,
+    pub value: StickerValue,
+}
+
+/// A task that could not be moved
+#[derive(Debug)]
+pub struct MoveFailure {
+    pub task_id: String,
+    pub title: String,
+    pub error: SDKError,
+}
+
+/// Outcome of moving every task of a column
+#[derive(Debug, Default)]
+pub struct ColumnMoveReport {
+    pub moved: Vec<MoveReport>,
+    pub failed: Vec<MoveFailure>,
+}
+
+impl ColumnMoveReport {
+    /// True if every task was moved
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    fn record_failure(&mut self, task: Task, error: SDKError) {
+        log::warn!("Moving task {} failed: {}", task.id, error);
+        self.failed.push(MoveFailure {
+            task_id: task.id,
+            title: task.title,
+            error,
+        });
+    }
+}
+
+/// String or sprint sticker enabled on a board, reduced to what remapping needs
+struct BoardSticker {
+    id: String,
+    name: String,
+    sprint: bool,
+    /// (state ID, state name)
+    states: Vec<(String, String)>,
+}
+
+/// Everything needed to move tasks onto a board, loaded once per board
+struct BoardTarget {
+    board_id: String,
+    columns: Vec<Column>,
+    stickers: Vec<BoardSticker>,
+}
+
+impl TasksAPI {
+    /// Move a task to another column of the same board
+    ///
+    /// Fails if the column belongs to a different board than the task's current
+    /// column; use [`TasksAPI::move_to_board`] to remap stickers in that case.
+    pub async fn move_to(&self, task_id: &str, column_id: &str) -> Result<Id, SDKError> {
+        let task = self.get(task_id).await?;
+        if let Some(current_id) = task.column_id.as_deref()
+            && current_id != column_id
+        {
+            let columns = ColumnsAPI::new(self.client.clone());
+            let current = columns.get(current_id).await?;
+            let target = columns.get(column_id).await?;
+            if current.board_id != target.board_id {
+                return Err(SDKError::validation(format!(
+                    "Column {} is on another board than task {}, use move_to_board",
+                    column_id, task_id
+                )));
+            }
+        }
+        self.set_column(task_id, column_id).await
+    }
+
+    async fn set_column(&self, task_id: &str, column_id: &str) -> Result<Id, SDKError> {
+        let update = UpdateTask {
+            column_id: Some(column_id.to_string()),
+            ..Default::default()
+        };
+        self.update(task_id, update).await
+    }
+
+    /// Move a task to another board, remapping board-local stickers and their states
+    ///
+    /// Stickers available on both boards are preserved as is. Stickers missing on the
+    /// target board are replaced by a sticker with the same name (and a state with the
+    /// same name) when one exists, otherwise they are detached and listed in the report.
+    /// A state name used by several states of the target sticker is not guessed.
+    pub async fn move_to_board(
+        &self,
+        task_id: &str,
+        board_id: &str,
+        column_mapping: ColumnMapping,
+    ) -> Result<MoveReport, SDKError> {
+        let task = self.get(task_id).await?;
+        let target = self.load_board_target(board_id).await?;
+
+        let source_column = match task.column_id.as_deref() {
+            Some(column_id) => Some(ColumnsAPI::new(self.client.clone()).get(column_id).await?),
+            None => None,
+        };
+
+        // Moving within the same board never needs remapping
+        let source_stickers = match &source_column {
+            Some(column) if column.board_id != board_id => {
+                self.load_board_stickers(&column.board_id).await?
+            }
+            _ => Vec::new(),
+        };
+
+        self.move_task_to_board(
+            &task,
+            source_column.as_ref(),
+            &source_stickers,
+            &target,
+            &column_mapping,
+        )
+        .await
+    }
+
+    /// Move every task of one column into another, e.g. before retiring a column
+    ///
+    /// Works across boards as well, applying the same sticker remapping as
+    /// [`TasksAPI::move_to_board`]. A failed update is recorded in the report and
+    /// the remaining tasks are still moved.
+    pub async fn move_column_tasks(
+        &self,
+        from_column_id: &str,
+        to_column_id: &str,
+    ) -> Result<ColumnMoveReport, SDKError> {
+        let columns = ColumnsAPI::new(self.client.clone());
+        let from = columns.get(from_column_id).await?;
+        let to = columns.get(to_column_id).await?;
+        let tasks = self.list_by_column(from_column_id).await?;
+
+        let mut report = ColumnMoveReport::default();
+
+        if from.board_id == to.board_id {
+            for task in tasks {
+                match self.set_column(&task.id, to_column_id).await {
+                    Ok(_) => report.moved.push(MoveReport {
+                        task_id: task.id,
+                        from_column_id: Some(from.id.clone()),
+                        to_column_id: to.id.clone(),
+                        kept_stickers: task
+                            .stickers
+                            .map(|stickers| stickers.into_keys().collect())
+                            .unwrap_or_default(),
+                        ..Default::default()
+                    }),
+                    Err(error) => report.record_failure(task, error),
+                }
+            }
+            return Ok(report);
+        }
+
+        let target = self.load_board_target(&to.board_id).await?;
+        let source_stickers = self.load_board_stickers(&from.board_id).await?;
+        let mapping = ColumnMapping::Column(to.id.clone());
+
+        for task in tasks {
+            match self
+                .move_task_to_board(&task, Some(&from), &source_stickers, &target, &mapping)
+                .await
+            {
+                Ok(moved) => report.moved.push(moved),
+                Err(error) => report.record_failure(task, error),
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn move_task_to_board(
+        &self,
+        task: &Task,
+        source_column: Option<&Column>,
+        source_stickers: &[BoardSticker],
+        target: &BoardTarget,
+        mapping: &ColumnMapping,
+    ) -> Result<MoveReport, SDKError> {
+        let to_column_id = resolve_column(mapping, source_column, &target.columns)?;
+
+        let mut report = MoveReport {
+            task_id: task.id.clone(),
+            from_column_id: task.column_id.clone(),
+            to_column_id: to_column_id.clone(),
+            ..Default::default()
+        };
+
+        let same_board = source_column.is_some_and(|column| column.board_id == target.board_id);
+
+        let patch = match &task.stickers {
+            Some(stickers) if same_board => {
+                report.kept_stickers = stickers.keys().cloned().collect();
+                TaskStickers::new()
+            }
+            Some(stickers) => {
+                remap_stickers(stickers, source_stickers, &target.stickers, &mut report)
+            }
+            None => TaskStickers::new(),
+        };
+
+        let update = UpdateTask {
+            column_id: Some(to_column_id),
+            stickers: (!patch.is_empty()).then_some(patch),
+            ..Default::default()
+        };
+        self.update(&task.id, update).await?;
+
+        if !report.dropped_stickers.is_empty() {
+            log::warn!(
+                "Task {} lost {} sticker value(s) while moving to column {}",
+                report.task_id,
+                report.dropped_stickers.len(),
+                report.to_column_id
+            );
+        }
+
+        Ok(report)
+    }
+
+    async fn load_board_target(&self, board_id: &str) -> Result<BoardTarget, SDKError> {
+        let columns = ColumnsAPI::new(self.client.clone())
+            .list_by_board(board_id)
+            .await?
+            .into_iter()
+            .filter(|column| column.deleted != Some(true))
+            .collect();
+        let stickers = self.load_board_stickers(board_id).await?;
+
+        Ok(BoardTarget {
+            board_id: board_id.to_string(),
+            columns,
+            stickers,
+        })
+    }
+
+    async fn load_board_stickers(&self, board_id: &str) -> Result<Vec<BoardSticker>, SDKError> {
+        let api = StickersAPI::new(self.client.clone());
+        let strings = api.list_string_by_board(board_id).await?;
+        let sprints = api.list_sprint_by_board(board_id).await?;
+
+        let strings = strings.into_iter().map(|sticker| BoardSticker {
+            id: sticker.id,
+            name: sticker.data.name,
+            sprint: false,
+            states: sticker
+                .states
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|state| state.data.deleted != Some(true))
+                .map(|state| (state.id, state.data.name))
+                .collect(),
+        });
+        let sprints = sprints.into_iter().map(|sticker| BoardSticker {
+            id: sticker.id,
+            name: sticker.data.name,
+            sprint: true,
+            states: sticker
+                .states
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|state| state.data.deleted != Some(true))
+                .map(|state| (state.id, state.data.name))
+                .collect(),
+        });
+
+        Ok(strings.chain(sprints).collect())
+    }
+}
+
+fn resolve_column(
+    mapping: &ColumnMapping,
+    source_column: Option<&Column>,
+    target_columns: &[Column],
+) -> Result<String, SDKError> {
+    let by_title = || {
+        source_column
+            .and_then(|source| {
+                target_columns
+                    .iter()
+                    .find(|column| names_match(&column.title, &source.title))
+            })
+            .or_else(|| target_columns.first())
+            .map(|column| column.id.clone())
+            .ok_or_else(|| SDKError::not_found("Target board has no columns"))
+    };
+
+    let explicit = |column_id: &String| {
+        if target_columns.iter().any(|column| &column.id == column_id) {
+            Ok(column_id.clone())
+        } else {
+            Err(SDKError::validation(format!(
+                "Column {} does not belong to the target board",
+                column_id
+            )))
+        }
+    };
+
+    match mapping {
+        ColumnMapping::Column(column_id) => explicit(column_id),
+        ColumnMapping::ByTitle => by_title(),
+        ColumnMapping::Map(map) => match source_column.and_then(|source| map.get(&source.id)) {
+            Some(column_id) => explicit(column_id),
+            None => by_title(),
+        },
+    }
+}
+
+/// Build the sticker patch for a cross-board move, recording every decision in `report`
+fn remap_stickers(
+    stickers: &TaskStickers,
+    source: &[BoardSticker],
+    target: &[BoardSticker],
+    report: &mut MoveReport,
+) -> TaskStickers {
+    let mut patch = TaskStickers::new();
+
+    let mut entries: Vec<_> = stickers.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (sticker_id, value) in entries {
+        if target.iter().any(|sticker| &sticker.id == sticker_id) {
+            report.kept_stickers.push(sticker_id.clone());
+            continue;
+        }
+
+        let source_sticker = source.iter().find(|sticker| &sticker.id == sticker_id);
+        let counterpart = source_sticker.and_then(|source_sticker| {
+            target.iter().find(|sticker| {
+                sticker.sprint == source_sticker.sprint
+                    && names_match(&sticker.name, &source_sticker.name)
+            })
+        });
+
+        patch.insert(sticker_id.clone(), StickerValue::StateId(UNSET.to_string()));
+
+        let remapped = source_sticker
+            .zip(counterpart)
+            .and_then(|(from, to)| remap_value(value, from, to).map(|value| (to, value)));
+
+        match remapped {
+            Some((to, to_value)) => {
+                patch.insert(to.id.clone(), to_value.clone());
+                report.remapped_stickers.push(StickerRemap {
+                    from_sticker_id: sticker_id.clone(),
+                    to_sticker_id: to.id.clone(),
+                    from_value: value.clone(),
+                    to_value,
+                });
+            }
+            None => report.dropped_stickers.push(DroppedSticker {
+                sticker_id: sticker_id.clone(),
+                sticker_name: source_sticker.map(|sticker| sticker.name.clone()),
+                value: value.clone(),
+            }),
+        }
+    }
+
+    patch
+}
+
+fn remap_value(
+    value: &StickerValue,
+    from: &BoardSticker,
+    to: &BoardSticker,
+) -> Option<StickerValue> {
+    let raw = match value {
+        StickerValue::StateId(raw) | StickerValue::Text(raw) => raw,
+        other => return Some(other.clone()),
+    };
+
+    if raw == "empty" {
+        return Some(value.clone());
+    }
+
+    // Free text and numeric fields carry the value itself; a sticker with states
+    // only takes it if it names one of its states
+    if from.states.is_empty() {
+        if to.states.is_empty() {
+            return Some(value.clone());
+        }
+        return state_named(to, raw);
+    }
+
+    let state_name = from
+        .states
+        .iter()
+        .find(|(id, _)| id == raw)
+        .map(|(_, name)| name)?;
+
+    state_named(to, state_name)
+}
+
+/// The state of `sticker` called `name`; none if several states share the name
+fn state_named(sticker: &BoardSticker, name: &str) -> Option<StickerValue> {
+    let mut states = sticker
+        .states
+        .iter()
+        .filter(|(_, state_name)| names_match(state_name, name));
+    match (states.next(), states.next()) {
+        (Some((id, _)), None) => Some(StickerValue::StateId(id.clone())),
+        _ => None,
+    }
+}
+
+fn names_match(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sticker(id: &str, name: &str, states: &[(&str, &str)]) -> BoardSticker {
+        BoardSticker {
+            id: id.to_string(),
+            name: name.to_string(),
+            sprint: false,
+            states: states
+                .iter()
+                .map(|(id, name)| (id.to_string(), name.to_string()))
+                .collect(),
+        }
+    }
+
+    fn state(id: &str) -> StickerValue {
+        StickerValue::StateId(id.to_string())
+    }
+
+    #[test]
+    fn states_are_matched_by_name() {
+        let from = sticker("s1", "Priority", &[("a1", "High"), ("a2", "Low")]);
+        let to = sticker("s2", "priority", &[("b1", " high "), ("b2", "Medium")]);
+
+        assert_eq!(remap_value(&state("a1"), &from, &to), Some(state("b1")));
+        // Renamed or missing on the target board
+        assert_eq!(remap_value(&state("a2"), &from, &to), None);
+        // Unknown on the source board
+        assert_eq!(remap_value(&state("a3"), &from, &to), None);
+        assert_eq!(
+            remap_value(&state("empty"), &from, &to),
+            Some(state("empty"))
+        );
+    }
+
+    #[test]
+    fn duplicate_state_names_are_not_guessed() {
+        let from = sticker("s1", "Priority", &[("a1", "High"), ("a2", "High")]);
+        let to = sticker("s2", "Priority", &[("b1", "High"), ("b2", "high")]);
+        let unique = sticker("s3", "Priority", &[("c1", "High")]);
+
+        assert_eq!(remap_value(&state("a1"), &from, &to), None);
+        assert_eq!(remap_value(&state("a2"), &from, &unique), Some(state("c1")));
+    }
+
+    #[test]
+    fn free_text_only_fills_a_matching_state() {
+        let from = sticker("s1", "Stage", &[]);
+        let text = sticker("s2", "Stage", &[]);
+        let states = sticker("s3", "Stage", &[("b1", "Review")]);
+        let value = StickerValue::Text("review".to_string());
+
+        assert_eq!(remap_value(&value, &from, &text), Some(value.clone()));
+        assert_eq!(remap_value(&value, &from, &states), Some(state("b1")));
+        assert_eq!(
+            remap_value(&StickerValue::Text("Done".to_string()), &from, &states),
+            None
+        );
+    }
+
+    #[test]
+    fn remapping_detaches_the_source_sticker() {
+        let source = [
+            sticker("s1", "Priority", &[("a1", "High")]),
+            sticker("s2", "Size", &[("a2", "XL")]),
+        ];
+        let target = [
+            sticker("t1", "Priority", &[("b1", "High")]),
+            sticker("shared", "Owner", &[]),
+        ];
+        let stickers = TaskStickers::from([
+            ("s1".to_string(), state("a1")),
+            ("s2".to_string(), state("a2")),
+            ("shared".to_string(), StickerValue::Text("Ann".to_string())),
+        ]);
+        let mut report = MoveReport::default();
+
+        let patch = remap_stickers(&stickers, &source, &target, &mut report);
+
+        assert_eq!(patch.get("s1"), Some(&state(UNSET)));
+        assert_eq!(patch.get("s2"), Some(&state(UNSET)));
+        assert_eq!(patch.get("t1"), Some(&state("b1")));
+        assert!(!patch.contains_key("shared"));
+        assert_eq!(report.kept_stickers, ["shared"]);
+        assert_eq!(report.remapped_stickers.len(), 1);
+        assert_eq!(
+            report.dropped_stickers[0].sticker_name.as_deref(),
+            Some("Size")
+        );
+        assert!(!report.is_lossless());
+    }
+
+    #[test]
+    fn failures_are_collected() {
+        let mut report = ColumnMoveReport::default();
+        assert!(report.is_success());
+
+        let task = Task {
+            id: "task-1".to_string(),
+            title: "Write docs".to_string(),
+            ..Default::default()
+        };
+        report.record_failure(task, SDKError::validation("rejected"));
+
+        assert!(!report.is_success());
+        assert_eq!(report.failed[0].task_id, "task-1");
+        assert_eq!(report.failed[0].title, "Write docs");
+    }
+}
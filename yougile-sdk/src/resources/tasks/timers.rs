@@ -0,0 +1,263 @@
+use super::{TaskSearchBuilder, TasksAPI};
+use crate::SDKError;
+use crate::time::{SECOND_MS, now_ms, stopwatch_seconds};
+use std::sync::Arc;
+use std::time::Duration;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// Handle for the stopwatch of a single task
+///
+/// Unlike [`TaskTimer`] there is no `set`: the API only accepts starting,
+/// pausing and detaching a stopwatch, not a counted value.
+pub struct TaskStopwatch {
+    client: Arc<YouGileClient>,
+    task_id: String,
+}
+
+/// Outcome of [`TaskStopwatch::switch`]
+#[derive(Debug, Default)]
+pub struct StopwatchSwitch {
+    /// Tasks whose stopwatch was paused
+    pub paused: Vec<String>,
+    /// Tasks whose stopwatch could not be paused, or this task if it could not be started
+    pub failed: Vec<(String, SDKError)>,
+    /// True if this stopwatch was started
+    pub started: bool,
+}
+
+impl StopwatchSwitch {
+    pub fn is_success(&self) -> bool {
+        self.started && self.failed.is_empty()
+    }
+}
+
+/// Handle for the countdown timer of a single task
+pub struct TaskTimer {
+    client: Arc<YouGileClient>,
+    task_id: String,
+}
+
+impl TaskStopwatch {
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    /// Current stopwatch of the task, `None` if it has none
+    pub async fn get(&self) -> Result<Option<Stopwatch>, SDKError> {
+        Ok(self.client.get_task(&self.task_id).await?.stopwatch)
+    }
+
+    /// Time counted so far, including the time since the last update if running
+    pub async fn elapsed(&self) -> Result<Option<Duration>, SDKError> {
+        let now = now_ms();
+        Ok(self
+            .get()
+            .await?
+            .map(|stopwatch| seconds(stopwatch_seconds(&stopwatch, now))))
+    }
+
+    /// Start the stopwatch, attaching it to the task if needed
+    pub async fn start(&self) -> Result<(), SDKError> {
+        self.send(UpdateStopwatch {
+            running: Some(true),
+            ..Default::default()
+        })
+        .await
+    }
+
+    pub async fn pause(&self) -> Result<(), SDKError> {
+        self.send(UpdateStopwatch {
+            running: Some(false),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Stop the stopwatch and clear the counted time
+    ///
+    /// The API cannot set the stopwatch value, so the stopwatch is removed
+    /// and attached again.
+    pub async fn reset(&self) -> Result<(), SDKError> {
+        self.remove().await?;
+        self.pause().await
+    }
+
+    /// Detach the stopwatch from the task
+    pub async fn remove(&self) -> Result<(), SDKError> {
+        self.send(UpdateStopwatch {
+            deleted: Some(true),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Start this stopwatch and pause every other running stopwatch on tasks
+    /// assigned to the user
+    ///
+    /// Every running stopwatch is paused even if some pauses fail. This
+    /// stopwatch is only started once all of them are paused, so that two
+    /// stopwatches never run at the same time.
+    pub async fn switch(&self, user_id: &str) -> Result<StopwatchSwitch, SDKError> {
+        let tasks = TaskSearchBuilder::new(self.client.clone())
+            .assigned_to(user_id)
+            .all()
+            .await?;
+
+        let mut switch = StopwatchSwitch::default();
+        for task in tasks {
+            let running = task
+                .stopwatch
+                .as_ref()
+                .is_some_and(|stopwatch| stopwatch.running);
+            if !running || task.id == self.task_id {
+                continue;
+            }
+
+            let other = TaskStopwatch {
+                client: self.client.clone(),
+                task_id: task.id,
+            };
+            match other.pause().await {
+                Ok(()) => {
+                    log::debug!("Paused stopwatch of task {}", other.task_id);
+                    switch.paused.push(other.task_id);
+                }
+                Err(error) => {
+                    log::warn!(
+                        "Pausing stopwatch of task {} failed: {}",
+                        other.task_id,
+                        error
+                    );
+                    switch.failed.push((other.task_id, error));
+                }
+            }
+        }
+
+        if switch.failed.is_empty() {
+            match self.start().await {
+                Ok(()) => switch.started = true,
+                Err(error) => switch.failed.push((self.task_id.clone(), error)),
+            }
+        }
+        Ok(switch)
+    }
+
+    async fn send(&self, stopwatch: UpdateStopwatch) -> Result<(), SDKError> {
+        self.client
+            .update_task(
+                &self.task_id,
+                UpdateTask {
+                    stopwatch: Some(stopwatch),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+impl TaskTimer {
+    pub fn task_id(&self) -> &str {
+        &self.task_id
+    }
+
+    /// Current timer of the task, `None` if it has none
+    pub async fn get(&self) -> Result<Option<Timer>, SDKError> {
+        Ok(self.client.get_task(&self.task_id).await?.timer)
+    }
+
+    /// Time left until the timer runs out, zero once it has expired
+    pub async fn remaining(&self) -> Result<Option<Duration>, SDKError> {
+        let now = now_ms();
+        Ok(self.get().await?.map(|timer| {
+            let left = if timer.running {
+                timer.seconds - (now - timer.since) / SECOND_MS
+            } else {
+                timer.seconds
+            };
+            seconds(left)
+        }))
+    }
+
+    /// Start counting down, attaching the timer to the task if needed
+    pub async fn start(&self) -> Result<(), SDKError> {
+        self.send(UpdateTimer {
+            running: Some(true),
+            ..Default::default()
+        })
+        .await
+    }
+
+    pub async fn pause(&self) -> Result<(), SDKError> {
+        self.send(UpdateTimer {
+            running: Some(false),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Stop the timer and set it to zero
+    pub async fn reset(&self) -> Result<(), SDKError> {
+        self.send(UpdateTimer {
+            seconds: Some(0.0),
+            running: Some(false),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Detach the timer from the task
+    pub async fn remove(&self) -> Result<(), SDKError> {
+        self.send(UpdateTimer {
+            deleted: Some(true),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Set the time left on the timer, keeping it running or paused
+    pub async fn set(&self, duration: Duration) -> Result<(), SDKError> {
+        self.send(UpdateTimer {
+            seconds: Some(duration.as_secs_f64()),
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn send(&self, timer: UpdateTimer) -> Result<(), SDKError> {
+        self.client
+            .update_task(
+                &self.task_id,
+                UpdateTask {
+                    timer: Some(timer),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+impl TasksAPI {
+    /// Handle for the stopwatch of a task
+    pub fn stopwatch(&self, task_id: &str) -> TaskStopwatch {
+        TaskStopwatch {
+            client: self.client.clone(),
+            task_id: task_id.to_string(),
+        }
+    }
+
+    /// Handle for the countdown timer of a task
+    pub fn timer(&self, task_id: &str) -> TaskTimer {
+        TaskTimer {
+            client: self.client.clone(),
+            task_id: task_id.to_string(),
+        }
+    }
+}
+
+fn seconds(value: f64) -> Duration {
+    Duration::from_secs_f64(value.max(0.0))
+}
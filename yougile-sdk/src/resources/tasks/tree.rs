@@ -0,0 +1,336 @@
+use super::bulk::fetch_tasks;
+use super::{BulkOptions, BulkReport, TaskSet, TasksAPI};
+use crate::SDKError;
+use crate::throttle::Throttle;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use yougile_api_client::models::*;
+
+/// Number of task fetches in flight while loading a tree
+const TREE_CONCURRENCY: usize = 4;
+
+/// A task with its loaded subtasks
+#[derive(Clone, Debug)]
+pub struct TaskNode {
+    pub task: Task,
+    pub children: Vec<TaskNode>,
+}
+
+/// Totals over a task and all of its descendants
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TreeRollup {
+    pub tasks: usize,
+    pub completed: usize,
+    /// Planned hours
+    pub plan: f64,
+    /// Worked hours
+    pub work: f64,
+}
+
+impl TreeRollup {
+    /// Share of completed tasks in percent
+    pub fn percent(&self) -> f64 {
+        if self.tasks == 0 {
+            return 0.0;
+        }
+        self.completed as f64 * 100.0 / self.tasks as f64
+    }
+}
+
+/// Subtask link that was not followed while loading a tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkippedLink {
+    pub parent_id: String,
+    pub child_id: String,
+    pub reason: SkipReason,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The child is an ancestor of the parent
+    Cycle,
+    /// The child was already reached through another parent
+    Duplicate,
+    /// The child could not be fetched
+    Missing,
+}
+
+/// Subtask hierarchy below a root task
+#[derive(Clone, Debug)]
+pub struct TaskTree {
+    pub root: TaskNode,
+    pub skipped: Vec<SkippedLink>,
+}
+
+impl TaskNode {
+    /// Depth-first iterator over this node and its descendants with their depth
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &TaskNode)> {
+        let mut stack = vec![(0, self)];
+        std::iter::from_fn(move || {
+            let (depth, node) = stack.pop()?;
+            stack.extend(node.children.iter().rev().map(|child| (depth + 1, child)));
+            Some((depth, node))
+        })
+    }
+
+    pub fn find(&self, task_id: &str) -> Option<&TaskNode> {
+        self.iter()
+            .map(|(_, node)| node)
+            .find(|node| node.task.id == task_id)
+    }
+
+    /// IDs of this task and all descendants, parents before children
+    pub fn ids(&self) -> Vec<String> {
+        self.iter().map(|(_, node)| node.task.id.clone()).collect()
+    }
+
+    pub fn rollup(&self) -> TreeRollup {
+        self.iter()
+            .fold(TreeRollup::default(), |mut rollup, (_, node)| {
+                rollup.tasks += 1;
+                if node.task.completed == Some(true) {
+                    rollup.completed += 1;
+                }
+                if let Some(time) = &node.task.time_tracking {
+                    rollup.plan += time.plan;
+                    rollup.work += time.work;
+                }
+                rollup
+            })
+    }
+}
+
+impl TaskTree {
+    pub fn rollup(&self) -> TreeRollup {
+        self.root.rollup()
+    }
+
+    /// Links that point back to an ancestor
+    pub fn cycles(&self) -> impl Iterator<Item = &SkippedLink> {
+        self.skipped
+            .iter()
+            .filter(|link| link.reason == SkipReason::Cycle)
+    }
+
+    /// Render the tree as indented text, one task per line
+    pub fn render(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for TaskTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_node(
+            f: &mut fmt::Formatter<'_>,
+            node: &TaskNode,
+            prefix: &str,
+            last: bool,
+            root: bool,
+        ) -> fmt::Result {
+            let branch = match (root, last) {
+                (true, _) => "",
+                (false, true) => "└── ",
+                (false, false) => "├── ",
+            };
+            let mark = if node.task.completed == Some(true) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            write!(f, "{}{}{} {}", prefix, branch, mark, node.task.title)?;
+            if !node.children.is_empty() {
+                let rollup = node.rollup();
+                write!(f, " ({}/{})", rollup.completed, rollup.tasks)?;
+            }
+            writeln!(f)?;
+
+            let child_prefix = match (root, last) {
+                (true, _) => prefix.to_string(),
+                (false, true) => format!("{}    ", prefix),
+                (false, false) => format!("{}│   ", prefix),
+            };
+            for (index, child) in node.children.iter().enumerate() {
+                write_node(
+                    f,
+                    child,
+                    &child_prefix,
+                    index + 1 == node.children.len(),
+                    false,
+                )?;
+            }
+            Ok(())
+        }
+
+        write_node(f, &self.root, "", true, true)
+    }
+}
+
+impl TasksAPI {
+    /// Load a task and all of its subtasks, level by level
+    ///
+    /// Links back to an ancestor, repeated links and subtasks that cannot be
+    /// fetched are reported in [`TaskTree::skipped`] instead of failing.
+    pub async fn tree(&self, root_id: &str) -> Result<TaskTree, SDKError> {
+        let throttle = Arc::new(Throttle::new(&self.client, TREE_CONCURRENCY));
+
+        let root = self.client.get_task(root_id).await?;
+        let mut parents: HashMap<String, String> = HashMap::new();
+        let mut seen = HashSet::from([root.id.clone()]);
+        let mut skipped = Vec::new();
+        let mut level = vec![root.id.clone()];
+        let mut tasks = HashMap::from([(root.id.clone(), root)]);
+
+        while !level.is_empty() {
+            let mut next = Vec::new();
+            for parent_id in &level {
+                for child_id in tasks[parent_id].subtasks.iter().flatten() {
+                    if seen.insert(child_id.clone()) {
+                        parents.insert(child_id.clone(), parent_id.clone());
+                        next.push(child_id.clone());
+                        continue;
+                    }
+
+                    let reason = if is_ancestor(&parents, child_id, parent_id) {
+                        SkipReason::Cycle
+                    } else {
+                        SkipReason::Duplicate
+                    };
+                    skipped.push(SkippedLink {
+                        parent_id: parent_id.clone(),
+                        child_id: child_id.clone(),
+                        reason,
+                    });
+                }
+            }
+
+            let mut fetched = Vec::new();
+            for result in fetch_tasks(&self.client, &throttle, next).await {
+                match result {
+                    Ok(task) => {
+                        fetched.push(task.id.clone());
+                        tasks.insert(task.id.clone(), task);
+                    }
+                    Err((child_id, error)) => {
+                        log::warn!("Could not load subtask {}: {}", child_id, error);
+                        skipped.push(SkippedLink {
+                            parent_id: parents.get(&child_id).cloned().unwrap_or_default(),
+                            child_id,
+                            reason: SkipReason::Missing,
+                        });
+                    }
+                }
+            }
+            level = fetched;
+        }
+
+        let root = build_node(root_id, &mut tasks, &parents);
+        Ok(TaskTree { root, skipped })
+    }
+
+    /// Add a task to the subtasks of another task
+    ///
+    /// Fails if the parent is already below the child, as that would create a cycle.
+    pub async fn attach_subtask(&self, parent_id: &str, child_id: &str) -> Result<(), SDKError> {
+        if parent_id == child_id || self.tree(child_id).await?.root.find(parent_id).is_some() {
+            return Err(SDKError::validation(format!(
+                "Attaching {} under {} would create a cycle",
+                child_id, parent_id
+            )));
+        }
+
+        let parent = self.client.get_task(parent_id).await?;
+        let mut subtasks = parent.subtasks.unwrap_or_default();
+        if subtasks.iter().any(|id| id == child_id) {
+            return Ok(());
+        }
+        subtasks.push(child_id.to_string());
+        self.set_subtasks(parent_id, subtasks).await
+    }
+
+    /// Remove a task from the subtasks of its parent; the task itself is kept
+    pub async fn detach_subtask(&self, parent_id: &str, child_id: &str) -> Result<(), SDKError> {
+        let parent = self.client.get_task(parent_id).await?;
+        let mut subtasks = parent.subtasks.unwrap_or_default();
+        let before = subtasks.len();
+        subtasks.retain(|id| id != child_id);
+        if subtasks.len() == before {
+            return Ok(());
+        }
+        self.set_subtasks(parent_id, subtasks).await
+    }
+
+    /// Move a subtask from one parent to another
+    pub async fn reparent(
+        &self,
+        child_id: &str,
+        from_parent_id: &str,
+        to_parent_id: &str,
+    ) -> Result<(), SDKError> {
+        self.attach_subtask(to_parent_id, child_id).await?;
+        self.detach_subtask(from_parent_id, child_id).await
+    }
+
+    /// Mark a task and all of its descendants as completed
+    pub async fn complete_subtree(
+        &self,
+        root_id: &str,
+        options: BulkOptions,
+    ) -> Result<BulkReport, SDKError> {
+        // The tree already holds every task, so they are not fetched again
+        let tree = self.tree(root_id).await?;
+        let tasks = tree
+            .root
+            .iter()
+            .map(|(_, node)| node.task.clone())
+            .collect();
+        self.bulk_complete(TaskSet::Tasks(tasks), options).await
+    }
+
+    async fn set_subtasks(&self, task_id: &str, subtasks: Vec<String>) -> Result<(), SDKError> {
+        self.client
+            .update_task(
+                task_id,
+                UpdateTask {
+                    subtasks: Some(subtasks),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// True if `ancestor` is `task_id` or one of its parents
+fn is_ancestor(parents: &HashMap<String, String>, ancestor: &str, task_id: &str) -> bool {
+    let mut current = Some(task_id);
+    while let Some(id) = current {
+        if id == ancestor {
+            return true;
+        }
+        current = parents.get(id).map(String::as_str);
+    }
+    false
+}
+
+fn build_node(
+    task_id: &str,
+    tasks: &mut HashMap<String, Task>,
+    parents: &HashMap<String, String>,
+) -> TaskNode {
+    let task = tasks
+        .remove(task_id)
+        .expect("every node of the tree was fetched");
+
+    let mut children = Vec::new();
+    for child_id in task.subtasks.iter().flatten() {
+        if parents.get(child_id).map(String::as_str) == Some(task_id)
+            && tasks.contains_key(child_id)
+        {
+            children.push(build_node(child_id, tasks, parents));
+        }
+    }
+
+    TaskNode { task, children }
+}
@@ -1,3 +1,10 @@
+mod workflows;
+
+pub use workflows::{
+    LoggedStep, OffboardOptions, OnboardTemplate, StepStatus, UserStep, UserWorkflow, WorkflowKind,
+    WorkflowLog,
+};
+
 use crate::SDKError;
 use std::sync::Arc;
 use yougile_api_client::YouGileClient;
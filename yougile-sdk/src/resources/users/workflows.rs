@@ -0,0 +1,639 @@
+use super::UsersAPI;
+use crate::resources::{DepartmentsAPI, GroupChatMembers, GroupChatsAPI, ProjectsAPI, TasksAPI};
+use crate::time::now_ms;
+use crate::{MemberRole, SDKError, state};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkflowKind {
+    Onboard,
+    Offboard,
+}
+
+/// A single change made by an onboarding or offboarding workflow
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum UserStep {
+    /// Invite the user to the company
+    Invite {
+        email: String,
+        is_admin: bool,
+    },
+    AddToProject {
+        project_id: String,
+        role: String,
+    },
+    AddToDepartment {
+        department_id: String,
+        role: String,
+    },
+    AddToGroupChat {
+        chat_id: String,
+        role_id: String,
+    },
+    /// Replace the user with the successor among the assignees, or just
+    /// unassign them without a successor
+    ReassignTask {
+        task_id: String,
+        title: String,
+    },
+    RemoveFromProject {
+        project_id: String,
+    },
+    RemoveFromDepartment {
+        department_id: String,
+    },
+    RemoveFromGroupChat {
+        chat_id: String,
+    },
+    /// Delete the user from the company
+    DeleteUser,
+}
+
+impl fmt::Display for UserStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UserStep::Invite { email, is_admin } => {
+                write!(f, "invite {}", email)?;
+                if *is_admin {
+                    write!(f, " as administrator")?;
+                }
+                Ok(())
+            }
+            UserStep::AddToProject { project_id, role } => {
+                write!(f, "add to project {} as {}", project_id, role)
+            }
+            UserStep::AddToDepartment {
+                department_id,
+                role,
+            } => write!(f, "add to department {} as {}", department_id, role),
+            UserStep::AddToGroupChat { chat_id, role_id } => {
+                write!(f, "add to group chat {} as {}", chat_id, role_id)
+            }
+            UserStep::ReassignTask { task_id, title } => {
+                write!(f, "reassign task {} {}", task_id, title)
+            }
+            UserStep::RemoveFromProject { project_id } => {
+                write!(f, "remove from project {}", project_id)
+            }
+            UserStep::RemoveFromDepartment { department_id } => {
+                write!(f, "remove from department {}", department_id)
+            }
+            UserStep::RemoveFromGroupChat { chat_id } => {
+                write!(f, "remove from group chat {}", chat_id)
+            }
+            UserStep::DeleteUser => write!(f, "delete user"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepStatus {
+    #[default]
+    Pending,
+    Done,
+    Failed,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoggedStep {
+    pub step: UserStep,
+    pub status: StepStatus,
+    /// Error of the last failed attempt
+    pub error: Option<String>,
+    pub finished_at: Option<f64>,
+}
+
+/// Plan of a workflow and the progress of its execution
+///
+/// Before [`UserWorkflow::run`] every step is pending, so the log doubles as a
+/// dry-run plan.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowLog {
+    pub kind: WorkflowKind,
+    /// ID of the user, known once an onboarded user has been invited
+    pub user_id: Option<String>,
+    pub email: Option<String>,
+    /// User who takes over the tasks of an offboarded user
+    pub successor: Option<String>,
+    pub steps: Vec<LoggedStep>,
+}
+
+impl WorkflowLog {
+    fn new(kind: WorkflowKind, steps: Vec<UserStep>) -> Self {
+        Self {
+            kind,
+            user_id: None,
+            email: None,
+            successor: None,
+            steps: steps
+                .into_iter()
+                .map(|step| LoggedStep {
+                    step,
+                    status: StepStatus::Pending,
+                    error: None,
+                    finished_at: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.steps
+            .iter()
+            .all(|step| step.status == StepStatus::Done)
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &LoggedStep> {
+        self.steps
+            .iter()
+            .filter(|step| step.status == StepStatus::Pending)
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &LoggedStep> {
+        self.steps
+            .iter()
+            .filter(|step| step.status == StepStatus::Failed)
+    }
+}
+
+impl fmt::Display for WorkflowLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            WorkflowKind::Onboard => "Onboard",
+            WorkflowKind::Offboard => "Offboard",
+        };
+        let user = self
+            .user_id
+            .as_deref()
+            .or(self.email.as_deref())
+            .unwrap_or("?");
+        write!(f, "{} {}", kind, user)?;
+        if let Some(successor) = &self.successor {
+            write!(f, ", tasks go to {}", successor)?;
+        }
+        writeln!(f)?;
+
+        for logged in &self.steps {
+            match logged.status {
+                StepStatus::Pending => writeln!(f, "pending  {}", logged.step)?,
+                StepStatus::Done => writeln!(f, "done     {}", logged.step)?,
+                StepStatus::Failed => writeln!(
+                    f,
+                    "failed   {}: {}",
+                    logged.step,
+                    logged.error.as_deref().unwrap_or("unknown error")
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Settings for [`UsersAPI::offboard`]
+#[derive(Clone, Debug, Default)]
+pub struct OffboardOptions {
+    keep_account: bool,
+    include_closed: bool,
+}
+
+impl OffboardOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leave the user in the company after removing them everywhere else
+    pub fn keep_account(mut self, keep: bool) -> Self {
+        self.keep_account = keep;
+        self
+    }
+
+    /// Also reassign completed and archived tasks
+    pub fn include_closed(mut self, include: bool) -> Self {
+        self.include_closed = include;
+        self
+    }
+}
+
+/// Where a new user is added by [`UsersAPI::onboard`]
+#[derive(Clone, Debug, Default)]
+pub struct OnboardTemplate {
+    is_admin: bool,
+    projects: Vec<(String, MemberRole)>,
+    departments: Vec<(String, String)>,
+    group_chats: Vec<(String, String)>,
+}
+
+impl OnboardTemplate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Invite the user as a company administrator
+    pub fn admin(mut self, is_admin: bool) -> Self {
+        self.is_admin = is_admin;
+        self
+    }
+
+    pub fn project(mut self, project_id: &str, role: impl Into<MemberRole>) -> Self {
+        self.projects.push((project_id.to_string(), role.into()));
+        self
+    }
+
+    /// Add to a department as `manager` or `member`
+    pub fn department(mut self, department_id: &str, role: &str) -> Self {
+        self.departments
+            .push((department_id.to_string(), role.to_string()));
+        self
+    }
+
+    /// Add to a group chat with a role defined in the chat
+    pub fn group_chat(mut self, chat_id: &str, role_id: &str) -> Self {
+        self.group_chats
+            .push((chat_id.to_string(), role_id.to_string()));
+        self
+    }
+}
+
+/// Planned onboarding or offboarding, executed step by step with [`UserWorkflow::run`]
+///
+/// ```rust,no_run
+/// use yougile_sdk::OffboardOptions;
+///
+/// # async fn example(sdk: yougile_sdk::YouGileSDK) -> Result<(), yougile_sdk::SDKError> {
+/// let mut workflow = sdk
+///     .users()
+///     .offboard("user-id", Some("successor-id"), OffboardOptions::new())
+///     .await?
+///     .log_path("offboard-user-id.json");
+/// log::info!("{}", workflow.log());
+/// workflow.run().await?;
+///
+/// // After a failure, continue where the run stopped
+/// sdk.users().resume("offboard-user-id.json").await?.run().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct UserWorkflow {
+    client: Arc<YouGileClient>,
+    log: WorkflowLog,
+    log_path: Option<PathBuf>,
+}
+
+impl UserWorkflow {
+    /// Save the log to `path` after every step so the run can be resumed
+    pub fn log_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.log_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// The plan, or the progress once the workflow has run
+    pub fn log(&self) -> &WorkflowLog {
+        &self.log
+    }
+
+    /// Execute every step that is not done yet, in order
+    ///
+    /// Stops at the first failing step, which is recorded in the log together
+    /// with its error; running again retries it. Steps are safe to repeat, e.g.
+    /// removing a user who is already gone counts as done and inviting reuses a
+    /// user with the same email.
+    pub async fn run(&mut self) -> Result<(), SDKError> {
+        self.save().await?;
+        for index in 0..self.log.steps.len() {
+            if self.log.steps[index].status == StepStatus::Done {
+                continue;
+            }
+
+            let step = self.log.steps[index].step.clone();
+            let result = self.execute(&step).await;
+            let logged = &mut self.log.steps[index];
+            logged.finished_at = Some(now_ms());
+            match result {
+                Ok(()) => {
+                    logged.status = StepStatus::Done;
+                    logged.error = None;
+                }
+                Err(error) => {
+                    log::warn!("Step '{}' failed: {}", step, error);
+                    logged.status = StepStatus::Failed;
+                    logged.error = Some(error.to_string());
+                    self.save().await?;
+                    return Err(error);
+                }
+            }
+            self.save().await?;
+        }
+        Ok(())
+    }
+
+    async fn execute(&mut self, step: &UserStep) -> Result<(), SDKError> {
+        if let UserStep::Invite { email, is_admin } = step {
+            if self.log.user_id.is_none() {
+                let users = UsersAPI::new(self.client.clone());
+                let id = match users.find_by_email(email).await? {
+                    Some(user) => user.id,
+                    None => {
+                        users
+                            .create(CreateUser {
+                                email: email.clone(),
+                                is_admin: Some(*is_admin),
+                            })
+                            .await?
+                            .id
+                    }
+                };
+                self.log.user_id = Some(id);
+            }
+            return Ok(());
+        }
+
+        let user_id = self
+            .log
+            .user_id
+            .clone()
+            .ok_or_else(|| SDKError::validation("The workflow has no user ID"))?;
+        match step {
+            UserStep::Invite { .. } => Ok(()),
+            UserStep::AddToProject { project_id, role } => {
+                ProjectsAPI::new(self.client.clone())
+                    .members(project_id)
+                    .add(&user_id, role.as_str())
+                    .await
+            }
+            UserStep::AddToDepartment {
+                department_id,
+                role,
+            } => {
+                self.set_department_role(department_id, &user_id, role)
+                    .await
+            }
+            UserStep::AddToGroupChat { chat_id, role_id } => {
+                GroupChatsAPI::new(self.client.clone())
+                    .add_member(chat_id, &user_id, role_id)
+                    .await
+            }
+            UserStep::ReassignTask { task_id, .. } => {
+                let tasks = TasksAPI::new(self.client.clone());
+                let task = tasks.get(task_id).await?;
+                let current = task.assigned.unwrap_or_default();
+                let mut assigned: Vec<String> = current
+                    .iter()
+                    .filter(|id| **id != user_id)
+                    .cloned()
+                    .collect();
+                if let Some(successor) = &self.log.successor
+                    && !assigned.contains(successor)
+                {
+                    assigned.push(successor.clone());
+                }
+                if assigned != current {
+                    tasks
+                        .update(
+                            task_id,
+                            UpdateTask {
+                                assigned: Some(assigned),
+                                ..Default::default()
+                            },
+                        )
+                        .await?;
+                }
+                Ok(())
+            }
+            UserStep::RemoveFromProject { project_id } => ignore_missing(
+                ProjectsAPI::new(self.client.clone())
+                    .members(project_id)
+                    .remove(&user_id)
+                    .await,
+            ),
+            UserStep::RemoveFromDepartment { department_id } => {
+                self.set_department_role(department_id, &user_id, UNSET)
+                    .await
+            }
+            UserStep::RemoveFromGroupChat { chat_id } => ignore_missing(
+                GroupChatsAPI::new(self.client.clone())
+                    .remove_member(chat_id, &user_id)
+                    .await,
+            ),
+            UserStep::DeleteUser => {
+                let users = UsersAPI::new(self.client.clone());
+                let mut search = users.search();
+                if let Some(email) = &self.log.email {
+                    search = search.email(email);
+                }
+                // A user who is already gone counts as deleted
+                if search.all().await?.iter().any(|user| user.id == user_id) {
+                    users.delete(&user_id).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    async fn set_department_role(
+        &self,
+        department_id: &str,
+        user_id: &str,
+        role: &str,
+    ) -> Result<(), SDKError> {
+        DepartmentsAPI::new(self.client.clone())
+            .update(
+                department_id,
+                UpdateDepartment {
+                    users: Some([(user_id.to_string(), role.to_string())].into()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn save(&self) -> Result<(), SDKError> {
+        match &self.log_path {
+            Some(path) => state::save(path, &self.log).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl UsersAPI {
+    /// Plan the offboarding of a user
+    ///
+    /// The plan reassigns the user's open tasks to `successor` (or unassigns
+    /// them without one), removes the user from every group chat, department
+    /// and project, and finally deletes the user. Nothing changes until the
+    /// returned workflow is run.
+    pub async fn offboard(
+        &self,
+        user_id: &str,
+        successor: Option<&str>,
+        options: OffboardOptions,
+    ) -> Result<UserWorkflow, SDKError> {
+        let user = self.get(user_id).await?;
+        if let Some(successor) = successor {
+            if successor == user_id {
+                return Err(SDKError::validation("A user cannot be their own successor"));
+            }
+            self.get(successor).await?;
+        }
+
+        let mut steps = Vec::new();
+
+        let mut tasks = TasksAPI::new(self.client.clone())
+            .search()
+            .assigned_to(user_id)
+            .all()
+            .await?;
+        tasks.retain(|task| {
+            task.deleted != Some(true)
+                && (options.include_closed
+                    || (task.completed != Some(true) && task.archived != Some(true)))
+        });
+        tasks.sort_by(|a, b| a.title.cmp(&b.title));
+        steps.extend(tasks.into_iter().map(|task| UserStep::ReassignTask {
+            task_id: task.id,
+            title: task.title,
+        }));
+
+        let chats = GroupChatsAPI::new(self.client.clone()).list_all().await?;
+        steps.extend(
+            chats
+                .iter()
+                .filter(|chat| {
+                    chat.deleted != Some(true) && GroupChatMembers::from(*chat).is_member(user_id)
+                })
+                .map(|chat| UserStep::RemoveFromGroupChat {
+                    chat_id: chat.id.clone(),
+                }),
+        );
+
+        let departments = DepartmentsAPI::new(self.client.clone()).list_all().await?;
+        steps.extend(
+            departments
+                .iter()
+                .filter(|department| {
+                    department.deleted != Some(true)
+                        && department
+                            .users
+                            .as_ref()
+                            .is_some_and(|users| users.contains_key(user_id))
+                })
+                .map(|department| UserStep::RemoveFromDepartment {
+                    department_id: department.id.clone(),
+                }),
+        );
+
+        let projects = ProjectsAPI::new(self.client.clone()).list_all().await?;
+        steps.extend(
+            projects
+                .iter()
+                .filter(|project| {
+                    project.deleted != Some(true)
+                        && project
+                            .users
+                            .as_ref()
+                            .is_some_and(|users| users.contains_key(user_id))
+                })
+                .map(|project| UserStep::RemoveFromProject {
+                    project_id: project.id.clone(),
+                }),
+        );
+
+        if !options.keep_account {
+            steps.push(UserStep::DeleteUser);
+        }
+
+        let mut log = WorkflowLog::new(WorkflowKind::Offboard, steps);
+        log.user_id = Some(user.id);
+        log.email = Some(user.email);
+        log.successor = successor.map(str::to_string);
+        Ok(self.workflow(log))
+    }
+
+    /// Plan the onboarding of a user from a template
+    ///
+    /// The plan invites the user, unless a user with this email already
+    /// exists, and adds them to the projects, departments and group chats of
+    /// the template. Nothing changes until the returned workflow is run.
+    pub async fn onboard(
+        &self,
+        email: &str,
+        template: &OnboardTemplate,
+    ) -> Result<UserWorkflow, SDKError> {
+        let existing = self.find_by_email(email).await?;
+
+        let mut steps = Vec::new();
+        if existing.is_none() {
+            steps.push(UserStep::Invite {
+                email: email.to_string(),
+                is_admin: template.is_admin,
+            });
+        }
+        steps.extend(
+            template
+                .projects
+                .iter()
+                .map(|(project_id, role)| UserStep::AddToProject {
+                    project_id: project_id.clone(),
+                    role: role.to_string(),
+                }),
+        );
+        steps.extend(template.departments.iter().map(|(department_id, role)| {
+            UserStep::AddToDepartment {
+                department_id: department_id.clone(),
+                role: role.clone(),
+            }
+        }));
+        steps.extend(template.group_chats.iter().map(|(chat_id, role_id)| {
+            UserStep::AddToGroupChat {
+                chat_id: chat_id.clone(),
+                role_id: role_id.clone(),
+            }
+        }));
+
+        let mut log = WorkflowLog::new(WorkflowKind::Onboard, steps);
+        log.user_id = existing.map(|user| user.id);
+        log.email = Some(email.to_string());
+        Ok(self.workflow(log))
+    }
+
+    /// Continue a workflow from the log saved at `path`
+    pub async fn resume(&self, path: impl AsRef<Path>) -> Result<UserWorkflow, SDKError> {
+        let path = path.as_ref();
+        let log: Option<WorkflowLog> = state::load(path).await?;
+        let log =
+            log.ok_or_else(|| SDKError::not_found(format!("workflow log {}", path.display())))?;
+        Ok(self.workflow(log).log_path(path))
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, SDKError> {
+        Ok(self
+            .search()
+            .email(email)
+            .all()
+            .await?
+            .into_iter()
+            .find(|user| user.email.eq_ignore_ascii_case(email)))
+    }
+
+    fn workflow(&self, log: WorkflowLog) -> UserWorkflow {
+        UserWorkflow {
+            client: self.client.clone(),
+            log,
+            log_path: None,
+        }
+    }
+}
+
+/// Treat a missing membership as already removed
+fn ignore_missing(result: Result<(), SDKError>) -> Result<(), SDKError> {
+    match result {
+        Err(SDKError::NotFound(_)) => Ok(()),
+        other => other,
+    }
+}
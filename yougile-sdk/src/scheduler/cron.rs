@@ -0,0 +1,361 @@
+use crate::SDKError;
+use crate::time::{DAY_MS, MINUTE_MS, civil_from_days};
+use std::fmt;
+use std::str::FromStr;
+
+/// Days searched for the next occurrence before giving up
+const SEARCH_DAYS: i64 = 366 * 5;
+
+/// A cron schedule: `minute hour day-of-month month day-of-week`
+///
+/// Fields accept `*`, numbers, ranges (`1-5`), lists (`1,15`) and steps
+/// (`*/15`, `0-30/10`). Days of the week are 0-7 (Sunday is 0 and 7) or
+/// `mon`..`sun`, months are 1-12 or `jan`..`dec`. The aliases `@hourly`,
+/// `@daily`, `@weekly` (Monday 00:00), `@monthly` and `@yearly` are supported.
+/// Times are UTC shifted by the configured offset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days: Vec<u32>,
+    months: Vec<u32>,
+    weekdays: Vec<u32>,
+    any_day: bool,
+    any_weekday: bool,
+    utc_offset_minutes: i32,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, SDKError> {
+        let expanded = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * mon",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minutes, hours, days, months, weekdays] = fields[..] else {
+            return Err(SDKError::validation(format!(
+                "Cron expression '{}' must have 5 fields",
+                expression
+            )));
+        };
+
+        let any_day = days == "*";
+        let any_weekday = weekdays == "*";
+        let mut weekdays = parse_field(weekdays, 0, 7, WEEKDAYS)?;
+        // Sunday may be written as 7
+        for day in &mut weekdays {
+            if *day == 7 {
+                *day = 0;
+            }
+        }
+        weekdays.sort_unstable();
+        weekdays.dedup();
+
+        Ok(Self {
+            expression: expression.trim().to_string(),
+            minutes: parse_field(minutes, 0, 59, &[])?,
+            hours: parse_field(hours, 0, 23, &[])?,
+            any_day,
+            days: parse_field(days, 1, 31, &[])?,
+            months: parse_field(months, 1, 12, MONTHS)?,
+            any_weekday,
+            weekdays,
+            utc_offset_minutes: 0,
+        })
+    }
+
+    /// Interpret the schedule in a time zone `minutes` ahead of UTC
+    pub fn with_utc_offset(mut self, minutes: i32) -> Self {
+        self.utc_offset_minutes = minutes;
+        self
+    }
+
+    /// First occurrence strictly after `after`, in milliseconds
+    pub fn next_after(&self, after: f64) -> Option<f64> {
+        let offset = self.utc_offset_minutes as f64 * MINUTE_MS;
+        let local = after + offset;
+        let first_day = (local / DAY_MS).floor() as i64;
+
+        for day in first_day..first_day + SEARCH_DAYS {
+            if !self.matches_day(day) {
+                continue;
+            }
+            for hour in &self.hours {
+                for minute in &self.minutes {
+                    let at = day as f64 * DAY_MS + (*hour * 60 + *minute) as f64 * MINUTE_MS;
+                    if at > local {
+                        return Some(at - offset);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// At most `limit` occurrences in `(after, until]`, oldest first
+    pub fn between(&self, after: f64, until: f64, limit: usize) -> Vec<f64> {
+        std::iter::successors(self.next_after(after), |at| self.next_after(*at))
+            .take_while(|at| *at <= until)
+            .take(limit)
+            .collect()
+    }
+
+    /// Latest occurrence in `(after, until]`, searching back from `until`
+    pub fn last_between(&self, after: f64, until: f64) -> Option<f64> {
+        let offset = self.utc_offset_minutes as f64 * MINUTE_MS;
+        let (after, until) = (after + offset, until + offset);
+        let last_day = (until / DAY_MS).floor() as i64;
+        let first_day = ((after / DAY_MS).floor() as i64).max(last_day - SEARCH_DAYS);
+
+        for day in (first_day..=last_day).rev() {
+            if !self.matches_day(day) {
+                continue;
+            }
+            for hour in self.hours.iter().rev() {
+                for minute in self.minutes.iter().rev() {
+                    let at = day as f64 * DAY_MS + (*hour * 60 + *minute) as f64 * MINUTE_MS;
+                    if at <= after {
+                        return None;
+                    }
+                    if at <= until {
+                        return Some(at - offset);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, day: i64) -> bool {
+        let (_, month, day_of_month) = civil_from_days(day);
+        // 1970-01-01 was a Thursday
+        let weekday = (day + 4).rem_euclid(7) as u32;
+
+        if !self.months.contains(&month) {
+            return false;
+        }
+
+        let day_matches = self.days.contains(&day_of_month);
+        let weekday_matches = self.weekdays.contains(&weekday);
+        // As in cron, a restricted day of month and day of week match either way
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday_matches,
+            (false, true) => day_matches,
+            (false, false) => day_matches || weekday_matches,
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = SDKError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        CronSchedule::parse(expression)
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+const WEEKDAYS: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+const MONTHS: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<Vec<u32>, SDKError> {
+    let invalid = || SDKError::validation(format!("Invalid cron field '{}'", field));
+
+    let value = |text: &str| -> Result<u32, SDKError> {
+        let lower = text.to_lowercase();
+        // Names are listed from the smallest value of the field
+        let named = names
+            .iter()
+            .position(|name| *name == lower)
+            .map(|index| index as u32 + min);
+        let number = named.or_else(|| text.parse().ok()).ok_or_else(invalid)?;
+        (min..=max)
+            .contains(&number)
+            .then_some(number)
+            .ok_or_else(invalid)
+    };
+
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(invalid());
+        }
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                None if step > 1 => (value(range)?, max),
+                None => (value(range)?, value(range)?),
+            },
+        };
+        if start > end {
+            return Err(invalid());
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::{HOUR_MS, parse_date_ms};
+
+    /// Timestamp of a UTC date (2026-01-01 is a Thursday) plus hours and minutes
+    fn at(date: &str, hour: u32, minute: u32) -> f64 {
+        parse_date_ms(date).unwrap() + hour as f64 * HOUR_MS + minute as f64 * MINUTE_MS
+    }
+
+    fn next(expression: &str, after: f64) -> Option<f64> {
+        CronSchedule::parse(expression).unwrap().next_after(after)
+    }
+
+    #[test]
+    fn steps_and_ranges() {
+        assert_eq!(
+            next("*/15 * * * *", at("2026-01-01", 0, 7)),
+            Some(at("2026-01-01", 0, 15))
+        );
+        assert_eq!(
+            next("0-30/10 8 * * *", at("2026-01-01", 8, 20)),
+            Some(at("2026-01-01", 8, 30))
+        );
+        assert_eq!(
+            next("0 9 * * mon-fri", at("2026-01-02", 10, 0)),
+            Some(at("2026-01-05", 9, 0))
+        );
+    }
+
+    #[test]
+    fn next_is_strictly_after() {
+        assert_eq!(
+            next("0 12 * * *", at("2026-01-01", 12, 0)),
+            Some(at("2026-01-02", 12, 0))
+        );
+    }
+
+    #[test]
+    fn aliases_and_names() {
+        assert_eq!(
+            next("@weekly", at("2026-01-01", 0, 0)),
+            Some(at("2026-01-05", 0, 0))
+        );
+        assert_eq!(
+            next("@monthly", at("2026-01-01", 0, 0)),
+            Some(at("2026-02-01", 0, 0))
+        );
+        assert_eq!(
+            next("0 0 1 MAR *", at("2026-01-01", 0, 0)),
+            Some(at("2026-03-01", 0, 0))
+        );
+        // Sunday can be written as 0 or 7
+        assert_eq!(
+            CronSchedule::parse("0 0 * * 7").unwrap().next_after(0.0),
+            CronSchedule::parse("0 0 * * sun").unwrap().next_after(0.0)
+        );
+    }
+
+    #[test]
+    fn day_of_month_or_weekday() {
+        // Friday the 2nd matches the weekday before the 13th comes
+        assert_eq!(
+            next("0 0 13 * fri", at("2026-01-01", 0, 0)),
+            Some(at("2026-01-02", 0, 0))
+        );
+        assert_eq!(next("0 0 31 2 *", at("2026-01-01", 0, 0)), None);
+    }
+
+    #[test]
+    fn utc_offset_shifts_local_times() {
+        let schedule = CronSchedule::parse("0 9 * * *")
+            .unwrap()
+            .with_utc_offset(180);
+        assert_eq!(
+            schedule.next_after(at("2026-01-01", 0, 0)),
+            Some(at("2026-01-01", 6, 0))
+        );
+    }
+
+    #[test]
+    fn between_includes_the_end() {
+        let schedule = CronSchedule::parse("0 */6 * * *").unwrap();
+        assert_eq!(
+            schedule.between(at("2026-01-01", 0, 0), at("2026-01-02", 0, 0), 10),
+            vec![
+                at("2026-01-01", 6, 0),
+                at("2026-01-01", 12, 0),
+                at("2026-01-01", 18, 0),
+                at("2026-01-02", 0, 0),
+            ]
+        );
+        assert_eq!(
+            schedule.between(at("2026-01-01", 0, 0), at("2026-01-02", 0, 0), 2),
+            vec![at("2026-01-01", 6, 0), at("2026-01-01", 12, 0)]
+        );
+    }
+
+    #[test]
+    fn last_between_finds_the_latest_occurrence() {
+        let schedule = CronSchedule::parse("*/5 * * * *").unwrap();
+        assert_eq!(
+            schedule.last_between(at("2025-01-01", 0, 0), at("2026-01-01", 10, 7)),
+            Some(at("2026-01-01", 10, 5))
+        );
+        assert_eq!(
+            schedule.last_between(at("2026-01-01", 10, 5), at("2026-01-01", 10, 9)),
+            None
+        );
+
+        let offset = CronSchedule::parse("0 9 * * *")
+            .unwrap()
+            .with_utc_offset(180);
+        assert_eq!(
+            offset.last_between(at("2026-01-01", 0, 0), at("2026-01-03", 7, 0)),
+            Some(at("2026-01-03", 6, 0))
+        );
+        assert_eq!(
+            offset.last_between(at("2026-01-03", 6, 0), at("2026-01-03", 7, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expression in [
+            "60 * * * *",
+            "* * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "0 0 * foo *",
+            "0 0 0 * *",
+        ] {
+            assert!(
+                CronSchedule::parse(expression).is_err(),
+                "{} should fail",
+                expression
+            );
+        }
+    }
+}
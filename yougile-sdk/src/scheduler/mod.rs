@@ -0,0 +1,263 @@
+//! Local scheduler for recurring tasks
+//!
+//! YouGile's own repeating tasks cannot be set up through the API, so the
+//! scheduler creates tasks from templates on cron-like rules:
+//!
+//! ```rust,no_run
+//! use yougile_sdk::scheduler::{RecurringRule, Scheduler};
+//! use yougile_sdk::{CreateTask, YouGileSDK};
+//!
+//! # async fn example(sdk: YouGileSDK) -> Result<(), yougile_sdk::SDKError> {
+//! let mut scheduler = sdk.scheduler("scheduler-state.json").await?;
+//! scheduler.add_rule(RecurringRule::new(
+//!     "weekly-report",
+//!     "0 9 * * mon",
+//!     CreateTask {
+//!         title: "Weekly report".into(),
+//!         column_id: Some("column-id".into()),
+//!         ..Default::default()
+//!     },
+//! )?
+//! .with_utc_offset(180));
+//! scheduler.run_due(false).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod cron;
+
+pub use cron::CronSchedule;
+
+use crate::SDKError;
+use crate::resources::tasks::TaskSearchBuilder;
+use crate::state;
+use crate::time::now_ms;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use yougile_api_client::YouGileClient;
+use yougile_api_client::models::*;
+
+/// A task template created on a schedule
+#[derive(Clone, Debug)]
+pub struct RecurringRule {
+    /// Unique name, used as the key of the persisted state
+    pub name: String,
+    pub schedule: CronSchedule,
+    pub template: CreateTask,
+}
+
+impl RecurringRule {
+    pub fn new(
+        name: impl Into<String>,
+        schedule: &str,
+        template: CreateTask,
+    ) -> Result<Self, SDKError> {
+        if template.title.trim().is_empty() {
+            return Err(SDKError::validation("Recurring task title is required"));
+        }
+        if template.column_id.is_none() {
+            return Err(SDKError::validation("Recurring task column is required"));
+        }
+
+        Ok(Self {
+            name: name.into(),
+            schedule: CronSchedule::parse(schedule)?,
+            template,
+        })
+    }
+
+    /// Interpret the schedule in a time zone `minutes` ahead of UTC
+    pub fn with_utc_offset(mut self, minutes: i32) -> Self {
+        self.schedule = self.schedule.with_utc_offset(minutes);
+        self
+    }
+}
+
+/// Persisted time of the last handled occurrence per rule
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchedulerState {
+    pub last_runs: HashMap<String, f64>,
+}
+
+/// An upcoming occurrence of a rule
+#[derive(Clone, Debug, PartialEq)]
+pub struct Occurrence {
+    pub rule: String,
+    pub at: f64,
+}
+
+/// What happened to a due occurrence
+#[derive(Debug)]
+pub enum RunOutcome {
+    Created(String),
+    /// An open task with the same title already exists in the column
+    SkippedOpen(String),
+    /// Dry run: the task would have been created
+    Planned,
+    Failed(SDKError),
+}
+
+#[derive(Debug)]
+pub struct RunEntry {
+    pub rule: String,
+    pub occurrence: f64,
+    pub outcome: RunOutcome,
+}
+
+/// Creates tasks from [`RecurringRule`]s and remembers which occurrences were handled
+pub struct Scheduler {
+    client: Arc<YouGileClient>,
+    state_path: PathBuf,
+    state: SchedulerState,
+    rules: Vec<RecurringRule>,
+}
+
+impl Scheduler {
+    /// Create a scheduler, loading its state from `state_path` if the file exists
+    pub async fn load(
+        client: Arc<YouGileClient>,
+        state_path: impl AsRef<Path>,
+    ) -> Result<Self, SDKError> {
+        let state_path = state_path.as_ref().to_path_buf();
+        let state = state::load(&state_path).await?;
+
+        Ok(Self {
+            client,
+            state_path,
+            state,
+            rules: Vec::new(),
+        })
+    }
+
+    pub fn add_rule(&mut self, rule: RecurringRule) {
+        self.rules.retain(|existing| existing.name != rule.name);
+        self.rules.push(rule);
+    }
+
+    pub fn rules(&self) -> &[RecurringRule] {
+        &self.rules
+    }
+
+    pub fn state(&self) -> &SchedulerState {
+        &self.state
+    }
+
+    /// The next `count` occurrences of every rule after `from`, sorted by time
+    pub fn preview(&self, from: f64, count: usize) -> Vec<Occurrence> {
+        let mut occurrences: Vec<Occurrence> = self
+            .rules
+            .iter()
+            .flat_map(|rule| {
+                std::iter::successors(rule.schedule.next_after(from), |at| {
+                    rule.schedule.next_after(*at)
+                })
+                .take(count)
+                .map(|at| Occurrence {
+                    rule: rule.name.clone(),
+                    at,
+                })
+            })
+            .collect();
+        occurrences.sort_by(|a, b| a.at.total_cmp(&b.at));
+        occurrences.truncate(count);
+        occurrences
+    }
+
+    /// Create a task for every rule with an occurrence since its last run
+    ///
+    /// Missed occurrences are collapsed into one task. A rule without a recorded
+    /// last run starts tracking now and creates nothing. With `dry_run`, nothing
+    /// is created and the state is left unchanged.
+    pub async fn run_due(&mut self, dry_run: bool) -> Result<Vec<RunEntry>, SDKError> {
+        let now = now_ms();
+        let mut entries = Vec::new();
+        let mut state = self.state.clone();
+
+        for rule in &self.rules {
+            let Some(last_run) = state.last_runs.get(&rule.name).copied() else {
+                state.last_runs.insert(rule.name.clone(), now);
+                continue;
+            };
+            let Some(occurrence) = rule.schedule.last_between(last_run, now) else {
+                continue;
+            };
+
+            let outcome = if dry_run {
+                RunOutcome::Planned
+            } else {
+                self.create(rule).await
+            };
+            if !matches!(outcome, RunOutcome::Failed(_)) {
+                state.last_runs.insert(rule.name.clone(), occurrence);
+            }
+            entries.push(RunEntry {
+                rule: rule.name.clone(),
+                occurrence,
+                outcome,
+            });
+        }
+
+        if !dry_run && state != self.state {
+            self.state = state;
+            self.save().await?;
+        }
+        Ok(entries)
+    }
+
+    /// Call [`Scheduler::run_due`] every `interval` until an error occurs
+    pub async fn run(&mut self, interval: Duration) -> Result<(), SDKError> {
+        loop {
+            for entry in self.run_due(false).await? {
+                match entry.outcome {
+                    RunOutcome::Failed(error) => {
+                        log::warn!("Recurring task '{}' failed: {}", entry.rule, error)
+                    }
+                    outcome => log::info!("Recurring task '{}': {:?}", entry.rule, outcome),
+                }
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn create(&self, rule: &RecurringRule) -> RunOutcome {
+        match self.open_instance(rule).await {
+            Ok(Some(existing)) => return RunOutcome::SkippedOpen(existing),
+            Ok(None) => {}
+            Err(error) => return RunOutcome::Failed(error),
+        }
+
+        match self.client.create_task(rule.template.clone()).await {
+            Ok(id) => RunOutcome::Created(id.id),
+            Err(error) => RunOutcome::Failed(error.into()),
+        }
+    }
+
+    /// An uncompleted task with the template's title in the template's column
+    async fn open_instance(&self, rule: &RecurringRule) -> Result<Option<String>, SDKError> {
+        let mut search =
+            TaskSearchBuilder::new(self.client.clone()).title(rule.template.title.clone());
+        if let Some(column_id) = &rule.template.column_id {
+            search = search.column_id(column_id.clone());
+        }
+
+        Ok(search
+            .all()
+            .await?
+            .into_iter()
+            .find(|task| {
+                task.title == rule.template.title
+                    && task.completed != Some(true)
+                    && task.archived != Some(true)
+                    && task.deleted != Some(true)
+            })
+            .map(|task| task.id))
+    }
+
+    async fn save(&self) -> Result<(), SDKError> {
+        state::save(&self.state_path, &self.state).await
+    }
+}
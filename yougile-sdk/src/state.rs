@@ -0,0 +1,78 @@
+//! JSON state files used by long-running jobs to remember what they already did
+
+use crate::SDKError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::ffi::OsString;
+use std::path::Path;
+
+/// Read a state file, returning the default state if it does not exist yet
+pub(crate) async fn load<T: DeserializeOwned + Default>(path: &Path) -> Result<T, SDKError> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(json) => serde_json::from_str(&json).map_err(|error| {
+            SDKError::ConfigurationError(format!(
+                "Invalid state file {}: {}",
+                path.display(),
+                error
+            ))
+        }),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(error) => Err(SDKError::Other(error.to_string())),
+    }
+}
+
+/// Write a state file atomically
+///
+/// The state goes to a temporary file next to the target, which then replaces
+/// it, so an interrupted write never leaves a truncated state file behind.
+pub(crate) async fn save<T: Serialize>(path: &Path, state: &T) -> Result<(), SDKError> {
+    let json =
+        serde_json::to_string_pretty(state).map_err(|error| SDKError::Other(error.to_string()))?;
+
+    let file_name = path.file_name().ok_or_else(|| {
+        SDKError::ConfigurationError(format!("Invalid state file {}", path.display()))
+    })?;
+    let mut temp_name = OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let written = async {
+        tokio::fs::write(&temp_path, json).await?;
+        tokio::fs::rename(&temp_path, path).await
+    }
+    .await;
+    if let Err(error) = written {
+        let _ = tokio::fs::remove_file(&temp_path).await;
+        return Err(SDKError::Other(format!(
+            "Failed to write state file {}: {}",
+            path.display(),
+            error
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn save_replaces_the_file_without_leaving_a_temp_file() {
+        let dir = std::env::temp_dir().join(format!("yougile-state-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("state.json");
+
+        save(&path, &vec![1, 2, 3]).await.unwrap();
+        save(&path, &vec![4]).await.unwrap();
+
+        assert_eq!(load::<Vec<i32>>(&path).await.unwrap(), vec![4]);
+        assert!(!dir.join(".state.json.tmp").exists());
+        assert_eq!(
+            load::<Vec<i32>>(&dir.join("missing.json")).await.unwrap(),
+            Vec::<i32>::new()
+        );
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::sync::{Mutex, Semaphore, SemaphorePermit};
+use tokio::time::Instant;
+
+use yougile_api_client::YouGileClient;
+
+/// YouGile allows 50 requests per minute per company
+const DEFAULT_REQUESTS_PER_MINUTE: u32 = 50;
+
+/// Rate limits by API key; a key belongs to a single company
+static RATE_LIMITS: LazyLock<std::sync::Mutex<HashMap<String, Arc<RateLimit>>>> =
+    LazyLock::new(Default::default);
+
+/// Request slots of one company, shared by all of its throttles
+struct RateLimit {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimit {
+    fn for_client(client: &YouGileClient) -> Arc<Self> {
+        let mut limits = RATE_LIMITS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        limits
+            .entry(client.configuration().token.clone())
+            .or_insert_with(|| {
+                Arc::new(Self {
+                    interval: Duration::from_secs(60) / DEFAULT_REQUESTS_PER_MINUTE,
+                    next_slot: Mutex::new(Instant::now()),
+                })
+            })
+            .clone()
+    }
+
+    async fn wait(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// Limits both the number of in-flight requests and the request rate
+///
+/// Used by operations that fan out many API calls at once. The rate limit is
+/// shared by every throttle created for the same API key, so operations running
+/// side by side stay under the company limit together.
+pub(crate) struct Throttle {
+    permits: Semaphore,
+    rate: Option<Arc<RateLimit>>,
+}
+
+impl Throttle {
+    pub(crate) fn new(client: &YouGileClient, concurrency: usize) -> Self {
+        Self {
+            permits: Semaphore::new(concurrency.max(1)),
+            rate: Some(RateLimit::for_client(client)),
+        }
+    }
+
+    /// Limit only the number of in-flight requests
+    pub(crate) fn unlimited(concurrency: usize) -> Self {
+        Self {
+            permits: Semaphore::new(concurrency.max(1)),
+            rate: None,
+        }
+    }
+
+    /// Wait for a free slot; the request may be sent while the permit is held
+    pub(crate) async fn acquire(&self) -> SemaphorePermit<'_> {
+        // The semaphore is owned by the throttle and never closed
+        let permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("throttle semaphore is never closed");
+
+        if let Some(rate) = &self.rate {
+            rate.wait().await;
+        }
+
+        permit
+    }
+}
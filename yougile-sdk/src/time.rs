@@ -0,0 +1,104 @@
+//! Helpers for the millisecond Unix timestamps used throughout the YouGile API
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use yougile_api_client::models::Stopwatch;
+
+pub(crate) const SECOND_MS: f64 = 1_000.0;
+pub(crate) const MINUTE_MS: f64 = 60.0 * SECOND_MS;
+pub(crate) const HOUR_MS: f64 = 60.0 * MINUTE_MS;
+pub(crate) const DAY_MS: f64 = 24.0 * HOUR_MS;
+pub(crate) const WEEK_MS: f64 = 7.0 * DAY_MS;
+
+/// Current time as a YouGile timestamp (milliseconds since the Unix epoch)
+pub(crate) fn now_ms() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as f64)
+        .unwrap_or_default()
+}
+
+/// Parse a relative duration such as `7d`, `-2h`, `30m` or `1w` into milliseconds
+pub(crate) fn parse_duration_ms(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let unit = value.chars().last()?;
+    let amount: f64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+
+    let unit_ms = match unit {
+        's' => SECOND_MS,
+        'm' => MINUTE_MS,
+        'h' => HOUR_MS,
+        'd' => DAY_MS,
+        'w' => WEEK_MS,
+        _ => return None,
+    };
+
+    Some(amount * unit_ms)
+}
+
+/// Parse a UTC date in `YYYY-MM-DD` form into a timestamp at midnight
+pub(crate) fn parse_date_ms(value: &str) -> Option<f64> {
+    let mut parts = value.trim().splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day) as f64 * DAY_MS)
+}
+
+// Howard Hinnant's days-from-civil algorithm
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Seconds counted by a stopwatch at `now`, including the time since `at_moment` if running
+pub(crate) fn stopwatch_seconds(stopwatch: &Stopwatch, now: f64) -> f64 {
+    if stopwatch.running {
+        stopwatch.seconds + ((now - stopwatch.at_moment) / SECOND_MS).max(0.0)
+    } else {
+        stopwatch.seconds
+    }
+}
+
+/// Format a timestamp as a UTC date in `YYYY-MM-DD` form
+pub(crate) fn format_date(timestamp: f64) -> String {
+    let (year, month, day) = civil_from_days((timestamp / DAY_MS).floor() as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Midnight UTC of the Monday starting the week that contains the timestamp
+pub(crate) fn week_start(timestamp: f64) -> f64 {
+    let days = (timestamp / DAY_MS).floor() as i64;
+    // 1970-01-01 was a Thursday
+    let since_monday = (days + 3).rem_euclid(7);
+    (days - since_monday) as f64 * DAY_MS
+}
+
+// Howard Hinnant's civil-from-days algorithm
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -1,13 +1,15 @@
 use crate::app::{ColumnWithTasks, StickerMeta};
 use crate::config::Config;
 use log::{debug, error, info};
-use std::collections::HashMap;
+use std::sync::Arc;
 use yougile_api_client::YouGileClient;
 use yougile_api_client::apis::configuration::Configuration;
 use yougile_api_client::models::{Board, Project, User};
+use yougile_sdk::Directory;
 
 pub struct YouGileAPI {
-    client: YouGileClient,
+    client: Arc<YouGileClient>,
+    directory: Directory,
 }
 
 impl YouGileAPI {
@@ -16,8 +18,9 @@ impl YouGileAPI {
         let configuration =
             Configuration::new(config.api_token.clone()).with_base_path(&config.api_url);
 
-        let client = YouGileClient::new(configuration);
-        Ok(YouGileAPI { client })
+        let client = Arc::new(YouGileClient::new(configuration));
+        let directory = Directory::new(client.clone());
+        Ok(YouGileAPI { client, directory })
     }
 
     pub async fn fetch_projects(&self) -> Result<Vec<Project>, String> {
@@ -125,15 +128,10 @@ impl YouGileAPI {
     pub async fn fetch_users(&self) -> Result<Vec<User>, String> {
         info!("Fetching users from YouGile API");
 
-        match self
-            .client
-            .search_users(Some(100.0), None, None, None)
-            .await
-        {
-            Ok(page) => {
-                let users = page.content;
+        match self.directory.users().await {
+            Ok(users) => {
                 info!("Successfully fetched {} users", users.len());
-                Ok(users)
+                Ok(users.to_vec())
             }
             Err(e) => {
                 error!("Failed to fetch users: {}", e);
@@ -142,105 +140,35 @@ impl YouGileAPI {
         }
     }
 
-    pub async fn fetch_stickers(&self, board_id: Option<&str>) -> Result<Vec<StickerMeta>, String> {
-        info!(
-            "Fetching all stickers from YouGile API (board filter: {})",
-            board_id.unwrap_or("none")
-        );
+    /// All sprint and string stickers of the company with their states
+    pub async fn fetch_stickers(&self) -> Result<Vec<StickerMeta>, String> {
+        info!("Fetching all stickers from YouGile API");
 
-        let mut all_stickers = Vec::new();
-
-        // Fetch sprint stickers WITHOUT board filter to get all stickers (global + board-specific)
-        match self
-            .client
-            .search_sprint_stickers(None, Some(100.0), None, None, None)
-            .await
-        {
-            Ok(page) => {
-                let count = page.content.len();
-                for sticker in page.content {
-                    let mut states = HashMap::new();
-                    if let Some(sticker_states) = sticker.states {
-                        for state in sticker_states {
-                            debug!(
-                                "Sprint sticker state: id={}, name={}",
-                                state.id, state.data.name
-                            );
-                            states.insert(state.id.clone(), state.data.name.clone());
-                        }
-                    }
-
-                    debug!(
-                        "Sprint sticker: id={}, name={}, states_count={}",
-                        sticker.id,
-                        sticker.data.name,
-                        states.len()
-                    );
-
-                    all_stickers.push(StickerMeta {
-                        id: sticker.id.clone(),
-                        title: sticker.data.name.clone(),
-                        states,
-                    });
-                }
-                info!("Fetched {} sprint stickers", count);
-            }
+        let stickers = match self.directory.stickers().await {
+            Ok(stickers) => stickers,
             Err(e) => {
-                error!("Failed to fetch sprint stickers: {}", e);
-                // Don't fail completely, continue to string stickers
+                error!("Failed to fetch stickers: {}", e);
+                return Err(format!("Failed to fetch stickers: {}", e));
             }
-        }
-
-        // Fetch string stickers WITHOUT board filter to get all stickers (global + board-specific)
-        match self
-            .client
-            .search_string_stickers(None, Some(100.0), None, None, None)
-            .await
-        {
-            Ok(page) => {
-                let count = page.content.len();
-                for sticker in page.content {
-                    let mut states = HashMap::new();
-                    if let Some(sticker_states) = sticker.states {
-                        for state in sticker_states {
-                            debug!(
-                                "String sticker state: id={}, name={}",
-                                state.id, state.data.name
-                            );
-                            states.insert(state.id.clone(), state.data.name.clone());
-                        }
-                    }
-
-                    debug!(
-                        "String sticker: id={}, name={}, states_count={}",
-                        sticker.id,
-                        sticker.data.name,
-                        states.len()
-                    );
+        };
 
-                    all_stickers.push(StickerMeta {
-                        id: sticker.id.clone(),
-                        title: sticker.data.name.clone(),
-                        states,
-                    });
-                }
-                info!("Fetched {} string stickers", count);
-            }
-            Err(e) => {
-                error!("Failed to fetch string stickers: {}", e);
-                // Don't fail completely
-            }
-        }
+        let all_stickers: Vec<StickerMeta> = stickers
+            .iter()
+            .map(|sticker| StickerMeta {
+                id: sticker.id.clone(),
+                title: sticker.name.clone(),
+                states: sticker
+                    .states
+                    .iter()
+                    .map(|state| (state.id.clone(), state.name.clone()))
+                    .collect(),
+            })
+            .collect();
 
         info!(
             "Successfully fetched {} total stickers (sprint + string)",
             all_stickers.len()
         );
-        debug!(
-            "Note: String stickers can have both state-based values (state_id) and free-text values"
-        );
-
-        // Log all loaded stickers for debugging
         for sticker in &all_stickers {
             debug!(
                 "Loaded sticker: id='{}', title='{}', states={}",
@@ -121,7 +121,7 @@ impl App {
         log::info!("Loading all stickers...");
 
         match &self.api {
-            Some(api) => match api.fetch_stickers(None).await {
+            Some(api) => match api.fetch_stickers().await {
                 Ok(stickers) => {
                     self.stickers = stickers.into_iter().map(|s| (s.id.clone(), s)).collect();
                     log::info!("Loaded {} stickers", self.stickers.len());
@@ -182,7 +182,7 @@ fn draw_single_column(
     has_right: bool,
 ) {
     let is_selected = actual_col_idx == app.selected_column_idx;
-    let column_color = get_column_color(column_with_tasks.column.color);
+    let column_color = get_column_color(column_with_tasks.column.color.as_ref());
 
     let border_style = if is_selected {
         Style::default().fg(Color::Green)
@@ -8,6 +8,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 use yougile_api_client::models::StickerValue;
+use yougile_sdk::markup::html_to_text;
 
 pub fn draw_task_detail_panel(f: &mut Frame, app: &App, area: Rect) {
     if let Some(ref task) = app.current_task {
@@ -68,7 +69,8 @@ pub fn draw_task_detail_panel(f: &mut Frame, app: &App, area: Rect) {
 
                 let (display_title, value_str) = if let Some(meta) = sticker_meta {
                     let value = match state_value {
-                        StickerValue::StateId(state_id_or_text) | StickerValue::Text(state_id_or_text) => meta
+                        StickerValue::StateId(state_id_or_text)
+                        | StickerValue::Text(state_id_or_text) => meta
                             .states
                             .get(state_id_or_text)
                             .cloned()
@@ -153,7 +155,9 @@ pub fn draw_task_detail_panel(f: &mut Frame, app: &App, area: Rect) {
 
         if let Some(ref description) = task.description {
             if !description.is_empty() {
-                details.push(Line::from(description.clone()));
+                for line in html_to_text(description).lines() {
+                    details.push(Line::from(line.to_string()));
+                }
             } else {
                 details.push(Line::from(Span::styled(
                     "No description",